@@ -67,6 +67,7 @@ impl KnowledgeBase {
                     self.features.insert(feature.id.clone(), feature);
                 }
             }
+            self.merge_catalog_features();
         } else {
             // Initialize with default features
             self.initialize_default_features();
@@ -99,56 +100,71 @@ impl KnowledgeBase {
     }
 
     fn initialize_default_features(&mut self) {
-        // Windows tweaks
-        self.features.insert("disable_hpet".to_string(), Feature {
-            id: "disable_hpet".to_string(),
-            name: "Disable HPET".to_string(),
-            category: "Windows".to_string(),
-            description: "HPET é um timer antigo que força o CPU a verificá-lo constantemente. Desactivar reduz input lag.".to_string(),
-            technical_details: "High Precision Event Timer (HPET) adiciona ~2-3ms de latência. CPUs modernos têm TSC (Time Stamp Counter) que é mais eficiente.".to_string(),
-            impact: Impact {
-                fps_gain: None,
-                latency_reduction: Some("-2.5ms average".to_string()),
-                memory_impact: None,
-                requires_restart: true,
-            },
-            requirements: vec!["CPU com suporte TSC (todos os modernos)".to_string()],
-            warnings: vec!["Requer restart".to_string()],
-        });
-
-        self.features.insert("hardware_gpu_scheduling".to_string(), Feature {
-            id: "hardware_gpu_scheduling".to_string(),
-            name: "Hardware-accelerated GPU Scheduling".to_string(),
-            category: "Windows".to_string(),
-            description: "Permite GPU gerir a sua própria memória directamente, reduzindo overhead do CPU.".to_string(),
-            technical_details: "Windows 10 2004+ feature. GPU controla VRAM scheduling em vez do CPU fazer via kernel do Windows.".to_string(),
-            impact: Impact {
-                fps_gain: Some("+5-15 fps".to_string()),
-                latency_reduction: Some("-1ms".to_string()),
-                memory_impact: None,
-                requires_restart: false,
-            },
-            requirements: vec!["Windows 10 2004+".to_string(), "GPU WDDM 2.7+".to_string()],
-            warnings: vec![],
-        });
-
-        self.features.insert("disable_core_parking".to_string(), Feature {
-            id: "disable_core_parking".to_string(),
-            name: "Disable Core Parking".to_string(),
-            category: "Windows".to_string(),
-            description: "Mantém todos os cores do CPU activos em vez de adormecer os não usados.".to_string(),
-            technical_details: "Core parking coloca cores CPU em C-states profundos. Acordá-los adiciona latência. Para gaming, melhor manter todos activos.".to_string(),
-            impact: Impact {
-                fps_gain: Some("+8-12 fps em CPUs 8+ cores".to_string()),
-                latency_reduction: Some("-2-5ms".to_string()),
-                memory_impact: None,
-                requires_restart: false,
-            },
-            requirements: vec!["CPU multi-core (4+ cores)".to_string()],
-            warnings: vec!["Aumenta consumo energia/temperatura".to_string()],
-        });
-
-        // Add more default features...
+        // One feature per entry of the tweak catalog (tweaks.json)
+        self.merge_catalog_features();
+
+        // Measured impact for the tweaks we have data on
+        self.annotate("w_hpet", Impact {
+            fps_gain: None,
+            latency_reduction: Some("-2.5ms average".to_string()),
+            memory_impact: None,
+            requires_restart: true,
+        }, &["CPU com suporte TSC (todos os modernos)"], &["Requer restart"]);
+
+        self.annotate("w_hgs", Impact {
+            fps_gain: Some("+5-15 fps".to_string()),
+            latency_reduction: Some("-1ms".to_string()),
+            memory_impact: None,
+            requires_restart: true,
+        }, &["Windows 10 2004+", "GPU WDDM 2.7+"], &[]);
+
+        self.annotate("w_park", Impact {
+            fps_gain: Some("+8-12 fps em CPUs 8+ cores".to_string()),
+            latency_reduction: Some("-2-5ms".to_string()),
+            memory_impact: None,
+            requires_restart: false,
+        }, &["CPU multi-core (4+ cores)"], &["Aumenta consumo energia/temperatura"]);
+    }
+
+    /// Adds catalog tweaks that are not in the knowledge base yet. Features
+    /// are keyed by the tweak key; `id` is only unique within a section.
+    fn merge_catalog_features(&mut self) {
+        let catalog = crate::tweaks::catalog();
+        for tweak in &catalog.tweaks {
+            let category = catalog
+                .section(&tweak.section)
+                .map(|s| s.title.split(" -- ").next().unwrap_or(&s.title).to_string())
+                .unwrap_or_else(|| tweak.section.clone());
+            let name = tweak.name().to_string();
+            // Knowledge bases saved before features were keyed by tweak key
+            if let Some(mut old) = self.features.remove(&tweak.id) {
+                old.id = tweak.key.clone();
+                self.features.entry(tweak.key.clone()).or_insert(old);
+            }
+            self.features.entry(tweak.key.clone()).or_insert_with(|| Feature {
+                id: tweak.key.clone(),
+                description: tweak.description.clone().unwrap_or_else(|| name.clone()),
+                name,
+                category,
+                technical_details: tweak.notes.clone().unwrap_or_default(),
+                impact: Impact {
+                    fps_gain: None,
+                    latency_reduction: None,
+                    memory_impact: None,
+                    requires_restart: tweak.restart,
+                },
+                requirements: vec![],
+                warnings: vec![],
+            });
+        }
+    }
+
+    fn annotate(&mut self, id: &str, impact: Impact, requirements: &[&str], warnings: &[&str]) {
+        if let Some(f) = self.features.get_mut(id) {
+            f.impact = impact;
+            f.requirements = requirements.iter().map(|r| r.to_string()).collect();
+            f.warnings = warnings.iter().map(|w| w.to_string()).collect();
+        }
     }
 
     pub fn get_feature(&self, id: &str) -> Option<&Feature> {
@@ -122,16 +122,16 @@ impl VoiceHandler {
         for (i, word) in words.iter().enumerate() {
             let word_lower = word.to_lowercase();
             if word_lower == "hpet" {
-                return "disable_hpet".to_string();
+                return "w_hpet".to_string();
             }
             if word_lower.contains("core") && i + 1 < words.len() {
                 if words[i + 1].to_lowercase().contains("park") {
-                    return "disable_core_parking".to_string();
+                    return "w_park".to_string();
                 }
             }
             if word_lower.contains("gpu") && i + 1 < words.len() {
                 if words[i + 1].to_lowercase().contains("schedul") {
-                    return "hardware_gpu_scheduling".to_string();
+                    return "w_hgs".to_string();
                 }
             }
         }
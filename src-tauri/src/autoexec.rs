@@ -0,0 +1,430 @@
+// ────────────────────────────────────────────────────────────────────
+// autoexec.cfg – the lines the agent generates from AutoexecConfig, the
+// way back from a player's cfg into AutoexecConfig, and a three-way
+// merge so regenerating keeps whatever the player added or changed.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::cfg::{self, Statement};
+use crate::{crosshair, cvars, AutoexecConfig};
+use std::collections::{HashMap, HashSet};
+
+const BANNER: [&str; 3] = [
+    "// ═══════════════════════════════════════════════════════",
+    "// ★  aim.camp Player Agent — CS2 autoexec.cfg  ★",
+    "// ═══════════════════════════════════════════════════════",
+];
+
+/// Lines written to autoexec.cfg for this config.
+pub fn generate(ae: &AutoexecConfig) -> Vec<String> {
+    let mut lines: Vec<String> = BANNER.iter().map(|l| l.to_string()).collect();
+    for (name, value) in [
+        ("fps_max", &ae.fps_max),
+        ("rate", &ae.rate),
+        ("cl_interp", &ae.cl_interp),
+        ("cl_interp_ratio", &ae.cl_interp_ratio),
+        ("cl_updaterate", &ae.cl_updaterate),
+        ("cl_cmdrate", &ae.cl_cmdrate),
+    ] {
+        if !value.trim().is_empty() {
+            lines.push(format!("{} {}", name, value.trim()));
+        }
+    }
+    if ae.m_rawinput {
+        lines.push("m_rawinput 1".into());
+    }
+    if let Ok(xh) = crosshair::decode(&ae.crosshair_code) {
+        lines.extend(
+            xh.cvars()
+                .into_iter()
+                .filter(|(name, _)| ae.crosshair_cvars.is_empty() || ae.crosshair_cvars.iter().any(|c| c == name))
+                .map(|(name, value)| format!("{} {}", name, value)),
+        );
+    }
+    lines.extend(
+        ae.custom_commands
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string),
+    );
+    lines
+}
+
+// ── cfg → AutoexecConfig ──
+
+fn field<'a>(ae: &'a mut AutoexecConfig, name: &str) -> Option<&'a mut String> {
+    match name {
+        "fps_max" => Some(&mut ae.fps_max),
+        "rate" => Some(&mut ae.rate),
+        "cl_interp" => Some(&mut ae.cl_interp),
+        "cl_interp_ratio" => Some(&mut ae.cl_interp_ratio),
+        "cl_updaterate" => Some(&mut ae.cl_updaterate),
+        "cl_cmdrate" => Some(&mut ae.cl_cmdrate),
+        _ => None,
+    }
+}
+
+fn is_crosshair_cvar(name: &str) -> bool {
+    crosshair::Crosshair::default().cvars().iter().any(|(n, _)| *n == name)
+}
+
+/// The crosshair a cfg sets, as a share code plus the cl_crosshair* cvars
+/// it set, in catalog order – only when the code reproduces every value
+/// it sets (codes store tenths).
+fn crosshair_from_cfg(file: &cfg::CfgFile) -> Option<(String, Vec<String>)> {
+    let mut values: HashMap<String, String> = HashMap::new();
+    for st in file.lines.iter().flat_map(|l| &l.statements) {
+        if let Statement::Command { name, args } = st {
+            let name = name.to_ascii_lowercase();
+            if let (true, Some(a)) = (is_crosshair_cvar(&name), args.first()) {
+                values.insert(name, a.text.trim().to_string());
+            }
+        }
+    }
+    if values.is_empty() {
+        return None;
+    }
+    let code = crosshair::encode(&crosshair::Crosshair::from_cvars(&values));
+    let decoded = crosshair::decode(&code).ok()?;
+    let cvars = decoded.cvars();
+    let lossless = cvars.iter().all(|(name, decoded)| {
+        values.get(*name).map_or(true, |v| v.parse::<f64>().ok() == decoded.parse::<f64>().ok())
+    });
+    let set = cvars.iter().filter(|(name, _)| values.contains_key(*name)).map(|(name, _)| name.to_string()).collect();
+    lossless.then_some((code, set))
+}
+
+/// Fill the structured fields from a parsed cfg. Statements that don't
+/// map to a field become `custom_commands`, in their original order;
+/// a value the field can't hold (e.g. `fps_max abc`) stays a custom line.
+/// Fields the cfg doesn't set keep their current value, except
+/// `m_rawinput`, which the generator only writes when enabled. A crosshair
+/// taken from the cfg only writes back the cl_crosshair* cvars it set.
+pub fn apply_cfg(ae: &mut AutoexecConfig, file: &cfg::CfgFile) {
+    let mut custom: Vec<String> = Vec::new();
+    ae.m_rawinput = false;
+    let crosshair = crosshair_from_cfg(file);
+
+    for line in &file.lines {
+        if BANNER.contains(&line.raw.trim()) {
+            continue;
+        }
+        let mut kept: Vec<String> = Vec::new();
+        let mut mapped = false;
+        for st in &line.statements {
+            if let Statement::Command { name, args } = st {
+                let name = name.to_ascii_lowercase();
+                let value = args.first().map(|a| a.text.trim()).unwrap_or("");
+                if crosshair.is_some() && is_crosshair_cvar(&name) {
+                    mapped = true;
+                    continue;
+                }
+                if name == "m_rawinput" && matches!(value, "0" | "1") {
+                    ae.m_rawinput = value == "1";
+                    mapped = true;
+                    continue;
+                }
+                if crate::ps::number(&name, value).is_ok() {
+                    if let Some(f) = field(ae, &name) {
+                        *f = value.to_string();
+                        mapped = true;
+                        continue;
+                    }
+                }
+            }
+            kept.push(st.to_string());
+        }
+        if !mapped {
+            // Untouched lines are kept verbatim, comments and all
+            if !line.raw.trim().is_empty() {
+                custom.push(line.raw.trim().to_string());
+            }
+        } else if !kept.is_empty() {
+            let mut rest = kept.join("; ");
+            if let Some(c) = &line.comment {
+                rest.push_str(&format!(" //{}", c));
+            }
+            custom.push(rest);
+        }
+    }
+    if let Some((code, cvars)) = crosshair {
+        ae.crosshair_code = code;
+        ae.crosshair_cvars = cvars;
+    }
+    ae.enabled = true;
+    ae.custom_commands = custom.join("\n");
+}
+
+// ── Three-way merge ──
+
+#[derive(serde::Serialize, Clone)]
+pub struct Conflict {
+    /// Cvar name, `bind <key>` or `alias <name>`
+    pub name: String,
+    /// Line in the merged file, 0 when the line was left out
+    pub line: usize,
+    pub base: Option<String>,
+    /// The player's line (kept), if any
+    pub current: Option<String>,
+    /// What the agent generates now, if any
+    pub generated: Option<String>,
+    pub reason: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct Merge {
+    pub content: String,
+    pub conflicts: Vec<Conflict>,
+    /// False when there was no previously generated version to compare with
+    pub has_base: bool,
+}
+
+struct Entry {
+    key: String,
+    name: String,
+    /// Whitespace-normalised, for comparing
+    text: String,
+    raw: String,
+}
+
+/// Key a line by what it sets, so a changed value is the same line in
+/// all three versions. Lines that set nothing in particular (comments,
+/// chains, one-off commands) are keyed by their text.
+fn entries(content: &str) -> Vec<Entry> {
+    let db = cvars::builtin();
+    let file = cfg::parse(content);
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut out = Vec::new();
+    for line in &file.lines {
+        let text = line.raw.split_whitespace().collect::<Vec<_>>().join(" ");
+        let (key, name) = match line.statements.as_slice() {
+            [Statement::Command { name, args }]
+                if !args.is_empty()
+                    && db.get(name).map_or(true, |c| c.kind != cvars::CvarType::Command) =>
+            {
+                (format!("set:{}", name.to_ascii_lowercase()), name.clone())
+            }
+            [Statement::Bind { key, .. }] => {
+                (format!("bind:{}", key.to_ascii_lowercase()), format!("bind {}", key))
+            }
+            [Statement::Alias { name, .. }] => {
+                (format!("alias:{}", name.to_ascii_lowercase()), format!("alias {}", name))
+            }
+            _ => (format!("line:{}", text), text.clone()),
+        };
+        // Repeats (blank lines, a cvar set twice) are told apart by count
+        let n = seen.entry(key.clone()).or_insert(0);
+        *n += 1;
+        out.push(Entry { key: format!("{}#{}", key, n), name, text, raw: line.raw.trim().to_string() });
+    }
+    out
+}
+
+/// Merge the newly generated autoexec into the player's current file.
+/// `base` is the version the agent generated last time; lines the player
+/// hasn't touched since then follow the generator, lines the player
+/// added or changed are kept. When both sides changed a line the
+/// player's version stays and the change is reported as a conflict.
+pub fn merge(base: Option<&str>, current: &str, generated: &str) -> Merge {
+    let base_entries = entries(base.unwrap_or(""));
+    let gen_entries = entries(generated);
+    let cur_entries = entries(current);
+    let base_map: HashMap<&str, &Entry> = base_entries.iter().map(|e| (e.key.as_str(), e)).collect();
+    let gen_map: HashMap<&str, &Entry> = gen_entries.iter().map(|e| (e.key.as_str(), e)).collect();
+    let cur_keys: HashSet<&str> = cur_entries.iter().map(|e| e.key.as_str()).collect();
+
+    let mut merged: Vec<(String, String)> = Vec::new();
+    let mut conflicts: Vec<(usize, Conflict)> = Vec::new();
+    let conflict = |e: &Entry, b: Option<&&Entry>, c: Option<&str>, g: Option<&&Entry>, reason: &str| Conflict {
+        name: e.name.clone(),
+        line: 0,
+        base: b.map(|b| b.raw.clone()),
+        current: c.map(str::to_string),
+        generated: g.map(|g| g.raw.clone()),
+        reason: reason.into(),
+    };
+
+    for cur in &cur_entries {
+        let b = base_map.get(cur.key.as_str());
+        let g = gen_map.get(cur.key.as_str());
+        let player_changed = b.map_or(true, |b| b.text != cur.text);
+        match g {
+            Some(g) if g.text == cur.text => merged.push((cur.key.clone(), cur.raw.clone())),
+            Some(g) if !player_changed => merged.push((cur.key.clone(), g.raw.clone())),
+            Some(g) => {
+                let generator_changed = b.map_or(true, |b| b.text != g.text);
+                if generator_changed {
+                    conflicts.push((
+                        merged.len(),
+                        conflict(cur, b, Some(&cur.raw), Some(g), "Changed by you and by the agent"),
+                    ));
+                }
+                merged.push((cur.key.clone(), cur.raw.clone()));
+            }
+            None if b.is_some() && !player_changed => {} // the agent dropped it
+            None if b.is_some() => {
+                conflicts.push((
+                    merged.len(),
+                    conflict(cur, b, Some(&cur.raw), None, "Changed by you, no longer generated"),
+                ));
+                merged.push((cur.key.clone(), cur.raw.clone()));
+            }
+            None => merged.push((cur.key.clone(), cur.raw.clone())),
+        }
+    }
+
+    // Generated lines the player's file doesn't have: new ones go in after
+    // the line they follow in the generated file; ones the player deleted
+    // stay deleted
+    let mut after: Option<String> = None;
+    for g in &gen_entries {
+        if cur_keys.contains(g.key.as_str()) {
+            after = Some(g.key.clone());
+            continue;
+        }
+        match base_map.get(g.key.as_str()) {
+            Some(b) if b.text == g.text => {}
+            Some(b) => conflicts.push((
+                usize::MAX,
+                conflict(g, Some(b), None, Some(&g), "Removed by you, changed by the agent"),
+            )),
+            None => {
+                let at = after
+                    .as_ref()
+                    .and_then(|k| merged.iter().position(|(key, _)| key == k))
+                    .map_or(0, |i| i + 1);
+                for (i, _) in conflicts.iter_mut() {
+                    if *i != usize::MAX && *i >= at {
+                        *i += 1;
+                    }
+                }
+                merged.insert(at, (g.key.clone(), g.raw.clone()));
+                after = Some(g.key.clone());
+            }
+        }
+    }
+
+    let mut content = merged.into_iter().map(|(_, text)| text).collect::<Vec<_>>().join("\n");
+    content.push('\n');
+    Merge {
+        content,
+        conflicts: conflicts
+            .into_iter()
+            .map(|(i, mut c)| {
+                c.line = if i == usize::MAX { 0 } else { i + 1 };
+                c
+            })
+            .collect(),
+        has_base: base.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AutoexecConfig {
+        AutoexecConfig {
+            enabled: false,
+            fps_max: "400".into(),
+            rate: "786432".into(),
+            cl_interp: "0".into(),
+            cl_interp_ratio: "1".into(),
+            cl_updaterate: "128".into(),
+            cl_cmdrate: "128".into(),
+            m_rawinput: true,
+            crosshair_code: String::new(),
+            crosshair_cvars: Vec::new(),
+            custom_commands: String::new(),
+        }
+    }
+
+    #[test]
+    fn apply_cfg_maps_fields_and_keeps_the_rest() {
+        let mut ae = config();
+        let cfg = cfg::parse(
+            "fps_max 300 // capped\n\
+             rate abc\n\
+             m_rawinput 0\n\
+             bind \"f\" \"+lookatweapon\" // inspect\n\
+             cl_crosshairsize 2; cl_crosshairgap -1\n\
+             volume 0.5; cl_cmdrate 64\n",
+        );
+        apply_cfg(&mut ae, &cfg);
+        assert!(ae.enabled && !ae.m_rawinput);
+        assert_eq!((ae.fps_max.as_str(), ae.rate.as_str(), ae.cl_cmdrate.as_str()), ("300", "786432", "64"));
+        assert_eq!(ae.custom_commands, "rate abc\nbind \"f\" \"+lookatweapon\" // inspect\nvolume 0.5");
+        assert_eq!(ae.crosshair_cvars, ["cl_crosshairsize", "cl_crosshairgap"]);
+
+        // Only the crosshair cvars the cfg set are written back
+        let lines = generate(&ae);
+        let crosshair: Vec<&str> =
+            lines.iter().map(String::as_str).filter(|l| l.starts_with("cl_crosshair")).collect();
+        assert_eq!(crosshair, ["cl_crosshairsize 2", "cl_crosshairgap -1"]);
+        assert!(!lines.iter().any(|l| l.starts_with("m_rawinput")));
+
+        // A code typed in by hand writes all of them
+        ae.crosshair_cvars.clear();
+        assert_eq!(
+            generate(&ae).iter().filter(|l| l.starts_with("cl_crosshair")).count(),
+            crosshair::Crosshair::default().cvars().iter().filter(|(n, _)| n.starts_with("cl_crosshair")).count()
+        );
+    }
+
+    #[test]
+    fn apply_cfg_keeps_fields_the_cfg_does_not_set() {
+        let mut ae = config();
+        ae.crosshair_code = "CSGO-O4Jsi-V36wY-rTMGK-9w7qF-jQ8WB".into();
+        ae.crosshair_cvars = vec!["cl_crosshairsize".into()];
+        apply_cfg(&mut ae, &cfg::parse("sensitivity 1.2\n"));
+        assert_eq!(ae.fps_max, "400");
+        assert_eq!(ae.crosshair_code, "CSGO-O4Jsi-V36wY-rTMGK-9w7qF-jQ8WB");
+        assert_eq!(ae.crosshair_cvars, ["cl_crosshairsize"]);
+        assert_eq!(ae.custom_commands, "sensitivity 1.2");
+    }
+
+    #[test]
+    fn merge_keeps_player_edits_and_follows_the_generator() {
+        let base = "fps_max 400\nrate 786432\ncl_interp 0\ncl_interp_ratio 1\ncl_cmdrate 128\nm_rawinput 1\n";
+        // The player changed rate and cl_cmdrate, removed both interp lines and added a bind
+        let current = "fps_max 400\nrate 500000\ncl_cmdrate 64\nm_rawinput 1\nbind \"f\" \"+lookatweapon\"\n";
+        // The agent changed fps_max, cl_interp and cl_cmdrate and added cl_updaterate
+        let generated =
+            "fps_max 300\nrate 786432\ncl_interp 0.015\ncl_interp_ratio 1\ncl_cmdrate 256\ncl_updaterate 128\nm_rawinput 1\n";
+
+        let m = merge(Some(base), current, generated);
+        assert!(m.has_base);
+        assert_eq!(
+            m.content,
+            "fps_max 300\nrate 500000\ncl_cmdrate 64\ncl_updaterate 128\nm_rawinput 1\nbind \"f\" \"+lookatweapon\"\n"
+        );
+        let conflicts: Vec<(&str, usize, Option<&str>, Option<&str>)> = m
+            .conflicts
+            .iter()
+            .map(|c| (c.name.as_str(), c.line, c.current.as_deref(), c.generated.as_deref()))
+            .collect();
+        assert_eq!(
+            conflicts,
+            [
+                ("cl_cmdrate", 3, Some("cl_cmdrate 64"), Some("cl_cmdrate 256")),
+                ("cl_interp", 0, None, Some("cl_interp 0.015")),
+            ]
+        );
+        assert_eq!(m.conflicts[0].reason, "Changed by you and by the agent");
+        assert_eq!(m.conflicts[1].reason, "Removed by you, changed by the agent");
+    }
+
+    #[test]
+    fn merge_without_base_keeps_the_players_values() {
+        let m = merge(None, "// mine\nfps_max 0\n", "fps_max 400\nrate 786432\n");
+        assert!(!m.has_base);
+        assert_eq!(m.content, "// mine\nfps_max 0\nrate 786432\n");
+        assert_eq!(m.conflicts.len(), 1);
+        assert_eq!((m.conflicts[0].name.as_str(), m.conflicts[0].line), ("fps_max", 2));
+
+        // A line the player changed that the agent no longer generates
+        let m = merge(Some("fps_max 400\nrate 786432\n"), "fps_max 400\nrate 1000000\n", "fps_max 400\n");
+        assert_eq!(m.content, "fps_max 400\nrate 1000000\n");
+        assert_eq!(m.conflicts[0].reason, "Changed by you, no longer generated");
+    }
+}
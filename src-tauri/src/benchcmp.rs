@@ -0,0 +1,539 @@
+// ────────────────────────────────────────────────────────────────────
+// Before/after benchmark comparison. Pools the frame times of the
+// baseline and candidate runs, reports the change of avg FPS, the lows
+// and the frame-time percentiles with bootstrap confidence intervals, and
+// tests the frame-time distributions with Mann–Whitney U. With two or
+// more runs per side the bootstrap draws whole runs first, so the
+// intervals include run-to-run variation; a change only counts when the
+// interval excludes zero and Mann–Whitney agrees.
+// ────────────────────────────────────────────────────────────────────
+
+/// Resamples per interval. Seeded, so the same files give the same CIs.
+const ITERATIONS: usize = 500;
+const CONFIDENCE: f64 = 0.95;
+const SEED: u64 = 0x5eed_a1c0_ca4d_2026;
+/// Runs whose duration differs from the median by more than this are
+/// not comparable
+const DURATION_TOLERANCE_PCT: f64 = 20.0;
+/// Fewer frames than this make the 0.1% low meaningless
+const MIN_FRAMES: usize = 1000;
+
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() { return 0.0; }
+    let idx = (p / 100.0 * (sorted.len() as f64 - 1.0)).max(0.0);
+    let lo = idx.floor() as usize;
+    let hi = (lo + 1).min(sorted.len() - 1);
+    let frac = idx - lo as f64;
+    sorted[lo] * (1.0 - frac) + sorted[hi] * frac
+}
+
+/// One capture: raw (not downsampled) frame times in ms.
+pub struct Run {
+    pub file_name: String,
+    pub process_name: String,
+    pub duration_secs: f64,
+    pub frametimes: Vec<f64>,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct RunInfo {
+    pub file_name: String,
+    pub process_name: String,
+    pub duration_secs: f64,
+    pub frame_count: usize,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct MetricDelta {
+    /// "avg_fps", "p1_fps", "p01_fps", "p50_frametime", ...
+    pub metric: String,
+    pub label: String,
+    pub unit: String,
+    pub higher_is_better: bool,
+    pub baseline: f64,
+    pub candidate: f64,
+    /// candidate - baseline
+    pub delta: f64,
+    pub delta_pct: f64,
+    /// Bootstrap confidence interval of `delta`
+    pub ci_low: f64,
+    pub ci_high: f64,
+    /// The interval excludes zero
+    pub significant: bool,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct MannWhitney {
+    /// U of the candidate frame times
+    pub u: f64,
+    /// Normal approximation with tie correction
+    pub z: f64,
+    /// Two-sided
+    pub p_value: f64,
+    /// Probability that a candidate frame is faster than a baseline frame
+    /// (0.5 = no difference)
+    pub prob_faster: f64,
+}
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Better,
+    Worse,
+    /// Avg FPS and the lows moved in opposite directions
+    Mixed,
+    /// Within noise
+    NoDifference,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct Comparison {
+    pub baseline: Vec<RunInfo>,
+    pub candidate: Vec<RunInfo>,
+    pub metrics: Vec<MetricDelta>,
+    pub mann_whitney: MannWhitney,
+    pub confidence: f64,
+    pub iterations: usize,
+    pub verdict: Verdict,
+    /// One line for the coach, e.g. "+4.1 FPS avg (95% CI +1.2 to +7.0) ..."
+    pub summary: String,
+    /// Reasons the runs may not be comparable
+    pub warnings: Vec<String>,
+}
+
+/// SplitMix64; a fixed seed keeps results reproducible without a rand
+/// dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// How a metric is read from one side's frame times.
+struct Metric {
+    key: &'static str,
+    label: &'static str,
+    unit: &'static str,
+    higher_is_better: bool,
+    value: fn(&Stats) -> f64,
+}
+
+/// Sorted pooled frame times of one side, their per-frame FPS (sorted)
+/// and their mean.
+struct Stats {
+    sorted: Vec<f64>,
+    sorted_fps: Vec<f64>,
+    mean: f64,
+}
+
+impl Stats {
+    fn new(mut frametimes: Vec<f64>) -> Self {
+        frametimes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let mean = if frametimes.is_empty() { 0.0 } else { frametimes.iter().sum::<f64>() / frametimes.len() as f64 };
+        // Ascending FPS is descending frame time; frames of 0 ms count as 0 FPS
+        let zeros = frametimes.iter().take_while(|&&ms| ms <= 0.0).count();
+        let sorted_fps = std::iter::repeat(0.0)
+            .take(zeros)
+            .chain(frametimes[zeros..].iter().rev().map(|&ms| 1000.0 / ms))
+            .collect();
+        Self { sorted: frametimes, sorted_fps, mean }
+    }
+
+    /// Percentile `p` of the per-frame FPS, the way calc_metrics computes
+    /// the lows (interpolating between FPS values, not frame times).
+    fn fps_at(&self, p: f64) -> f64 {
+        percentile(&self.sorted_fps, p)
+    }
+}
+
+const METRICS: &[Metric] = &[
+    Metric { key: "avg_fps", label: "Average FPS", unit: "fps", higher_is_better: true,
+        value: |s| if s.mean > 0.0 { 1000.0 / s.mean } else { 0.0 } },
+    Metric { key: "p1_fps", label: "1% low", unit: "fps", higher_is_better: true, value: |s| s.fps_at(1.0) },
+    Metric { key: "p01_fps", label: "0.1% low", unit: "fps", higher_is_better: true, value: |s| s.fps_at(0.1) },
+    Metric { key: "p50_frametime", label: "Median frame time", unit: "ms", higher_is_better: false,
+        value: |s| percentile(&s.sorted, 50.0) },
+    Metric { key: "p95_frametime", label: "95th pct frame time", unit: "ms", higher_is_better: false,
+        value: |s| percentile(&s.sorted, 95.0) },
+    Metric { key: "p99_frametime", label: "99th pct frame time", unit: "ms", higher_is_better: false,
+        value: |s| percentile(&s.sorted, 99.0) },
+    Metric { key: "p999_frametime", label: "99.9th pct frame time", unit: "ms", higher_is_better: false,
+        value: |s| percentile(&s.sorted, 99.9) },
+];
+
+fn pooled(runs: &[Run]) -> Vec<f64> {
+    runs.iter().flat_map(|r| r.frametimes.iter().copied()).collect()
+}
+
+/// Bootstrap sample of a side. With `between_runs` the runs themselves
+/// are drawn with replacement first: frames of one run share its
+/// conditions (route, temperatures, background load), so resampling
+/// frames alone would hide how much whole runs differ. Inside each run a
+/// circular block bootstrap follows; consecutive frames are correlated (a
+/// stutter spans several), so blocks of about √n frames are drawn
+/// instead of single frames.
+fn resample(runs: &[Run], between_runs: bool, rng: &mut Rng) -> Vec<f64> {
+    let picked: Vec<&Run> = if between_runs {
+        (0..runs.len()).map(|_| &runs[rng.below(runs.len())]).collect()
+    } else {
+        runs.iter().collect()
+    };
+    let mut out = Vec::with_capacity(picked.iter().map(|r| r.frametimes.len()).sum());
+    for run in picked {
+        let n = run.frametimes.len();
+        if n == 0 {
+            continue;
+        }
+        let block = ((n as f64).sqrt().ceil() as usize).max(1);
+        let start = out.len();
+        while out.len() - start < n {
+            let from = rng.below(n);
+            for i in 0..block.min(n - (out.len() - start)) {
+                out.push(run.frametimes[(from + i) % n]);
+            }
+        }
+    }
+    out
+}
+
+/// Mann–Whitney U of candidate vs baseline frame times.
+fn mann_whitney(baseline: &[f64], candidate: &[f64]) -> MannWhitney {
+    let (n1, n2) = (baseline.len() as f64, candidate.len() as f64);
+    let mut all: Vec<(f64, bool)> = baseline.iter().map(|&v| (v, false)).chain(candidate.iter().map(|&v| (v, true))).collect();
+    all.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Average ranks over ties; the tie term corrects the variance
+    let mut rank_sum = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < all.len() {
+        let mut j = i;
+        while j + 1 < all.len() && all[j + 1].0 == all[i].0 {
+            j += 1;
+        }
+        let rank = (i + j) as f64 / 2.0 + 1.0;
+        let ties = (j - i + 1) as f64;
+        tie_term += ties * ties * ties - ties;
+        rank_sum += rank * all[i..=j].iter().filter(|x| x.1).count() as f64;
+        i = j + 1;
+    }
+
+    let u = rank_sum - n2 * (n2 + 1.0) / 2.0;
+    let n = n1 + n2;
+    let mean = n1 * n2 / 2.0;
+    let var = n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    let z = if var > 0.0 { (u - mean) / var.sqrt() } else { 0.0 };
+    MannWhitney {
+        u,
+        z,
+        p_value: 2.0 * (1.0 - normal_cdf(z.abs())),
+        // U counts candidate frames slower than baseline frames
+        prob_faster: if n1 * n2 > 0.0 { 1.0 - u / (n1 * n2) } else { 0.5 },
+    }
+}
+
+/// Standard normal CDF (Abramowitz–Stegun 7.1.26, |error| < 1.5e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t * (0.254_829_592 + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 { 0.5 * (1.0 + erf) } else { 0.5 * (1.0 - erf) }
+}
+
+fn info(runs: &[Run]) -> Vec<RunInfo> {
+    runs.iter()
+        .map(|r| RunInfo {
+            file_name: r.file_name.clone(),
+            process_name: r.process_name.clone(),
+            duration_secs: r.duration_secs,
+            frame_count: r.frametimes.len(),
+        })
+        .collect()
+}
+
+fn warnings(baseline: &[Run], candidate: &[Run]) -> Vec<String> {
+    let mut out = Vec::new();
+    let all: Vec<&Run> = baseline.iter().chain(candidate).collect();
+
+    let mut processes: Vec<&str> = all.iter().map(|r| r.process_name.as_str()).filter(|p| *p != "Unknown").collect();
+    processes.sort_unstable_by_key(|p| p.to_ascii_lowercase());
+    processes.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    if processes.len() > 1 {
+        out.push(format!("Runs captured different processes: {}", processes.join(", ")));
+    }
+
+    let mut durations: Vec<f64> = all.iter().map(|r| r.duration_secs).filter(|d| *d > 0.0).collect();
+    durations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median = percentile(&durations, 50.0);
+    if median > 0.0 {
+        for r in &all {
+            let off = (r.duration_secs - median).abs() / median * 100.0;
+            if off > DURATION_TOLERANCE_PCT {
+                out.push(format!(
+                    "{} lasts {:.0}s, {:.0}% off the median {:.0}s — compare runs of the same length and route",
+                    r.file_name, r.duration_secs, off, median
+                ));
+            }
+        }
+    }
+
+    for r in all.iter().filter(|r| r.frametimes.len() < MIN_FRAMES) {
+        out.push(format!("{} has only {} frames; the 0.1% low is unreliable", r.file_name, r.frametimes.len()));
+    }
+    if baseline.len() < 2 || candidate.len() < 2 {
+        out.push("With one run per side, run-to-run variation isn't measured; record 3 or more runs of each".into());
+    }
+    out
+}
+
+fn signed(v: f64, decimals: usize) -> String {
+    format!("{}{:.*}", if v >= 0.0 { "+" } else { "" }, decimals, v)
+}
+
+pub fn compare(baseline: &[Run], candidate: &[Run]) -> Result<Comparison, String> {
+    if baseline.is_empty() || candidate.is_empty() {
+        return Err("Select at least one baseline and one candidate run".into());
+    }
+    let base_ft = pooled(baseline);
+    let cand_ft = pooled(candidate);
+    if base_ft.is_empty() || cand_ft.is_empty() {
+        return Err("A side has no frames to compare".into());
+    }
+
+    let base = Stats::new(base_ft.clone());
+    let cand = Stats::new(cand_ft.clone());
+
+    // Bootstrap distribution of every metric's delta. A single run can't
+    // show run-to-run variation, so whole runs are only drawn when both
+    // sides have several
+    let between_runs = baseline.len() >= 2 && candidate.len() >= 2;
+    let mut rng = Rng(SEED);
+    let mut deltas: Vec<Vec<f64>> = vec![Vec::with_capacity(ITERATIONS); METRICS.len()];
+    for _ in 0..ITERATIONS {
+        let b = Stats::new(resample(baseline, between_runs, &mut rng));
+        let c = Stats::new(resample(candidate, between_runs, &mut rng));
+        for (m, out) in METRICS.iter().zip(deltas.iter_mut()) {
+            out.push((m.value)(&c) - (m.value)(&b));
+        }
+    }
+
+    let tail = (1.0 - CONFIDENCE) / 2.0 * 100.0;
+    let metrics: Vec<MetricDelta> = METRICS
+        .iter()
+        .zip(deltas.iter_mut())
+        .map(|(m, dist)| {
+            dist.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let (b, c) = ((m.value)(&base), (m.value)(&cand));
+            let (ci_low, ci_high) = (percentile(dist, tail), percentile(dist, 100.0 - tail));
+            MetricDelta {
+                metric: m.key.into(),
+                label: m.label.into(),
+                unit: m.unit.into(),
+                higher_is_better: m.higher_is_better,
+                baseline: b,
+                candidate: c,
+                delta: c - b,
+                delta_pct: if b > 0.0 { (c - b) / b * 100.0 } else { 0.0 },
+                ci_low,
+                ci_high,
+                significant: ci_low > 0.0 || ci_high < 0.0,
+            }
+        })
+        .collect();
+
+    // Frame times are autocorrelated, so Mann–Whitney alone overstates
+    // significance on long captures; the bootstrap interval has to agree
+    let mw = mann_whitney(&base_ft, &cand_ft);
+    let avg = &metrics[0];
+    let low = &metrics[1];
+    let verdict = if !(avg.significant && mw.p_value < 1.0 - CONFIDENCE) {
+        Verdict::NoDifference
+    } else if low.significant && (low.delta > 0.0) != (avg.delta > 0.0) {
+        Verdict::Mixed
+    } else if avg.delta > 0.0 {
+        Verdict::Better
+    } else {
+        Verdict::Worse
+    };
+
+    let conclusion = match (verdict, between_runs) {
+        (Verdict::Better, true) => "a real improvement",
+        (Verdict::Worse, true) => "a real regression",
+        (Verdict::Better, false) => "faster in these captures; record more runs to rule out run-to-run noise",
+        (Verdict::Worse, false) => "slower in these captures; record more runs to rule out run-to-run noise",
+        (Verdict::Mixed, _) => "avg FPS and 1% lows moved in opposite directions",
+        (Verdict::NoDifference, true) => "within run-to-run noise",
+        (Verdict::NoDifference, false) => "within frame-to-frame noise",
+    };
+    let p = if mw.p_value < 0.001 { "p < 0.001".to_string() } else { format!("p = {:.3}", mw.p_value) };
+    let summary = format!(
+        "{} FPS avg ({:.0}% CI {} to {}), 1% lows {} FPS, Mann–Whitney {} — {}",
+        signed(avg.delta, 1),
+        CONFIDENCE * 100.0,
+        signed(avg.ci_low, 1),
+        signed(avg.ci_high, 1),
+        signed(low.delta, 1),
+        p,
+        conclusion
+    );
+
+    Ok(Comparison {
+        baseline: info(baseline),
+        candidate: info(candidate),
+        metrics,
+        mann_whitney: mw,
+        confidence: CONFIDENCE,
+        iterations: ITERATIONS,
+        verdict,
+        summary,
+        warnings: warnings(baseline, candidate),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `n` frames of `ms` with a fixed ±0.2 ms jitter and a spike of
+    /// `spike_ms` every `spike_every` frames.
+    fn run(name: &str, n: usize, ms: f64, spike_every: usize, spike_ms: f64) -> Run {
+        let frametimes = (0..n)
+            .map(|i| {
+                if spike_every > 0 && i % spike_every == spike_every - 1 {
+                    spike_ms
+                } else {
+                    ms + (i * 7919 % 101) as f64 / 250.0 - 0.2
+                }
+            })
+            .collect();
+        Run { file_name: name.into(), process_name: "cs2.exe".into(), duration_secs: n as f64 * ms / 1000.0, frametimes }
+    }
+
+    fn verdict(baseline: &[Run], candidate: &[Run]) -> Verdict {
+        compare(baseline, candidate).unwrap().verdict
+    }
+
+    #[test]
+    fn mann_whitney_corrects_for_ties() {
+        let mw = mann_whitney(&[1.0, 2.0, 3.0, 3.0], &[3.0, 4.0, 5.0, 5.0]);
+        // Candidate ranks 4 + 6 + 7.5 + 7.5 = 25, U = 25 - 4·5/2
+        assert_eq!(mw.u, 15.0);
+        // Tie term (3³ - 3) + (2³ - 2) = 30 lowers the variance from 12 to 11.2857
+        let var = 16.0 / 12.0 * (9.0 - 30.0 / 56.0);
+        assert!((mw.z - 7.0 / f64::sqrt(var)).abs() < 1e-12);
+        assert!((mw.p_value - 0.03719).abs() < 1e-4, "{}", mw.p_value);
+        assert_eq!(mw.prob_faster, 0.0625);
+
+        // All frames equal: no variance, no difference
+        let mw = mann_whitney(&[5.0; 10], &[5.0; 10]);
+        assert_eq!((mw.z, mw.prob_faster), (0.0, 0.5));
+        assert!((mw.p_value - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bootstrap_is_seeded_and_draws_circular_blocks() {
+        // SplitMix64 reference output for seed 0
+        assert_eq!(Rng(0).next(), 0xe220_a839_7b1d_cdaf);
+
+        let runs = [Run {
+            file_name: "a.csv".into(),
+            process_name: "cs2.exe".into(),
+            duration_secs: 1.0,
+            frametimes: (0..100).map(f64::from).collect(),
+        }];
+        let a = resample(&runs, false, &mut Rng(SEED));
+        assert_eq!(a, resample(&runs, false, &mut Rng(SEED)));
+        assert_ne!(a, resample(&runs, false, &mut Rng(SEED + 1)));
+        // Blocks of √100 = 10 consecutive frames, wrapping at the end
+        assert_eq!(a.len(), 100);
+        for block in a.chunks(10) {
+            assert!(block.windows(2).all(|w| w[1] == (w[0] + 1.0) % 100.0), "{:?}", block);
+        }
+
+        let base = [run("b.csv", 2000, 5.0, 0, 0.0)];
+        let cand = [run("c.csv", 2000, 4.9, 0, 0.0)];
+        let (x, y) = (compare(&base, &cand).unwrap(), compare(&base, &cand).unwrap());
+        assert_eq!(x.summary, y.summary);
+        assert!(x.metrics.iter().zip(&y.metrics).all(|(a, b)| (a.ci_low, a.ci_high) == (b.ci_low, b.ci_high)));
+    }
+
+    #[test]
+    fn verdict_needs_bootstrap_and_mann_whitney() {
+        let base = [run("b1.csv", 1200, 5.0, 0, 0.0), run("b2.csv", 1200, 5.0, 0, 0.0)];
+        assert_eq!(verdict(&base, &base), Verdict::NoDifference);
+        assert_eq!(verdict(&base, &[run("c.csv", 1200, 4.5, 0, 0.0)]), Verdict::Better);
+        assert_eq!(verdict(&base, &[run("c.csv", 1200, 5.5, 0, 0.0)]), Verdict::Worse);
+        // Faster on average, but a 15 ms stutter every 40 frames sinks the 1% low
+        let c = compare(&base, &[run("c.csv", 1200, 4.5, 40, 15.0)]).unwrap();
+        assert!(c.metrics[0].delta > 0.0 && c.metrics[1].delta < 0.0);
+        assert_eq!(c.verdict, Verdict::Mixed);
+        // Most frames faster but the same mean: Mann–Whitney alone isn't enough
+        let skewed = Run {
+            frametimes: (0..1200).map(|i| if i % 4 == 3 { 5.3 } else { 4.9 }).collect(),
+            ..run("c.csv", 1200, 5.0, 0, 0.0)
+        };
+        let c = compare(&base, &[skewed]).unwrap();
+        assert!(c.mann_whitney.p_value < 0.05 && !c.metrics[0].significant);
+        assert_eq!(c.verdict, Verdict::NoDifference);
+        assert!(c.summary.ends_with("within frame-to-frame noise"));
+    }
+
+    #[test]
+    fn bootstrap_draws_whole_runs() {
+        // Each draw is one of the two runs, in full
+        let runs = [run("a.csv", 100, 5.0, 0, 0.0), run("b.csv", 100, 9.0, 0, 0.0)];
+        let mut rng = Rng(SEED);
+        let mut seen = [false; 2];
+        for _ in 0..20 {
+            let sample = resample(&runs, true, &mut rng);
+            assert_eq!(sample.len(), 200);
+            for half in sample.chunks(100) {
+                let fast = half.iter().all(|&ms| ms < 7.0);
+                assert!(fast || half.iter().all(|&ms| ms > 7.0));
+                seen[usize::from(!fast)] = true;
+            }
+        }
+        assert_eq!(seen, [true, true]);
+    }
+
+    #[test]
+    fn run_to_run_noise_outweighs_a_small_gain() {
+        // Two baseline runs 5% apart; the candidate is 2% faster than their mean
+        let base = [run("b1.csv", 2000, 5.0, 0, 0.0), run("b2.csv", 2000, 5.25, 0, 0.0)];
+        let cand = [run("c1.csv", 2000, 5.125 / 1.02, 0, 0.0), run("c2.csv", 2000, 5.125 / 1.02, 0, 0.0)];
+        let c = compare(&base, &cand).unwrap();
+        let avg = &c.metrics[0];
+        assert!(avg.delta > 0.0 && avg.ci_low < 0.0, "{:?}", avg);
+        assert_eq!(c.verdict, Verdict::NoDifference);
+        assert!(c.summary.ends_with("within run-to-run noise"));
+
+        // Consistent runs keep a clear gain significant
+        let base: Vec<Run> = [5.0, 5.02, 4.98].iter().map(|&ms| run("b.csv", 2000, ms, 0, 0.0)).collect();
+        let cand: Vec<Run> = [4.5, 4.52, 4.48].iter().map(|&ms| run("c.csv", 2000, ms, 0, 0.0)).collect();
+        let c = compare(&base, &cand).unwrap();
+        assert_eq!(c.verdict, Verdict::Better);
+        assert!(c.summary.ends_with("a real improvement"));
+    }
+
+    #[test]
+    fn lows_match_calc_metrics() {
+        let cap = crate::capture::parse(include_str!("../fixtures/captures/presentmon2.csv"), "presentmon2.csv").unwrap();
+        let metrics = crate::calc_metrics(&cap);
+        let stats = Stats::new(cap.frametimes.clone());
+        assert_eq!(stats.fps_at(1.0), metrics.p1_fps);
+        assert_eq!(stats.fps_at(0.1), metrics.p01_fps);
+        let stats = Stats::new(vec![0.0, 4.0, 5.0, 2.0]);
+        assert_eq!(stats.sorted_fps, [0.0, 200.0, 250.0, 500.0]);
+    }
+}
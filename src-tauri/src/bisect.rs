@@ -0,0 +1,475 @@
+// ────────────────────────────────────────────────────────────────────
+// Tweak bisection – finds which toggles of a profile actually move the
+// benchmark. Every step applies one group of the profile's tweaks on top
+// of Windows defaults (the rest are reverted) and asks for a capture.
+// A group whose avg FPS or 1% lows differ from the baseline by more than
+// the noise is split in two; a single tweak that still does is a finding,
+// and so is a group whose halves each stay within the noise.
+// The session is saved after every step so it survives reboots.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::tweaks::{self, Reversibility, Tweak};
+use std::path::Path;
+
+/// Smallest change counted as real, in percent of the baseline. The
+/// spread between baseline captures raises it on noisy systems.
+const AVG_FPS_FLOOR_PCT: f64 = 2.0;
+const P1_FPS_FLOOR_PCT: f64 = 5.0;
+
+/// The numbers of a capture the bisection compares.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Sample {
+    pub file_name: String,
+    pub avg_fps: f64,
+    pub p1_fps: f64,
+    pub frame_count: usize,
+    pub duration_secs: f64,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum StepKind {
+    /// All of the profile's tweaks reverted
+    Baseline,
+    /// Only `apply` applied
+    Group,
+}
+
+/// The step waiting for a capture.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Step {
+    pub number: usize,
+    pub kind: StepKind,
+    /// Tweak keys applied; every other candidate is reverted
+    pub apply: Vec<String>,
+    /// A tweak changed by this step only takes effect after a reboot
+    pub restart: bool,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Record {
+    pub number: usize,
+    pub kind: StepKind,
+    pub apply: Vec<String>,
+    pub sample: Sample,
+    /// Change against the baseline mean; None for baseline captures
+    #[serde(default)]
+    pub avg_fps_delta_pct: Option<f64>,
+    #[serde(default)]
+    pub p1_fps_delta_pct: Option<f64>,
+    /// Beyond the noise threshold on either metric
+    #[serde(default)]
+    pub changed: bool,
+}
+
+/// Percent change a metric needs before it counts.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug)]
+pub struct Noise {
+    pub avg_fps_pct: f64,
+    pub p1_fps_pct: f64,
+}
+
+/// A tweak that moved the benchmark on its own, or a group that only
+/// did so as a whole.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Finding {
+    /// The tweak key; the keys joined with `+` for a group
+    pub key: String,
+    pub name: String,
+    pub avg_fps_delta_pct: f64,
+    pub p1_fps_delta_pct: f64,
+    /// Keys of a group none of whose halves moved the benchmark alone
+    #[serde(default)]
+    pub combined: Vec<String>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Session {
+    /// RFC 3339
+    pub started: String,
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// OptimizationConfig the candidates come from
+    pub config: serde_json::Value,
+    /// Enabled tweaks the revert script can undo, in catalog order
+    pub candidates: Vec<String>,
+    /// Enabled tweaks left out: manual (BIOS), check-only or one-way changes
+    pub excluded: Vec<String>,
+    pub baseline_runs: usize,
+    pub baseline: Vec<Sample>,
+    /// Set once the baseline captures are in
+    #[serde(default)]
+    pub noise: Option<Noise>,
+    /// Groups still to test; the first one is `next` once the baseline is done
+    pub queue: Vec<Vec<String>>,
+    /// None when the bisection is finished
+    #[serde(default)]
+    pub next: Option<Step>,
+    pub history: Vec<Record>,
+    pub found: Vec<Finding>,
+    /// Tested tweaks that changed nothing beyond the noise
+    pub cleared: Vec<String>,
+}
+
+/// Tweaks a step can both apply and take back. Check-only tweaks (ReBAR,
+/// anisotropic filtering, FACEIT admin) touch nothing, so a baseline
+/// without them is no baseline at all.
+pub fn bisectable(tweak: &Tweak) -> bool {
+    !tweak.is_manual()
+        && !tweak.apply.is_empty()
+        && !tweak.revert.is_empty()
+        && !tweak.touches.is_empty()
+        && matches!(tweak.reversibility, Reversibility::Full | Reversibility::Partial)
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, n) = values.fold((0.0, 0usize), |(s, n), v| (s + v, n + 1));
+    if n == 0 { 0.0 } else { sum / n as f64 }
+}
+
+/// Max - min of the values in percent of their mean.
+fn spread_pct(values: &[f64]) -> f64 {
+    let m = mean(values.iter().copied());
+    if m <= 0.0 {
+        return 0.0;
+    }
+    let max = values.iter().copied().fold(f64::MIN, f64::max);
+    let min = values.iter().copied().fold(f64::MAX, f64::min);
+    (max - min) / m * 100.0
+}
+
+fn delta_pct(value: f64, base: f64) -> f64 {
+    if base > 0.0 { (value - base) / base * 100.0 } else { 0.0 }
+}
+
+impl Session {
+    pub fn start(config: serde_json::Value, profile: Option<String>, baseline_runs: usize) -> Result<Self, String> {
+        let (candidates, excluded): (Vec<&Tweak>, Vec<&Tweak>) =
+            tweaks::catalog().selected(&config, None, &[]).partition(|t| bisectable(t));
+        if candidates.is_empty() {
+            return Err("The profile has no enabled tweaks that can be reverted".into());
+        }
+        let candidates: Vec<String> = candidates.iter().map(|t| t.key.clone()).collect();
+        let excluded: Vec<String> = excluded.iter().map(|t| t.key.clone()).collect();
+        let mut session = Self {
+            started: chrono::Local::now().to_rfc3339(),
+            profile,
+            config,
+            excluded,
+            baseline_runs: baseline_runs.max(1),
+            baseline: Vec::new(),
+            noise: None,
+            // The whole profile first: if it changes nothing there is nothing to split
+            queue: vec![candidates.clone()],
+            candidates,
+            next: None,
+            history: Vec::new(),
+            found: Vec::new(),
+            cleared: Vec::new(),
+        };
+        session.advance();
+        Ok(session)
+    }
+
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read bisection session: {}", e))?;
+        serde_json::from_str(&text)
+            .map(Some)
+            .map_err(|e| format!("Bisection session parse error: {}", e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create bisection dir: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Bisection session serialize error: {}", e))?;
+        // Write to a temp file first so a crash never leaves a truncated session
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to save bisection session: {}", e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to save bisection session: {}", e))
+    }
+
+    /// Candidates the next step reverts.
+    pub fn reverted(&self) -> Vec<&str> {
+        let apply = self.next.as_ref().map(|s| s.apply.as_slice()).unwrap_or_default();
+        self.candidates.iter().filter(|k| !apply.contains(k)).map(|k| k.as_str()).collect()
+    }
+
+    /// The profile config with every tweak outside `apply` turned off.
+    pub fn step_config(&self, apply: &[String]) -> serde_json::Value {
+        let mut config = self.config.clone();
+        for tweak in &tweaks::catalog().tweaks {
+            if !apply.contains(&tweak.key) && tweak.is_enabled(&config) {
+                tweak.set_enabled(&mut config, false);
+            }
+        }
+        config
+    }
+
+    /// Record the capture taken for the pending step and move on.
+    pub fn record(&mut self, sample: Sample) -> Result<(), String> {
+        let step = self.next.clone().ok_or("The bisection is already finished")?;
+        if sample.avg_fps <= 0.0 {
+            return Err("The capture has no frames".into());
+        }
+        let mut record = Record {
+            number: step.number,
+            kind: step.kind,
+            apply: step.apply,
+            sample,
+            avg_fps_delta_pct: None,
+            p1_fps_delta_pct: None,
+            changed: false,
+        };
+
+        match record.kind {
+            StepKind::Baseline => {
+                self.baseline.push(record.sample.clone());
+                if self.baseline.len() >= self.baseline_runs {
+                    let avg: Vec<f64> = self.baseline.iter().map(|s| s.avg_fps).collect();
+                    let p1: Vec<f64> = self.baseline.iter().map(|s| s.p1_fps).collect();
+                    self.noise = Some(Noise {
+                        avg_fps_pct: spread_pct(&avg).max(AVG_FPS_FLOOR_PCT),
+                        p1_fps_pct: spread_pct(&p1).max(P1_FPS_FLOOR_PCT),
+                    });
+                }
+            }
+            StepKind::Group => {
+                // advance() only hands out group steps once the noise is known
+                let noise = self.noise.unwrap_or(Noise { avg_fps_pct: AVG_FPS_FLOOR_PCT, p1_fps_pct: P1_FPS_FLOOR_PCT });
+                let avg = delta_pct(record.sample.avg_fps, mean(self.baseline.iter().map(|s| s.avg_fps)));
+                let p1 = delta_pct(record.sample.p1_fps, mean(self.baseline.iter().map(|s| s.p1_fps)));
+                record.avg_fps_delta_pct = Some(avg);
+                record.p1_fps_delta_pct = Some(p1);
+                record.changed = avg.abs() > noise.avg_fps_pct || p1.abs() > noise.p1_fps_pct;
+
+                let group = self.queue.remove(0);
+                match (record.changed, group.as_slice()) {
+                    (true, [key]) => self.found.push(Finding {
+                        name: name(key),
+                        key: key.clone(),
+                        avg_fps_delta_pct: avg,
+                        p1_fps_delta_pct: p1,
+                        combined: vec![],
+                    }),
+                    (true, _) => {
+                        let (a, b) = split(&group);
+                        self.queue.insert(0, b);
+                        self.queue.insert(0, a);
+                    }
+                    (false, _) => match self.unexplained_parent(&group) {
+                        // Both halves within the noise: the change takes them all
+                        Some(parent) => {
+                            self.cleared.retain(|k| !parent.apply.contains(k));
+                            self.found.push(Finding {
+                                key: parent.apply.join("+"),
+                                name: parent.apply.iter().map(|k| name(k)).collect::<Vec<_>>().join(" + "),
+                                avg_fps_delta_pct: parent.avg_fps_delta_pct.unwrap_or_default(),
+                                p1_fps_delta_pct: parent.p1_fps_delta_pct.unwrap_or_default(),
+                                combined: parent.apply,
+                            });
+                        }
+                        None => self.cleared.extend(group),
+                    },
+                }
+            }
+        }
+        self.history.push(record);
+        self.advance();
+        Ok(())
+    }
+
+    /// The changed group `second_half` was split from, when its first half
+    /// stayed within the noise as well. Halves are tested right after the
+    /// split, so the two group records before this one are the candidates.
+    fn unexplained_parent(&self, second_half: &[String]) -> Option<Record> {
+        let mut groups = self.history.iter().rev().filter(|r| r.kind == StepKind::Group);
+        let first_half = groups.next().filter(|r| !r.changed)?;
+        let parent = groups.next().filter(|r| r.changed)?;
+        let (a, b) = split(&parent.apply);
+        (a == first_half.apply && b == second_half).then(|| parent.clone())
+    }
+
+    fn advance(&mut self) {
+        let number = self.history.len() + 1;
+        let (kind, apply) = if self.baseline.len() < self.baseline_runs {
+            (StepKind::Baseline, Vec::new())
+        } else if let Some(group) = self.queue.first() {
+            (StepKind::Group, group.clone())
+        } else {
+            self.next = None;
+            return;
+        };
+
+        // Before the first step the system state is unknown: assume every
+        // candidate may change
+        let previous: Option<&[String]> = self.history.last().map(|r| r.apply.as_slice());
+        let restart = self.candidates.iter().any(|key| {
+            let changes = previous.map_or(true, |p| p.contains(key) != apply.contains(key));
+            changes && tweaks::catalog().find(key).is_some_and(|t| t.restart)
+        });
+        self.next = Some(Step { number, kind, apply, restart });
+    }
+}
+
+fn name(key: &str) -> String {
+    tweaks::catalog().find(key).map_or(key.to_string(), |t| t.name().to_string())
+}
+
+/// Halve a group. A section boundary near the middle is preferred so the
+/// halves map onto whole script sections.
+fn split(group: &[String]) -> (Vec<String>, Vec<String>) {
+    let section = |key: &String| tweaks::catalog().find(key).map(|t| t.section.as_str());
+    let mid = group.len() / 2;
+    let cut = (1..group.len())
+        .filter(|&i| i.abs_diff(mid) <= group.len() / 4)
+        .filter(|&i| section(&group[i]) != section(&group[i - 1]))
+        .min_by_key(|&i| i.abs_diff(mid))
+        .unwrap_or(mid);
+    (group[..cut].to_vec(), group[cut..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECK_ONLY: [&str; 4] = ["nv_aniso", "amd_rebar", "intel_rebar", "x_faceit"];
+
+    /// An OptimizationConfig value with exactly `keys` switched on.
+    fn config(keys: &[&str]) -> serde_json::Value {
+        let cat = tweaks::catalog();
+        let mut config = serde_json::Map::new();
+        for sec in &cat.sections {
+            config.insert(sec.config.clone(), serde_json::json!({}));
+        }
+        let mut config = serde_json::Value::Object(config);
+        for key in keys {
+            cat.find(key).unwrap().set_enabled(&mut config, true);
+        }
+        config
+    }
+
+    fn sample(avg_fps: f64) -> Sample {
+        Sample {
+            file_name: "capture.csv".into(),
+            avg_fps,
+            p1_fps: avg_fps / 2.0,
+            frame_count: 6000,
+            duration_secs: 60.0,
+        }
+    }
+
+    /// Record captures until the session finishes; groups containing
+    /// `culprit` run 10% faster than the baseline.
+    fn run(session: &mut Session, culprit: &str) {
+        for _ in 0..64 {
+            let Some(step) = &session.next else { return };
+            let fps = if step.apply.iter().any(|k| k == culprit) { 110.0 } else { 100.5 };
+            session.record(sample(fps)).unwrap();
+        }
+        panic!("bisection did not finish");
+    }
+
+    #[test]
+    fn check_only_and_irreversible_tweaks_are_excluded() {
+        let cat = tweaks::catalog();
+        for key in CHECK_ONLY.iter().chain(&["w_largecache"]) {
+            assert!(!bisectable(cat.find(key).unwrap()), "{} is bisectable", key);
+        }
+        let mut keys = vec!["w_hpet", "s_fax"];
+        keys.extend(CHECK_ONLY);
+        let session = Session::start(config(&keys), None, 1).unwrap();
+        assert_eq!(session.candidates, ["w_hpet", "s_fax"]);
+        assert_eq!(session.excluded.len(), CHECK_ONLY.len());
+        assert!(Session::start(config(&CHECK_ONLY), None, 1).is_err());
+    }
+
+    #[test]
+    fn narrows_down_to_the_tweak_that_moves_fps() {
+        let keys = ["w_hpet", "w_hgs", "n_wifi", "s_fax", "s_remote"];
+        let mut session = Session::start(config(&keys), Some("test".into()), 2).unwrap();
+        assert_eq!(session.next.as_ref().map(|s| s.kind), Some(StepKind::Baseline));
+        session.record(sample(100.0)).unwrap();
+        session.record(sample(101.0)).unwrap();
+        // A 1% spread stays below the floor
+        let noise = session.noise.unwrap();
+        assert_eq!((noise.avg_fps_pct, noise.p1_fps_pct), (AVG_FPS_FLOOR_PCT, P1_FPS_FLOOR_PCT));
+        assert_eq!(session.next.as_ref().unwrap().apply, session.candidates);
+
+        run(&mut session, "n_wifi");
+        assert!(session.next.is_none() && session.queue.is_empty());
+        assert_eq!(session.found.len(), 1);
+        assert_eq!(session.found[0].key, "n_wifi");
+        assert!((session.found[0].avg_fps_delta_pct - 9.45).abs() < 0.01);
+        let mut cleared = session.cleared.clone();
+        cleared.sort();
+        assert_eq!(cleared, ["s_fax", "s_remote", "w_hgs", "w_hpet"]);
+        // Every changed group was split, every other group tested once
+        let groups: Vec<&Record> = session.history.iter().filter(|r| r.kind == StepKind::Group).collect();
+        assert!(groups.iter().filter(|r| r.changed).all(|r| r.apply.iter().any(|k| k == "n_wifi")));
+        assert!(groups.len() < 2 * keys.len());
+        assert!(session.record(sample(100.0)).is_err());
+    }
+
+    #[test]
+    fn nothing_changed_clears_the_whole_profile() {
+        let mut session = Session::start(config(&["w_hpet", "s_fax"]), None, 1).unwrap();
+        run(&mut session, "none");
+        assert!(session.found.is_empty());
+        assert_eq!(session.cleared, session.candidates);
+        assert_eq!(session.history.len(), 2);
+    }
+
+    #[test]
+    fn halves_that_only_matter_together_are_one_finding() {
+        let keys = ["w_hpet", "w_hgs", "w_park", "s_fax", "s_remote"];
+        let mut session = Session::start(config(&keys), None, 1).unwrap();
+        session.record(sample(100.0)).unwrap();
+        // The gain needs w_hgs and s_fax, which end up in different halves
+        for _ in 0..16 {
+            let Some(step) = &session.next else { break };
+            let both = ["w_hgs", "s_fax"].iter().all(|k| step.apply.iter().any(|a| a == k));
+            session.record(sample(if both { 110.0 } else { 100.5 })).unwrap();
+        }
+        assert!(session.next.is_none());
+        assert_eq!(session.found.len(), 1);
+        let finding = &session.found[0];
+        assert_eq!(finding.combined, session.candidates);
+        assert_eq!(finding.key, session.candidates.join("+"));
+        assert!((finding.avg_fps_delta_pct - 10.0).abs() < 0.01);
+        assert!(session.cleared.is_empty());
+        assert_eq!(session.history.len(), 4);
+    }
+
+    #[test]
+    fn split_prefers_a_section_boundary() {
+        let group: Vec<String> = ["w_hpet", "w_hgs", "w_park", "s_fax", "s_remote"].map(String::from).to_vec();
+        let (a, b) = split(&group);
+        assert_eq!((a.len(), b.len()), (3, 2));
+        assert_eq!(b[0], "s_fax");
+    }
+
+    #[test]
+    fn session_survives_a_save_and_load() {
+        let mut session = Session::start(config(&["w_hpet", "w_hgs", "s_fax"]), None, 1).unwrap();
+        session.record(sample(100.0)).unwrap();
+        session.record(sample(110.0)).unwrap();
+        let path = std::env::temp_dir().join(format!("aimcamp_bisect_test_{}.json", std::process::id()));
+        session.save(&path).unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+        let mut loaded = Session::load(&path).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), serde_json::to_value(&session).unwrap());
+
+        run(&mut session, "s_fax");
+        run(&mut loaded, "s_fax");
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), serde_json::to_value(&session).unwrap());
+        assert!(Session::load(&path).unwrap().is_none());
+    }
+}
@@ -0,0 +1,273 @@
+// ────────────────────────────────────────────────────────────────────
+// Bottleneck analysis – uses the extra columns of a capture to tell how
+// often frames were limited by the CPU or the GPU, how long input or
+// presents took to reach the screen, and which present modes were used.
+// The one-line verdict tells the user (and the advisor) which kind of
+// tweak can still raise FPS.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::benchcmp::percentile;
+use crate::capture::Capture;
+
+/// A frame whose busiest unit was active for less than this share of
+/// its frame time waited on a limiter (fps_max, V-Sync, Reflex).
+const BUSY_RATIO: f64 = 0.75;
+/// GPU utilisation (percent) from which a frame counts as GPU-bound.
+const GPU_LOAD_BOUND: f64 = 90.0;
+/// Share of frames (percent) one limiter needs for a clear verdict.
+const VERDICT_PCT: f64 = 70.0;
+/// Share of frames composed by DWM (percent) worth a warning.
+const COMPOSED_PCT: f64 = 10.0;
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    CpuBound,
+    GpuBound,
+    /// Neither unit was busy: a frame limiter set the pace
+    Capped,
+    Mixed,
+    /// The capture has no busy or load data
+    Unknown,
+}
+
+/// What the CPU/GPU split is based on.
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundSource {
+    /// MsCPUBusy and MsGPUBusy (PresentMon 2.x)
+    Busy,
+    /// GPU busy time only (FrameView, CapFrameX)
+    GpuBusy,
+    /// GPU utilisation (MangoHud, FrameView without busy times)
+    GpuLoad,
+}
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencySource {
+    /// MsPCLatency – input to photon, needs Reflex
+    PcLatency,
+    /// MsUntilDisplayed – present call to display only
+    Display,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct Latency {
+    pub source: LatencySource,
+    pub avg_ms: f64,
+    pub p99_ms: f64,
+    /// Frames with a value
+    pub frames: usize,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct PresentModeShare {
+    pub mode: String,
+    pub frames: usize,
+    pub pct: f64,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct Analysis {
+    pub verdict: Verdict,
+    /// One line, e.g. "CPU-bound 87% of frames — GPU tweaks won't help"
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<BoundSource>,
+    /// Frames with busy or load data
+    pub frames_analyzed: usize,
+    pub cpu_bound_pct: f64,
+    pub gpu_bound_pct: f64,
+    pub capped_pct: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency: Option<Latency>,
+    pub present_modes: Vec<PresentModeShare>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Limit {
+    Cpu,
+    Gpu,
+    Cap,
+}
+
+fn pct(part: usize, total: usize) -> f64 {
+    if total == 0 { 0.0 } else { part as f64 / total as f64 * 100.0 }
+}
+
+/// The limiter of every frame with data; NaN cells are skipped.
+fn limits(c: &Capture) -> Option<(BoundSource, Vec<Limit>)> {
+    let s = &c.series;
+    let at = |values: &[f64], i: usize| values.get(i).copied().filter(|v| !v.is_nan());
+    let frames = c.frametimes.iter().enumerate();
+    match (&s.cpu_busy, &s.gpu_busy, &s.gpu_load) {
+        (Some(cpu), Some(gpu), _) => {
+            let limits = frames
+                .filter_map(|(i, &ft)| {
+                    let (cpu, gpu) = (at(cpu, i)?, at(gpu, i)?);
+                    Some(if cpu.max(gpu) < ft * BUSY_RATIO {
+                        Limit::Cap
+                    } else if gpu >= cpu {
+                        Limit::Gpu
+                    } else {
+                        Limit::Cpu
+                    })
+                })
+                .collect();
+            Some((BoundSource::Busy, limits))
+        }
+        (None, Some(gpu), _) => {
+            let limits = frames
+                .filter_map(|(i, &ft)| at(gpu, i).map(|gpu| if gpu >= ft * BUSY_RATIO { Limit::Gpu } else { Limit::Cpu }))
+                .collect();
+            Some((BoundSource::GpuBusy, limits))
+        }
+        (_, None, Some(load)) => {
+            let limits = (0..c.frametimes.len())
+                .filter_map(|i| at(load, i).map(|load| if load >= GPU_LOAD_BOUND { Limit::Gpu } else { Limit::Cpu }))
+                .collect();
+            Some((BoundSource::GpuLoad, limits))
+        }
+        _ => None,
+    }
+}
+
+fn latency(c: &Capture) -> Option<Latency> {
+    let (source, values) = match (&c.series.pc_latency, &c.series.display_latency) {
+        (Some(v), _) => (LatencySource::PcLatency, v),
+        (None, Some(v)) => (LatencySource::Display, v),
+        _ => return None,
+    };
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan() && *v > 0.0).collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    Some(Latency {
+        source,
+        avg_ms: sorted.iter().sum::<f64>() / sorted.len() as f64,
+        p99_ms: percentile(&sorted, 99.0),
+        frames: sorted.len(),
+    })
+}
+
+pub fn analyze(c: &Capture) -> Analysis {
+    let mut notes = Vec::new();
+    let (source, limits) = match limits(c) {
+        Some((source, limits)) => (Some(source), limits),
+        None => (None, Vec::new()),
+    };
+    let n = limits.len();
+    let count = |limit: Limit| limits.iter().filter(|&&l| l == limit).count();
+    let (cpu, gpu, cap) = (pct(count(Limit::Cpu), n), pct(count(Limit::Gpu), n), pct(count(Limit::Cap), n));
+
+    let (verdict, summary) = if n == 0 {
+        (
+            Verdict::Unknown,
+            "No CPU/GPU busy or load data — capture with PresentMon 2.x, FrameView or MangoHud to see the bottleneck".to_string(),
+        )
+    } else if cap >= VERDICT_PCT {
+        (
+            Verdict::Capped,
+            format!("Frame-rate capped {:.0}% of frames — fps_max, V-Sync or Reflex sets the pace, not the hardware", cap),
+        )
+    } else if cpu >= VERDICT_PCT {
+        (
+            Verdict::CpuBound,
+            format!("CPU-bound {:.0}% of frames — GPU tweaks won't help; CPU, RAM and Windows tweaks can", cpu),
+        )
+    } else if gpu >= VERDICT_PCT {
+        (
+            Verdict::GpuBound,
+            format!("GPU-bound {:.0}% of frames — CPU and Windows tweaks won't raise FPS; lower resolution or GPU-heavy settings", gpu),
+        )
+    } else {
+        (
+            Verdict::Mixed,
+            format!("Mixed: CPU-bound {:.0}%, GPU-bound {:.0}% of frames — no single kind of tweak dominates", cpu, gpu),
+        )
+    };
+
+    match source {
+        Some(BoundSource::GpuBusy) => notes.push("No CPU busy times: frames held back by a frame cap count as CPU-bound".to_string()),
+        Some(BoundSource::GpuLoad) => notes.push("Based on GPU utilisation only; record with PresentMon 2.x for per-frame busy times".to_string()),
+        _ => {}
+    }
+
+    let latency = latency(c);
+    if let Some(l) = &latency {
+        if l.source == LatencySource::Display {
+            notes.push("Latency is present-to-display only; enable NVIDIA Reflex for input-to-photon (PC latency)".to_string());
+        }
+    }
+
+    let total: usize = c.present_modes.iter().map(|(_, n)| n).sum();
+    let present_modes: Vec<PresentModeShare> = c
+        .present_modes
+        .iter()
+        .map(|(mode, frames)| PresentModeShare { mode: mode.clone(), frames: *frames, pct: pct(*frames, total) })
+        .collect();
+    let composed: f64 = present_modes.iter().filter(|m| m.mode.starts_with("Composed")).map(|m| m.pct).sum();
+    if composed >= COMPOSED_PCT {
+        notes.push(format!(
+            "{:.0}% of frames were composed by DWM, which adds latency — run fullscreen and check fullscreen optimizations",
+            composed
+        ));
+    }
+
+    Analysis {
+        verdict,
+        summary,
+        source,
+        frames_analyzed: n,
+        cpu_bound_pct: cpu,
+        gpu_bound_pct: gpu,
+        capped_pct: cap,
+        latency,
+        present_modes,
+        notes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture;
+
+    fn analysis(name: &str, text: &str) -> Analysis {
+        analyze(&capture::parse(text, name).unwrap())
+    }
+
+    #[test]
+    fn busy_times_split_cpu_and_gpu() {
+        let a = analysis("presentmon2.csv", include_str!("../fixtures/captures/presentmon2.csv"));
+        assert_eq!(a.source, Some(BoundSource::Busy));
+        assert_eq!(a.verdict, Verdict::CpuBound);
+        assert!(a.summary.starts_with("CPU-bound 100% of frames"));
+        let l = a.latency.unwrap();
+        assert_eq!(l.source, LatencySource::PcLatency);
+        // The first frame has no PC latency
+        assert_eq!(l.frames, 23);
+        assert!(l.avg_ms > 11.0 && l.p99_ms <= 12.68 + 1e-9);
+    }
+
+    #[test]
+    fn gpu_time_and_load_only() {
+        let a = analysis("FrameView_cs2.csv", include_str!("../fixtures/captures/frameview.csv"));
+        assert_eq!((a.source, a.verdict), (Some(BoundSource::GpuBusy), Verdict::GpuBound));
+        let a = analysis("cs2_2026-10-17_12-00-00.csv", include_str!("../fixtures/captures/mangohud.csv"));
+        assert_eq!((a.source, a.verdict), (Some(BoundSource::GpuLoad), Verdict::GpuBound));
+        assert!(a.latency.is_none() && a.present_modes.is_empty());
+    }
+
+    #[test]
+    fn frame_times_only() {
+        let a = analysis("presentmon.csv", include_str!("../fixtures/captures/presentmon.csv"));
+        assert_eq!(a.verdict, Verdict::Unknown);
+        assert_eq!(a.latency.map(|l| l.source), Some(LatencySource::Display));
+        assert_eq!(a.present_modes.len(), 1);
+        assert!((a.present_modes[0].pct - 100.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,564 @@
+// ────────────────────────────────────────────────────────────────────
+// Frame capture files – PresentMon 1.x / 2.x, NVIDIA FrameView and OCAT
+// CSVs, MangoHud logs (Linux) and CapFrameX JSON. The format is detected
+// from the content, not the extension. Every parser yields the same raw
+// frame times plus whichever extra per-frame series the tool records.
+// ────────────────────────────────────────────────────────────────────
+
+use serde_json::Value;
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    PresentMon,
+    PresentMon2,
+    FrameView,
+    Ocat,
+    MangoHud,
+    CapFrameX,
+}
+
+/// Per-frame series beyond the frame time, aligned with the frame
+/// times. A frame without a value (PresentMon writes "NA") is NaN, which
+/// serializes as null.
+#[derive(serde::Serialize, Clone, Default, Debug)]
+pub struct Series {
+    /// MsBetweenDisplayChange – time between frames reaching the screen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_change: Option<Vec<f64>>,
+    /// MsUntilDisplayed – present call to the frame reaching the screen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_latency: Option<Vec<f64>>,
+    /// MsCPUBusy (PresentMon 2.x)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_busy: Option<Vec<f64>>,
+    /// MsGPUBusy (PresentMon 2.x), MsGPUActive (FrameView, CapFrameX)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_busy: Option<Vec<f64>>,
+    /// MsPCLatency – input to photon, needs Reflex / PC Latency Stats
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pc_latency: Option<Vec<f64>>,
+    /// CPU and GPU utilisation in percent (FrameView, MangoHud)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_load: Option<Vec<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_load: Option<Vec<f64>>,
+}
+
+#[derive(Clone, Copy)]
+enum Extra {
+    DisplayChange,
+    DisplayLatency,
+    CpuBusy,
+    GpuBusy,
+    PcLatency,
+    CpuLoad,
+    GpuLoad,
+}
+
+/// Column (or CapFrameX key) names of each extra series.
+const EXTRAS: [(Extra, &[&str]); 7] = [
+    (Extra::DisplayChange, &["MsBetweenDisplayChange"]),
+    (Extra::DisplayLatency, &["MsUntilDisplayed"]),
+    (Extra::CpuBusy, &["MsCPUBusy"]),
+    (Extra::GpuBusy, &["MsGPUBusy", "MsGPUActive"]),
+    (Extra::PcLatency, &["MsPCLatency"]),
+    (Extra::CpuLoad, &["CPUUtil(%)", "cpu_load"]),
+    (Extra::GpuLoad, &["GPU0Util(%)", "gpu_load"]),
+];
+
+impl Series {
+    fn slot(&mut self, extra: Extra) -> &mut Option<Vec<f64>> {
+        match extra {
+            Extra::DisplayChange => &mut self.display_change,
+            Extra::DisplayLatency => &mut self.display_latency,
+            Extra::CpuBusy => &mut self.cpu_busy,
+            Extra::GpuBusy => &mut self.gpu_busy,
+            Extra::PcLatency => &mut self.pc_latency,
+            Extra::CpuLoad => &mut self.cpu_load,
+            Extra::GpuLoad => &mut self.gpu_load,
+        }
+    }
+
+    fn push(&mut self, extra: Extra, value: f64) {
+        self.slot(extra).get_or_insert_with(Vec::new).push(value);
+    }
+
+    /// Drop series the tool left empty (all "NA").
+    fn prune(&mut self) {
+        for (extra, _) in EXTRAS {
+            let slot = self.slot(extra);
+            if slot.as_ref().is_some_and(|v| v.iter().all(|x| x.is_nan())) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// The frames at `indices`, for the downsampled chart data.
+    pub fn pick(&self, indices: &[usize]) -> Series {
+        let mut out = self.clone();
+        for (extra, _) in EXTRAS {
+            let slot = out.slot(extra);
+            if let Some(values) = slot.as_ref() {
+                *slot = Some(indices.iter().map(|&i| values.get(i).copied().unwrap_or(f64::NAN)).collect());
+            }
+        }
+        out
+    }
+}
+
+/// Raw frame data of one capture file, before metrics and downsampling.
+pub struct Capture {
+    pub format: Format,
+    pub file_name: String,
+    pub process: String,
+    /// ms
+    pub frametimes: Vec<f64>,
+    /// s
+    pub timestamps: Vec<f64>,
+    pub dropped: usize,
+    pub series: Series,
+    /// Frames per present mode ("Hardware: Independent Flip", ...), in
+    /// order of first appearance; empty when the tool doesn't record it
+    pub present_modes: Vec<(String, usize)>,
+}
+
+/// Count one frame presented with `mode`.
+fn count_mode(modes: &mut Vec<(String, usize)>, mode: &str) {
+    if mode.is_empty() || mode == "NA" {
+        return;
+    }
+    match modes.iter_mut().find(|(m, _)| m == mode) {
+        Some((_, n)) => *n += 1,
+        None => modes.push((mode.to_string(), 1)),
+    }
+}
+
+impl Capture {
+    pub fn duration_secs(&self) -> f64 {
+        self.timestamps.last().unwrap_or(&0.0) - self.timestamps.first().unwrap_or(&0.0)
+    }
+}
+
+pub fn read(path: &str) -> Result<Capture, String> {
+    let file_name = std::path::Path::new(path)
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Erro ao ler ficheiro: {}", e))?;
+    parse(&content, &file_name)
+}
+
+pub fn parse(content: &str, file_name: &str) -> Result<Capture, String> {
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(content);
+    let head = content.trim_start();
+    if head.starts_with('{') {
+        parse_capframex_json(content, file_name)
+    } else if content
+        .lines()
+        .take(5)
+        .any(|l| l.starts_with("os,") || l.contains("FRAME METRICS") || l.starts_with("fps,frametime"))
+    {
+        parse_mangohud(content, file_name)
+    } else if head.is_empty() {
+        Err("Ficheiro vazio".into())
+    } else {
+        parse_presentmon_csv(content, file_name)
+    }
+}
+
+/// Split a CSV line; FrameView and OCAT quote hardware names.
+fn fields(line: &str) -> Vec<String> {
+    if !line.contains('"') {
+        return line.split(',').map(|f| f.trim().to_string()).collect();
+    }
+    let mut out = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => out.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    out.push(field.trim().to_string());
+    out
+}
+
+/// "NA", empty and unparsable cells are NaN.
+fn number(cell: Option<&String>) -> f64 {
+    cell.and_then(|v| v.parse::<f64>().ok()).unwrap_or(f64::NAN)
+}
+
+/// Seconds per unit of a time column, judged by its span against the
+/// summed frame times: PresentMon 2.x writes ms or s depending on its
+/// options, MangoHud ns. None when nothing fits (QPC ticks).
+fn time_scale(times: &[f64], frametimes: &[f64]) -> Option<f64> {
+    let span = times.last()? - times.first()?;
+    let total = frametimes.iter().skip(1).sum::<f64>() / 1000.0;
+    if span <= 0.0 || total <= 0.0 {
+        return None;
+    }
+    [1.0, 1e-3, 1e-6, 1e-9].into_iter().find(|s| (0.5..2.0).contains(&(span * s / total)))
+}
+
+/// Timestamps in seconds from a time column (`scale` seconds per unit,
+/// inferred when None), or accumulated frame times when the column is
+/// missing, incomplete or in an unknown unit.
+fn timestamps(times: Vec<f64>, frametimes: &[f64], scale: Option<f64>) -> Vec<f64> {
+    let complete = times.len() == frametimes.len() && !times.iter().any(|t| t.is_nan());
+    match scale.or_else(|| time_scale(&times, frametimes)) {
+        Some(scale) if complete => times.iter().map(|t| t * scale).collect(),
+        _ => {
+            let mut t = 0.0;
+            frametimes
+                .iter()
+                .map(|ft| {
+                    let at = t;
+                    t += ft / 1000.0;
+                    at
+                })
+                .collect()
+        }
+    }
+}
+
+fn csv_format(cols: &[String]) -> Format {
+    let has = |name: &str| cols.iter().any(|c| c.eq_ignore_ascii_case(name));
+    if has("Motherboard") || has("Base Driver Version") {
+        Format::Ocat
+    } else if has("GPU0Clk(MHz)") || has("MsGPUActive") || has("PCAT Power Total(W)") {
+        Format::FrameView
+    } else if has("MsCPUBusy") || has("MsGPUBusy") || has("CPUStartTime") {
+        Format::PresentMon2
+    } else {
+        Format::PresentMon
+    }
+}
+
+/// PresentMon and the tools built on it (FrameView, OCAT) share the
+/// per-present columns; they differ in the extras they append.
+fn parse_presentmon_csv(content: &str, file_name: &str) -> Result<Capture, String> {
+    let mut lines = content.lines();
+
+    // Parse header to find column indices
+    let header = lines.next().ok_or("Ficheiro CSV vazio")?;
+    let cols = fields(header);
+    let format = csv_format(&cols);
+
+    let find_col = |names: &[&str]| -> Option<usize> {
+        names.iter().find_map(|name| cols.iter().position(|c| c.eq_ignore_ascii_case(name)))
+    };
+
+    let col_app = find_col(&["Application"]);
+    let col_seconds = find_col(&["TimeInSeconds"]);
+    // PresentMon 2.x: ms or s, depending on its options
+    let col_time = col_seconds.or_else(|| find_col(&["CPUStartTime"]));
+    let col_ft = find_col(&["MsBetweenPresents", "FrameTime", "MsBetweenAppStart"]);
+    let col_dropped = find_col(&["Dropped"]);
+    let col_mode = find_col(&["PresentMode"]);
+    // PresentMon 2.x has no Dropped column; frames never shown are "NA"
+    let col_displayed = if col_dropped.is_none() { find_col(&["MsUntilDisplayed"]) } else { None };
+    let extras: Vec<(Extra, usize)> = EXTRAS
+        .iter()
+        .filter_map(|(extra, names)| find_col(names).map(|i| (*extra, i)))
+        .collect();
+
+    let col_ft = col_ft.ok_or("Coluna 'MsBetweenPresents' ou 'FrameTime' não encontrada no CSV")?;
+
+    let mut frametimes: Vec<f64> = Vec::new();
+    let mut times: Vec<f64> = Vec::new();
+    let mut process = String::new();
+    let mut dropped_count = 0usize;
+    let mut series = Series::default();
+    let mut present_modes = Vec::new();
+
+    for line in lines {
+        let fields = fields(line);
+        if fields.len() <= col_ft { continue; }
+
+        let ft: f64 = match fields[col_ft].parse() {
+            Ok(v) if v > 0.0 => v,
+            _ => continue,
+        };
+
+        if process.is_empty() {
+            if let Some(v) = col_app.and_then(|i| fields.get(i)) {
+                if !v.is_empty() && v != "<unknown>" { process = v.to_string(); }
+            }
+        }
+
+        let dropped = match (col_dropped, col_displayed) {
+            (Some(i), _) => fields.get(i).is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+            (None, Some(i)) => number(fields.get(i)).is_nan(),
+            (None, None) => false,
+        };
+        if dropped {
+            dropped_count += 1;
+        }
+
+        if let Some(mode) = col_mode.and_then(|i| fields.get(i)) {
+            count_mode(&mut present_modes, mode);
+        }
+        for &(extra, i) in &extras {
+            series.push(extra, number(fields.get(i)));
+        }
+        frametimes.push(ft);
+        times.push(col_time.map_or(f64::NAN, |i| number(fields.get(i))));
+    }
+
+    if frametimes.is_empty() {
+        return Err("Nenhum frame válido encontrado no ficheiro CSV".into());
+    }
+
+    if process.is_empty() { process = "Unknown".into(); }
+    series.prune();
+    Ok(Capture {
+        format,
+        file_name: file_name.to_string(),
+        process,
+        timestamps: timestamps(times, &frametimes, col_seconds.map(|_| 1.0)),
+        frametimes,
+        dropped: dropped_count,
+        series,
+        present_modes,
+    })
+}
+
+/// MangoHud log: optional system-info header ("os,cpu,gpu,...", one row
+/// of values and a "FRAME METRICS" rule), then one row per frame with
+/// `frametime` in ms and `elapsed` in ns.
+fn parse_mangohud(content: &str, file_name: &str) -> Result<Capture, String> {
+    let mut lines = content.lines().skip_while(|l| !l.starts_with("fps,") && !l.contains(",frametime"));
+    let header = lines.next().ok_or("Log do MangoHud sem cabeçalho de frames")?;
+    let cols = fields(header);
+    let find_col = |names: &[&str]| -> Option<usize> {
+        names.iter().find_map(|name| cols.iter().position(|c| c.eq_ignore_ascii_case(name)))
+    };
+    let col_ft = find_col(&["frametime"]).ok_or("Coluna 'frametime' não encontrada no log do MangoHud")?;
+    let col_time = find_col(&["elapsed"]);
+    let extras: Vec<(Extra, usize)> = EXTRAS
+        .iter()
+        .filter_map(|(extra, names)| find_col(names).map(|i| (*extra, i)))
+        .collect();
+
+    let mut frametimes: Vec<f64> = Vec::new();
+    let mut times: Vec<f64> = Vec::new();
+    let mut series = Series::default();
+    for line in lines {
+        let fields = fields(line);
+        let ft = number(fields.get(col_ft));
+        if ft.is_nan() || ft <= 0.0 {
+            continue;
+        }
+        for &(extra, i) in &extras {
+            series.push(extra, number(fields.get(i)));
+        }
+        frametimes.push(ft);
+        times.push(col_time.map_or(f64::NAN, |i| number(fields.get(i))));
+    }
+
+    if frametimes.is_empty() {
+        return Err("Nenhum frame válido encontrado no log do MangoHud".into());
+    }
+
+    // Logs are named <program>_<date>_<time>.csv
+    let stem = std::path::Path::new(file_name).file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let parts: Vec<&str> = stem.rsplitn(3, '_').collect();
+    let process = if parts.len() == 3 { parts[2].to_string() } else { "Unknown".to_string() };
+
+    series.prune();
+    Ok(Capture {
+        format: Format::MangoHud,
+        file_name: file_name.to_string(),
+        process,
+        timestamps: timestamps(times, &frametimes, None),
+        frametimes,
+        dropped: 0,
+        series,
+        present_modes: Vec::new(),
+    })
+}
+
+fn is_dropped(v: &Value) -> bool {
+    v.as_bool().unwrap_or(false) || v.as_i64().unwrap_or(0) == 1
+}
+
+fn parse_capframex_json(content: &str, file_name: &str) -> Result<Capture, String> {
+    let json: Value = serde_json::from_str(content)
+        .map_err(|e| format!("JSON inválido: {}", e))?;
+
+    let mut frametimes: Vec<f64> = Vec::new();
+    let mut dropped = 0usize;
+    let mut series = Series::default();
+    let mut present_modes = Vec::new();
+
+    // CapFrameX JSON format: { "Runs": [ { "CaptureData": { "MsBetweenPresents": [...], "Dropped": [...] } } ], "Info": { "ProcessName": "..." } }
+    // Each Run is a SessionRun object with CaptureData sub-object
+    let captures: Vec<&Value> = match json.get("Runs").and_then(|r| r.as_array()) {
+        Some(runs) => runs.iter().filter_map(|run| run.get("CaptureData")).collect(),
+        None => json.get("CaptureData").into_iter().collect(),
+    };
+    for capture in &captures {
+        let Some(ms) = capture.get("MsBetweenPresents").and_then(|m| m.as_array()) else { continue };
+        let column = |key: &str| capture.get(key).and_then(|v| v.as_array()).filter(|a| a.len() == ms.len());
+        let drops = column("Dropped");
+        let modes = column("PresentMode");
+        let extras: Vec<(Extra, &Vec<Value>)> = EXTRAS
+            .iter()
+            .filter_map(|(extra, names)| names.iter().find_map(|n| column(n)).map(|a| (*extra, a)))
+            .collect();
+        for (i, v) in ms.iter().enumerate() {
+            let Some(ft) = v.as_f64().filter(|&v| v > 0.0) else { continue };
+            frametimes.push(ft);
+            if drops.is_some_and(|d| is_dropped(&d[i])) {
+                dropped += 1;
+            }
+            if let Some(mode) = modes.and_then(|m| m[i].as_str()) {
+                count_mode(&mut present_modes, mode);
+            }
+            for (extra, values) in &extras {
+                series.push(*extra, values[i].as_f64().unwrap_or(f64::NAN));
+            }
+        }
+    }
+
+    if captures.is_empty() {
+        // Fallback: Runs as arrays of raw frame times, or a bare array
+        let raw: Vec<&Value> = match (json.get("Runs").and_then(|r| r.as_array()), json.get("MsBetweenPresents")) {
+            (Some(runs), _) => runs.iter().filter_map(|r| r.as_array()).flatten().collect(),
+            (None, Some(Value::Array(ms))) => ms.iter().collect(),
+            _ => return Err("Formato CapFrameX não reconhecido: não encontrou frame times".into()),
+        };
+        frametimes = raw.into_iter().filter_map(|v| v.as_f64()).filter(|&v| v > 0.0).collect();
+    }
+
+    if frametimes.is_empty() {
+        return Err("Nenhum frame válido encontrado no CapFrameX JSON".into());
+    }
+
+    let process = json.get("Info")
+        .and_then(|i| i.get("ProcessName"))
+        .and_then(|p| p.as_str())
+        .or_else(|| json.get("ProcessName").and_then(|p| p.as_str()))
+        .unwrap_or("Unknown")
+        .to_string();
+
+    // Older captures only note the mode of the whole session
+    if present_modes.is_empty() {
+        if let Some(mode) = json.get("Info").and_then(|i| i.get("PresentationMode")).and_then(|m| m.as_str()) {
+            present_modes.push((mode.to_string(), frametimes.len()));
+        }
+    }
+
+    series.prune();
+    Ok(Capture {
+        format: Format::CapFrameX,
+        file_name: file_name.to_string(),
+        process,
+        // Build timestamps from cumulative frame times
+        timestamps: timestamps(Vec::new(), &frametimes, None),
+        frametimes,
+        dropped,
+        series,
+        present_modes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(name: &str, text: &str) -> Capture {
+        parse(text, name).unwrap_or_else(|e| panic!("{}: {}", name, e))
+    }
+
+    #[test]
+    fn presentmon_1x() {
+        let c = sample("presentmon.csv", include_str!("../fixtures/captures/presentmon.csv"));
+        assert_eq!(c.format, Format::PresentMon);
+        assert_eq!(c.process, "cs2.exe");
+        assert_eq!(c.frametimes.len(), 24);
+        assert_eq!(c.dropped, 1);
+        assert!((c.duration_secs() - 0.0785).abs() < 1e-6);
+        assert_eq!(c.series.display_change.as_ref().map(Vec::len), Some(24));
+        assert!(c.series.cpu_busy.is_none() && c.series.pc_latency.is_none());
+        assert_eq!(c.present_modes, [("Hardware: Independent Flip".to_string(), 24)]);
+    }
+
+    #[test]
+    fn presentmon_2x() {
+        let c = sample("presentmon2.csv", include_str!("../fixtures/captures/presentmon2.csv"));
+        assert_eq!(c.format, Format::PresentMon2);
+        assert_eq!(c.frametimes.len(), 24);
+        // Not displayed: MsUntilDisplayed is NA
+        assert_eq!(c.dropped, 2);
+        // CPUStartTime is in ms here
+        assert!((c.duration_secs() - 0.0785).abs() < 1e-6);
+        let busy = c.series.cpu_busy.as_ref().unwrap();
+        assert_eq!(busy.len(), 24);
+        assert!((busy[0] - 2.91).abs() < 1e-9);
+        assert!(c.series.gpu_busy.is_some());
+        assert!(c.series.pc_latency.as_ref().unwrap()[1] > 10.0);
+        assert!(c.series.display_change.as_ref().unwrap()[5].is_nan());
+        assert!(c.series.display_latency.as_ref().unwrap()[17].is_nan());
+    }
+
+    #[test]
+    fn frameview() {
+        let c = sample("FrameView_cs2.csv", include_str!("../fixtures/captures/frameview.csv"));
+        assert_eq!(c.format, Format::FrameView);
+        assert_eq!(c.process, "cs2.exe");
+        assert_eq!(c.frametimes.len(), 24);
+        assert_eq!(c.dropped, 0);
+        assert!(c.series.gpu_busy.is_some() && c.series.pc_latency.is_some());
+        assert!((c.series.gpu_load.as_ref().unwrap()[0] - 97.0).abs() < 1e-9);
+        assert!(c.series.cpu_load.is_some());
+    }
+
+    #[test]
+    fn ocat() {
+        let c = sample("OCAT-cs2.exe-2026-10-17T120000.csv", include_str!("../fixtures/captures/ocat.csv"));
+        assert_eq!(c.format, Format::Ocat);
+        assert_eq!(c.process, "cs2.exe");
+        assert_eq!(c.frametimes.len(), 24);
+        assert_eq!(c.dropped, 1);
+        assert!(c.series.display_change.is_some());
+    }
+
+    #[test]
+    fn mangohud() {
+        let c = sample("cs2_2026-10-17_12-00-00.csv", include_str!("../fixtures/captures/mangohud.csv"));
+        assert_eq!(c.format, Format::MangoHud);
+        assert_eq!(c.process, "cs2");
+        assert_eq!(c.frametimes.len(), 24);
+        // elapsed is in ns
+        assert!((c.duration_secs() - 0.0785).abs() < 1e-6);
+        assert!((c.series.cpu_load.as_ref().unwrap()[0] - 34.0).abs() < 1e-9);
+        assert!(c.series.gpu_load.is_some());
+    }
+
+    #[test]
+    fn capframex() {
+        let c = sample("CX_cs2.json", include_str!("../fixtures/captures/capframex.json"));
+        assert_eq!(c.format, Format::CapFrameX);
+        assert_eq!(c.process, "cs2.exe");
+        assert_eq!(c.frametimes.len(), 24);
+        assert_eq!(c.dropped, 1);
+        assert_eq!(c.series.pc_latency.as_ref().map(Vec::len), Some(24));
+        assert_eq!(c.series.gpu_busy.as_ref().map(Vec::len), Some(24));
+    }
+
+    #[test]
+    fn quoted_fields() {
+        assert_eq!(fields(r#"cs2.exe,"Ryzen 7, 8-Core","say ""hi""",3.3"#), ["cs2.exe", "Ryzen 7, 8-Core", "say \"hi\"", "3.3"]);
+    }
+}
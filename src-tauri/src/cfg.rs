@@ -0,0 +1,601 @@
+// ────────────────────────────────────────────────────────────────────
+// CS2 cfg parser and linter. A cfg is parsed line by line the way the
+// console reads it: `//` starts a comment and `;` chains statements,
+// both only outside double quotes. bind / alias bodies are parsed into
+// statements of their own so the linter can follow them.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::cvars::{self, CvarType};
+use std::collections::{HashMap, HashSet};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct CfgFile {
+    pub lines: Vec<Line>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Line {
+    /// 1-based
+    pub number: usize,
+    pub raw: String,
+    pub statements: Vec<Statement>,
+    /// Text after `//`, without the slashes
+    pub comment: Option<String>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+pub struct Arg {
+    pub text: String,
+    pub quoted: bool,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Statement {
+    /// A cvar assignment or concommand call
+    Command { name: String, args: Vec<Arg> },
+    Bind { key: String, command: String, body: Vec<Statement> },
+    Alias { name: String, command: String, body: Vec<Statement> },
+    Exec { file: String },
+}
+
+impl std::fmt::Display for Arg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.quoted {
+            write!(f, "\"{}\"", self.text)
+        } else {
+            f.write_str(&self.text)
+        }
+    }
+}
+
+/// Statements print back in console syntax (bodies quoted).
+impl std::fmt::Display for Statement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Statement::Command { name, args } => {
+                f.write_str(name)?;
+                for a in args {
+                    write!(f, " {}", a)?;
+                }
+                Ok(())
+            }
+            Statement::Bind { key, command, .. } => write!(f, "bind \"{}\" \"{}\"", key, command),
+            Statement::Alias { name, command, .. } => write!(f, "alias \"{}\" \"{}\"", name, command),
+            Statement::Exec { file } => write!(f, "exec {}", file),
+        }
+    }
+}
+
+pub fn parse(content: &str) -> CfgFile {
+    let content = content.trim_start_matches('\u{FEFF}');
+    CfgFile {
+        lines: content
+            .lines()
+            .enumerate()
+            .map(|(i, raw)| {
+                let (code, comment) = split_comment(raw);
+                Line {
+                    number: i + 1,
+                    raw: raw.to_string(),
+                    statements: parse_statements(code),
+                    comment: comment.map(str::to_string),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Split off a `//` comment that isn't inside quotes.
+fn split_comment(line: &str) -> (&str, Option<&str>) {
+    let mut in_quote = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => in_quote = !in_quote,
+            '/' if !in_quote && line[i + 1..].starts_with('/') => {
+                return (&line[..i], Some(&line[i + 2..]));
+            }
+            _ => {}
+        }
+    }
+    (line, None)
+}
+
+/// Parse a `;` chain (a cfg line or a bind / alias body).
+fn parse_statements(code: &str) -> Vec<Statement> {
+    split_chain(code)
+        .into_iter()
+        .filter_map(|s| statement(tokenize(s)))
+        .collect()
+}
+
+fn split_chain(code: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quote = false;
+    let mut start = 0;
+    for (i, ch) in code.char_indices() {
+        match ch {
+            '"' => in_quote = !in_quote,
+            ';' if !in_quote => {
+                parts.push(&code[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&code[start..]);
+    parts
+}
+
+/// Whitespace-separated words; a quoted run is one word. An unterminated
+/// quote runs to the end of the line, as in the console.
+fn tokenize(s: &str) -> Vec<Arg> {
+    let mut args = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+        } else if ch == '"' {
+            chars.next();
+            let text: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            args.push(Arg { text, quoted: true });
+        } else {
+            let mut text = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            args.push(Arg { text, quoted: false });
+        }
+    }
+    args
+}
+
+/// bind / alias take the rest of the statement when the body isn't quoted.
+fn body_text(args: &[Arg]) -> String {
+    match args {
+        [only] => only.text.clone(),
+        _ => args.iter().map(|a| a.text.as_str()).collect::<Vec<_>>().join(" "),
+    }
+}
+
+fn statement(mut tokens: Vec<Arg>) -> Option<Statement> {
+    if tokens.is_empty() {
+        return None;
+    }
+    let name = tokens.remove(0).text;
+    let lower = name.to_ascii_lowercase();
+    Some(match lower.as_str() {
+        "bind" if !tokens.is_empty() => {
+            let key = tokens.remove(0).text;
+            let command = body_text(&tokens);
+            Statement::Bind { key, body: parse_statements(&command), command }
+        }
+        "alias" if !tokens.is_empty() => {
+            let name = tokens.remove(0).text;
+            let command = body_text(&tokens);
+            Statement::Alias { name, body: parse_statements(&command), command }
+        }
+        "exec" if !tokens.is_empty() => Statement::Exec { file: tokens.remove(0).text },
+        _ => Statement::Command { name, args: tokens },
+    })
+}
+
+// ── Lint ──
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LintCode {
+    UnknownCvar,
+    InvalidValue,
+    OutOfRange,
+    /// CS:GO command that CS2 ignores
+    Removed,
+    /// Needs sv_cheats
+    Cheat,
+    Duplicate,
+    RecursiveAlias,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct LintIssue {
+    pub line: usize,
+    pub severity: Severity,
+    pub code: LintCode,
+    /// Cvar, command, bind key or alias the issue is about
+    pub name: String,
+    pub message: String,
+}
+
+pub fn lint(file: &CfgFile, db: &cvars::Database) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    // Aliases are usable anywhere in the file once defined, and +name
+    // aliases bring their -name counterpart with them
+    let mut aliases: HashMap<String, usize> = HashMap::new();
+    for line in &file.lines {
+        collect_aliases(&line.statements, line.number, &mut aliases);
+    }
+
+    let mut set_at: HashMap<String, usize> = HashMap::new();
+    let mut bound_at: HashMap<String, usize> = HashMap::new();
+    for line in &file.lines {
+        for st in &line.statements {
+            match st {
+                Statement::Command { name, args } if !args.is_empty() => {
+                    let is_cvar = db.get(name).is_some_and(|c| c.kind != CvarType::Command);
+                    if is_cvar {
+                        if let Some(first) = set_at.insert(name.to_ascii_lowercase(), line.number) {
+                            issues.push(LintIssue {
+                                line: line.number,
+                                severity: Severity::Warning,
+                                code: LintCode::Duplicate,
+                                name: name.clone(),
+                                message: format!(
+                                    "{} is already set on line {}; the later value wins",
+                                    name, first
+                                ),
+                            });
+                        }
+                    }
+                }
+                Statement::Bind { key, .. } => {
+                    if let Some(first) = bound_at.insert(key.to_ascii_lowercase(), line.number) {
+                        issues.push(LintIssue {
+                            line: line.number,
+                            severity: Severity::Warning,
+                            code: LintCode::Duplicate,
+                            name: key.clone(),
+                            message: format!(
+                                "Key {} is already bound on line {}; this bind replaces it",
+                                key, first
+                            ),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        lint_statements(&line.statements, line.number, db, &aliases, &mut issues);
+    }
+
+    lint_recursion(file, &mut issues);
+    issues.sort_by_key(|i| (i.line, i.severity));
+    issues
+}
+
+fn collect_aliases(statements: &[Statement], line: usize, aliases: &mut HashMap<String, usize>) {
+    for st in statements {
+        if let Statement::Alias { name, body, .. } = st {
+            let lower = name.to_ascii_lowercase();
+            if let Some(rest) = lower.strip_prefix('+') {
+                aliases.entry(format!("-{}", rest)).or_insert(line);
+            }
+            aliases.entry(lower).or_insert(line);
+            collect_aliases(body, line, aliases);
+        }
+    }
+}
+
+fn lint_statements(
+    statements: &[Statement],
+    line: usize,
+    db: &cvars::Database,
+    aliases: &HashMap<String, usize>,
+    issues: &mut Vec<LintIssue>,
+) {
+    for st in statements {
+        match st {
+            Statement::Command { name, args } => lint_command(name, args, line, db, aliases, issues),
+            Statement::Bind { body, .. } | Statement::Alias { body, .. } => {
+                lint_statements(body, line, db, aliases, issues)
+            }
+            Statement::Exec { .. } => {}
+        }
+    }
+}
+
+fn lint_command(
+    name: &str,
+    args: &[Arg],
+    line: usize,
+    db: &cvars::Database,
+    aliases: &HashMap<String, usize>,
+    issues: &mut Vec<LintIssue>,
+) {
+    let mut push = |severity, code, message: String| {
+        issues.push(LintIssue { line, severity, code, name: name.to_string(), message })
+    };
+
+    if aliases.contains_key(&name.to_ascii_lowercase()) {
+        return;
+    }
+    if let Some(r) = db.removed(name) {
+        push(Severity::Warning, LintCode::Removed, format!("{} does nothing in CS2: {}", name, r.note));
+        return;
+    }
+    let Some(cvar) = db.get(name) else {
+        let (severity, hint) = if db.is_complete() {
+            (Severity::Warning, "")
+        } else {
+            (Severity::Info, " (import a cvarlist dump for a complete check)")
+        };
+        push(severity, LintCode::UnknownCvar, format!("Unknown cvar or command {}{}", name, hint));
+        return;
+    };
+    if cvar.cheat {
+        push(
+            Severity::Warning,
+            LintCode::Cheat,
+            format!(
+                "{} is a cheat {} and only works with sv_cheats 1",
+                name,
+                if cvar.kind == CvarType::Command { "command" } else { "cvar" }
+            ),
+        );
+    }
+
+    let Some(value) = args.first().map(|a| a.text.trim()) else { return };
+    match cvar.kind {
+        CvarType::Command | CvarType::String => {}
+        CvarType::Bool => {
+            if !matches!(value.to_ascii_lowercase().as_str(), "0" | "1" | "true" | "false") {
+                push(
+                    Severity::Error,
+                    LintCode::InvalidValue,
+                    format!("{} expects 0 or 1, got \"{}\"", name, value),
+                );
+            }
+        }
+        CvarType::Int | CvarType::Float => {
+            let Ok(v) = value.parse::<f64>() else {
+                push(
+                    Severity::Error,
+                    LintCode::InvalidValue,
+                    format!("{} expects a number, got \"{}\"", name, value),
+                );
+                return;
+            };
+            if cvar.kind == CvarType::Int && v.fract() != 0.0 {
+                push(
+                    Severity::Warning,
+                    LintCode::InvalidValue,
+                    format!("{} expects a whole number; {} is truncated", name, value),
+                );
+            }
+            let below = cvar.min.is_some_and(|min| v < min);
+            let above = cvar.max.is_some_and(|max| v > max);
+            if below || above {
+                let bound = |b: Option<f64>| b.map_or("…".to_string(), |b| b.to_string());
+                push(
+                    Severity::Warning,
+                    LintCode::OutOfRange,
+                    format!(
+                        "{} {} is outside {}–{}; the game clamps it",
+                        name,
+                        value,
+                        bound(cvar.min),
+                        bound(cvar.max)
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Report alias cycles (a → b → a). Running one locks up the console.
+fn lint_recursion(file: &CfgFile, issues: &mut Vec<LintIssue>) {
+    // Every definition adds edges, including ones nested in other aliases
+    // (toggle patterns redefine an alias from inside another)
+    let mut calls: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut defined_at: HashMap<String, usize> = HashMap::new();
+    fn walk(
+        statements: &[Statement],
+        line: usize,
+        calls: &mut HashMap<String, HashSet<String>>,
+        defined_at: &mut HashMap<String, usize>,
+    ) {
+        for st in statements {
+            if let Statement::Alias { name, body, .. } = st {
+                let name = name.to_ascii_lowercase();
+                defined_at.entry(name.clone()).or_insert(line);
+                let edges = calls.entry(name).or_default();
+                for b in body {
+                    if let Statement::Command { name, .. } = b {
+                        edges.insert(name.to_ascii_lowercase());
+                    }
+                }
+                walk(body, line, calls, defined_at);
+            }
+        }
+    }
+    for line in &file.lines {
+        walk(&line.statements, line.number, &mut calls, &mut defined_at);
+    }
+
+    let mut names: Vec<&String> = calls.keys().collect();
+    names.sort_by_key(|n| (defined_at[*n], n.as_str()));
+    let mut reported: HashSet<Vec<String>> = HashSet::new();
+    for start in names {
+        let mut path = vec![start.clone()];
+        if let Some(cycle) = find_cycle(start, &calls, &mut path, &mut HashSet::new()) {
+            let mut key = cycle.clone();
+            key.sort();
+            if reported.insert(key) {
+                issues.push(LintIssue {
+                    line: defined_at[start],
+                    severity: Severity::Error,
+                    code: LintCode::RecursiveAlias,
+                    name: start.clone(),
+                    message: format!("Alias calls itself: {} → {}", cycle.join(" → "), cycle[0]),
+                });
+            }
+        }
+    }
+}
+
+/// Depth-first search for a path back to `path[0]`.
+fn find_cycle(
+    node: &str,
+    calls: &HashMap<String, HashSet<String>>,
+    path: &mut Vec<String>,
+    visited: &mut HashSet<String>,
+) -> Option<Vec<String>> {
+    let mut next: Vec<&String> = calls.get(node)?.iter().collect();
+    next.sort();
+    for n in next {
+        if *n == path[0] {
+            return Some(path.clone());
+        }
+        if calls.contains_key(n) && visited.insert(n.clone()) {
+            path.push(n.clone());
+            if let Some(c) = find_cycle(n, calls, path, visited) {
+                return Some(c);
+            }
+            path.pop();
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_text(text: &str) -> Vec<(usize, Severity, LintCode, String)> {
+        lint(&parse(text), cvars::builtin())
+            .into_iter()
+            .map(|i| (i.line, i.severity, i.code, i.name))
+            .collect()
+    }
+
+    #[test]
+    fn parses_quotes_chains_and_comments() {
+        let file = parse(
+            "\u{FEFF}fps_max \"300\" // cap\n\
+             echo \"a;b // c\"; volume 0.5\n\
+             bind \"MOUSE4\" \"+jump; -duck\"\n\
+             alias +jt +jump\n\
+             exec configs/practice\n\
+             say \"unterminated ; still quoted\n\
+             \n\
+             // only a comment",
+        );
+        assert_eq!(file.lines.len(), 8);
+        let l = &file.lines[0];
+        assert_eq!((l.number, l.comment.as_deref()), (1, Some(" cap")));
+        match &l.statements[..] {
+            [Statement::Command { name, args }] => {
+                assert_eq!(name, "fps_max");
+                assert_eq!(args, &[Arg { text: "300".into(), quoted: true }]);
+            }
+            other => panic!("{:?}", other),
+        }
+
+        // ; and // inside quotes belong to the argument
+        let l = &file.lines[1];
+        assert!(l.comment.is_none());
+        assert_eq!(l.statements.len(), 2);
+        assert_eq!(l.statements[0].to_string(), "echo \"a;b // c\"");
+        assert_eq!(l.statements[1].to_string(), "volume 0.5");
+
+        match &file.lines[2].statements[..] {
+            [Statement::Bind { key, command, body }] => {
+                assert_eq!((key.as_str(), command.as_str()), ("MOUSE4", "+jump; -duck"));
+                assert_eq!(body.iter().map(|s| s.to_string()).collect::<Vec<_>>(), ["+jump", "-duck"]);
+            }
+            other => panic!("{:?}", other),
+        }
+        // An unquoted body takes the rest of the statement
+        assert_eq!(file.lines[3].statements[0].to_string(), "alias \"+jt\" \"+jump\"");
+        assert!(matches!(&file.lines[4].statements[..], [Statement::Exec { file }] if file == "configs/practice"));
+        // An open quote runs to the end of the line
+        assert_eq!(file.lines[5].statements[0].to_string(), "say \"unterminated ; still quoted\"");
+        assert!(file.lines[6].statements.is_empty());
+        assert!(file.lines[7].statements.is_empty() && file.lines[7].comment.is_some());
+    }
+
+    #[test]
+    fn lints_unknown_removed_and_bad_values() {
+        let issues = lint_text(
+            "fps_max 5000\n\
+             m_rawinput 2\n\
+             sensitivity abc\n\
+             cl_interp 0\n\
+             cl_showfps 1.5\n\
+             noclip\n\
+             cl_not_a_cvar 1\n\
+             fps_max 400\n\
+             bind mouse1 +attack\n\
+             bind MOUSE1 +attack2\n",
+        );
+        assert_eq!(
+            issues,
+            [
+                (1, Severity::Warning, LintCode::OutOfRange, "fps_max".into()),
+                (2, Severity::Error, LintCode::InvalidValue, "m_rawinput".into()),
+                (3, Severity::Error, LintCode::InvalidValue, "sensitivity".into()),
+                (4, Severity::Warning, LintCode::Removed, "cl_interp".into()),
+                (5, Severity::Warning, LintCode::InvalidValue, "cl_showfps".into()),
+                (6, Severity::Warning, LintCode::Cheat, "noclip".into()),
+                (7, Severity::Info, LintCode::UnknownCvar, "cl_not_a_cvar".into()),
+                (8, Severity::Warning, LintCode::Duplicate, "fps_max".into()),
+                (10, Severity::Warning, LintCode::Duplicate, "MOUSE1".into()),
+            ]
+        );
+
+        // With a full cvarlist unknown names are likely typos
+        let full = cvars::from_cvarlist(include_str!("../fixtures/cvars/cvarlist.txt"), cvars::builtin()).unwrap();
+        let issues = lint(&parse("cl_not_a_cvar 1\n"), &full);
+        assert_eq!((issues[0].severity, issues[0].code), (Severity::Warning, LintCode::UnknownCvar));
+        assert!(!issues[0].message.contains("cvarlist"));
+    }
+
+    #[test]
+    fn reports_alias_cycles_once() {
+        let issues = lint_text(
+            "alias a \"volume 0; b\"\n\
+             alias b \"c\"\n\
+             alias c \"a\"\n\
+             alias loop loop\n\
+             alias +fwd \"+forward; exec fwd\"\n\
+             bind w +fwd\n",
+        );
+        assert_eq!(
+            issues,
+            [
+                (1, Severity::Error, LintCode::RecursiveAlias, "a".into()),
+                (4, Severity::Error, LintCode::RecursiveAlias, "loop".into()),
+            ]
+        );
+        let messages: Vec<String> =
+            lint(&parse("alias a b\nalias b a\n"), cvars::builtin()).into_iter().map(|i| i.message).collect();
+        assert_eq!(messages, ["Alias calls itself: a → b → a"]);
+    }
+
+    #[test]
+    fn toggle_aliases_are_not_cycles() {
+        // Aliases redefined from inside another alias, and the implied -alias
+        let issues = lint_text(
+            "alias vt vt_on\n\
+             alias vt_on \"volume 1; alias vt vt_off\"\n\
+             alias vt_off \"volume 0; alias vt vt_on\"\n\
+             alias +crouchjump \"+jump; +duck\"\n\
+             alias -crouchjump \"-jump; -duck\"\n\
+             bind v vt\n\
+             bind space +crouchjump\n",
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+}
@@ -0,0 +1,461 @@
+// ────────────────────────────────────────────────────────────────────
+// Competitive compatibility checks. Rule sets for FACEIT, ESEA-style
+// leagues and Valve matchmaking flag profile tweaks, launch options,
+// running processes and cfg commands that an anti-cheat refuses or that
+// hurt matchmaking. The rules are data (compat_rules.json); a newer file
+// in the app data folder replaces the embedded one without a rebuild.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::{cfg, cvars, tweaks};
+use std::sync::OnceLock;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The client won't start, or the match can't be joined
+    Blocking,
+    Warning,
+}
+
+/// What a rule looks for. Names are matched case-insensitively.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum Condition {
+    /// A catalog tweak (by its UI key, e.g. "w_spectre") is enabled
+    Tweak { key: String },
+    /// The launch options contain this option
+    LaunchOption { option: String },
+    /// One of these processes is running (".exe" optional); unknown when
+    /// the process list couldn't be read
+    Process { names: Vec<String> },
+    /// The cfg runs one of these commands, directly or in a bind / alias
+    CfgCommand { names: Vec<String> },
+    /// The cfg sets a cvar flagged as needing sv_cheats
+    CheatCvar,
+    /// Windows build in range; an unknown build is unknown, so neither
+    /// this nor its `not` matches
+    Os {
+        #[serde(default)]
+        min_build: Option<u32>,
+        #[serde(default)]
+        max_build: Option<u32>,
+    },
+    All { of: Vec<Condition> },
+    Any { of: Vec<Condition> },
+    Not { of: Box<Condition> },
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Rule {
+    pub id: String,
+    pub severity: Severity,
+    pub when: Condition,
+    pub title: String,
+    pub explanation: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct RuleSet {
+    /// "faceit", "esea", "valve_mm"
+    pub id: String,
+    pub name: String,
+    pub rules: Vec<Rule>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Rules {
+    /// Date of the rule data, shown with the results
+    pub updated: String,
+    pub rulesets: Vec<RuleSet>,
+}
+
+static BUILTIN: OnceLock<Rules> = OnceLock::new();
+
+pub fn builtin() -> &'static Rules {
+    BUILTIN.get_or_init(|| {
+        serde_json::from_str(include_str!("compat_rules.json")).expect("compat_rules.json is malformed")
+    })
+}
+
+impl Rules {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let rules: Rules = serde_json::from_str(text.trim_start_matches('\u{FEFF}'))
+            .map_err(|e| format!("Compatibility rules parse error: {}", e))?;
+        if rules.rulesets.is_empty() {
+            return Err("Compatibility rules contain no rule sets".into());
+        }
+        Ok(rules)
+    }
+
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read compatibility rules: {}", e))?;
+        Self::parse(&text)
+    }
+}
+
+/// What the rules are checked against.
+pub struct Context<'a> {
+    /// OptimizationConfig as JSON
+    pub config: &'a serde_json::Value,
+    pub launch_options: &'a str,
+    /// Running process names; None when the list couldn't be read
+    pub processes: Option<&'a [String]>,
+    pub cfg: &'a cfg::CfgFile,
+    pub cvars: &'a cvars::Database,
+    pub os_build: Option<u32>,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct Issue {
+    pub rule: String,
+    pub severity: Severity,
+    pub title: String,
+    pub explanation: String,
+    /// What matched: tweak names, options, processes, cfg lines
+    pub evidence: Vec<String>,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct RuleSetResult {
+    pub id: String,
+    pub name: String,
+    pub blocking: Vec<Issue>,
+    pub warnings: Vec<Issue>,
+}
+
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct Report {
+    pub rules_updated: String,
+    /// False when process rules were skipped
+    pub processes_checked: bool,
+    pub rulesets: Vec<RuleSetResult>,
+}
+
+fn process_name(name: &str) -> String {
+    let name = name.trim().to_ascii_lowercase();
+    name.strip_suffix(".exe").map(str::to_string).unwrap_or(name)
+}
+
+/// Every Command statement of the cfg with its line, bind and alias
+/// bodies included.
+fn commands(file: &cfg::CfgFile) -> Vec<(&cfg::Line, &str)> {
+    fn walk<'a>(line: &'a cfg::Line, statements: &'a [cfg::Statement], out: &mut Vec<(&'a cfg::Line, &'a str)>) {
+        for s in statements {
+            match s {
+                cfg::Statement::Command { name, .. } => out.push((line, name)),
+                cfg::Statement::Bind { body, .. } | cfg::Statement::Alias { body, .. } => walk(line, body, out),
+                cfg::Statement::Exec { .. } => {}
+            }
+        }
+    }
+    let mut out = Vec::new();
+    for line in &file.lines {
+        walk(line, &line.statements, &mut out);
+    }
+    out
+}
+
+fn cfg_evidence(ctx: &Context, matches: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for (line, name) in commands(ctx.cfg) {
+        let entry = format!("cfg line {}: {}", line.number, line.raw.trim());
+        if matches(name) && !out.contains(&entry) {
+            out.push(entry);
+        }
+    }
+    out
+}
+
+/// A condition's value. Unknown comes from facts that couldn't be read
+/// (the process list, the Windows build) and propagates as in three-valued
+/// logic, so `not` over an unknown stays unknown and the rule never fires
+/// on a guess.
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// Holds, with the evidence
+    Match(Vec<String>),
+    NoMatch,
+    Unknown,
+}
+
+fn eval(cond: &Condition, ctx: &Context) -> Outcome {
+    use Outcome::*;
+    let found = |v: Vec<String>| if v.is_empty() { NoMatch } else { Match(v) };
+    match cond {
+        Condition::Tweak { key } => match tweaks::catalog().find(key) {
+            Some(tweak) if tweak.is_enabled(ctx.config) => Match(vec![format!("Tweak: {}", tweak.name())]),
+            _ => NoMatch,
+        },
+        Condition::LaunchOption { option } => found(
+            ctx.launch_options
+                .split_whitespace()
+                .filter(|o| o.eq_ignore_ascii_case(option))
+                .map(|o| format!("Launch option: {}", o))
+                .take(1)
+                .collect(),
+        ),
+        Condition::Process { names } => {
+            let Some(processes) = ctx.processes else { return Unknown };
+            let wanted: Vec<String> = names.iter().map(|n| process_name(n)).collect();
+            let mut hits: Vec<String> = processes
+                .iter()
+                .filter(|p| wanted.contains(&process_name(p)))
+                .map(|p| format!("Running: {}", p))
+                .collect();
+            hits.dedup();
+            found(hits)
+        }
+        Condition::CfgCommand { names } => {
+            found(cfg_evidence(ctx, |n| names.iter().any(|w| w.eq_ignore_ascii_case(n))))
+        }
+        Condition::CheatCvar => found(cfg_evidence(ctx, |n| ctx.cvars.get(n).is_some_and(|c| c.cheat))),
+        Condition::Os { min_build, max_build } => {
+            let Some(build) = ctx.os_build else { return Unknown };
+            let inside = min_build.map_or(true, |m| build >= m) && max_build.map_or(true, |m| build <= m);
+            if inside { Match(vec![format!("Windows build {}", build)]) } else { NoMatch }
+        }
+        Condition::All { of } => {
+            let mut out = Vec::new();
+            let mut unknown = false;
+            for c in of {
+                match eval(c, ctx) {
+                    Match(v) => out.extend(v),
+                    NoMatch => return NoMatch,
+                    Unknown => unknown = true,
+                }
+            }
+            if unknown { Unknown } else { Match(out) }
+        }
+        Condition::Any { of } => {
+            let mut out = Vec::new();
+            let mut hit = false;
+            let mut unknown = false;
+            for c in of {
+                match eval(c, ctx) {
+                    Match(v) => {
+                        hit = true;
+                        out.extend(v);
+                    }
+                    NoMatch => {}
+                    Unknown => unknown = true,
+                }
+            }
+            match (hit, unknown) {
+                (true, _) => Match(out),
+                (false, true) => Unknown,
+                (false, false) => NoMatch,
+            }
+        }
+        Condition::Not { of } => match eval(of, ctx) {
+            Match(_) => NoMatch,
+            NoMatch => Match(Vec::new()),
+            Unknown => Unknown,
+        },
+    }
+}
+
+/// Check the context against the rule sets named in `only` (all when empty).
+pub fn check(rules: &Rules, ctx: &Context, only: &[String]) -> Report {
+    let rulesets = rules
+        .rulesets
+        .iter()
+        .filter(|rs| only.is_empty() || only.iter().any(|id| id.eq_ignore_ascii_case(&rs.id)))
+        .map(|rs| {
+            let (blocking, warnings): (Vec<Issue>, Vec<Issue>) = rs
+                .rules
+                .iter()
+                .filter_map(|r| {
+                    let Outcome::Match(evidence) = eval(&r.when, ctx) else { return None };
+                    Some(Issue {
+                        evidence,
+                        rule: r.id.clone(),
+                        severity: r.severity,
+                        title: r.title.clone(),
+                        explanation: r.explanation.clone(),
+                    })
+                })
+                .partition(|i| i.severity == Severity::Blocking);
+            RuleSetResult { id: rs.id.clone(), name: rs.name.clone(), blocking, warnings }
+        })
+        .collect();
+    Report {
+        rules_updated: rules.updated.clone(),
+        processes_checked: ctx.processes.is_some(),
+        rulesets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Facts {
+        config: serde_json::Value,
+        launch_options: &'static str,
+        processes: Option<Vec<String>>,
+        cfg: cfg::CfgFile,
+        os_build: Option<u32>,
+    }
+
+    impl Facts {
+        fn new() -> Self {
+            Facts {
+                config: serde_json::json!({ "windows": {}, "bios": {} }),
+                launch_options: "",
+                processes: Some(Vec::new()),
+                cfg: cfg::parse(""),
+                os_build: Some(19045),
+            }
+        }
+
+        fn ctx(&self) -> Context<'_> {
+            Context {
+                config: &self.config,
+                launch_options: self.launch_options,
+                processes: self.processes.as_deref(),
+                cfg: &self.cfg,
+                cvars: cvars::builtin(),
+                os_build: self.os_build,
+            }
+        }
+
+        fn enable(&mut self, key: &str) {
+            tweaks::catalog().find(key).unwrap().set_enabled(&mut self.config, true);
+        }
+    }
+
+    fn cond(json: serde_json::Value) -> Condition {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn fired(report: &Report, ruleset: &str) -> Vec<String> {
+        let rs = report.rulesets.iter().find(|r| r.id == ruleset).unwrap();
+        rs.blocking.iter().chain(&rs.warnings).map(|i| i.rule.clone()).collect()
+    }
+
+    #[test]
+    fn builtin_rules_parse() {
+        let rules = builtin();
+        let text = serde_json::to_string(rules).unwrap();
+        assert_eq!(Rules::parse(&text).unwrap().rulesets.len(), rules.rulesets.len());
+        assert!(Rules::parse(r#"{"updated": "x", "rulesets": []}"#).is_err());
+    }
+
+    #[test]
+    fn leaf_conditions() {
+        let mut f = Facts::new();
+        f.launch_options = "-novid  -INSECURE +fps_max 0";
+        f.processes = Some(vec!["explorer.exe".into(), "X64DBG.EXE".into()]);
+        f.cfg = cfg::parse("fps_max 400\nbind F1 \"noclip\"\n");
+        f.enable("w_spectre");
+        let ctx = f.ctx();
+
+        let evidence = |c| match eval(&cond(c), &ctx) {
+            Outcome::Match(v) => v,
+            other => panic!("expected a match, got {:?}", other),
+        };
+        let insecure = serde_json::json!({"check": "launch_option", "option": "-insecure"});
+        assert_eq!(evidence(insecure), ["Launch option: -INSECURE"]);
+        assert_eq!(evidence(serde_json::json!({"check": "process", "names": ["x64dbg"]})), ["Running: X64DBG.EXE"]);
+        let bind = ["cfg line 2: bind F1 \"noclip\""];
+        assert_eq!(evidence(serde_json::json!({"check": "cfg_command", "names": ["NOCLIP"]})), bind);
+        assert_eq!(evidence(serde_json::json!({"check": "cheat_cvar"})), bind);
+        assert_eq!(evidence(serde_json::json!({"check": "os", "max_build": 19045})), ["Windows build 19045"]);
+        assert_eq!(evidence(serde_json::json!({"check": "tweak", "key": "w_spectre"})).len(), 1);
+
+        for c in [
+            serde_json::json!({"check": "launch_option", "option": "-insecur"}),
+            serde_json::json!({"check": "process", "names": ["ollydbg.exe"]}),
+            serde_json::json!({"check": "cfg_command", "names": ["sv_cheats"]}),
+            serde_json::json!({"check": "os", "min_build": 22000}),
+            serde_json::json!({"check": "tweak", "key": "b_svm"}),
+            serde_json::json!({"check": "tweak", "key": "no_such_tweak"}),
+        ] {
+            assert_eq!(eval(&cond(c.clone()), &ctx), Outcome::NoMatch, "{}", c);
+        }
+    }
+
+    #[test]
+    fn combinators_collect_evidence() {
+        let mut f = Facts::new();
+        f.launch_options = "-insecure -novid";
+        let ctx = f.ctx();
+        let insecure = serde_json::json!({"check": "launch_option", "option": "-insecure"});
+        let novid = serde_json::json!({"check": "launch_option", "option": "-novid"});
+        let missing = serde_json::json!({"check": "launch_option", "option": "-tools"});
+
+        let all = cond(serde_json::json!({"check": "all", "of": [insecure, novid]}));
+        let both = vec!["Launch option: -insecure".to_string(), "Launch option: -novid".to_string()];
+        assert_eq!(eval(&all, &ctx), Outcome::Match(both));
+        let all = cond(serde_json::json!({"check": "all", "of": [insecure, missing]}));
+        assert_eq!(eval(&all, &ctx), Outcome::NoMatch);
+        let any = cond(serde_json::json!({"check": "any", "of": [missing, novid]}));
+        assert_eq!(eval(&any, &ctx), Outcome::Match(vec!["Launch option: -novid".into()]));
+        let any = cond(serde_json::json!({"check": "any", "of": [missing]}));
+        assert_eq!(eval(&any, &ctx), Outcome::NoMatch);
+        let not = cond(serde_json::json!({"check": "not", "of": missing}));
+        assert_eq!(eval(&not, &ctx), Outcome::Match(Vec::new()));
+        let not = cond(serde_json::json!({"check": "not", "of": insecure}));
+        assert_eq!(eval(&not, &ctx), Outcome::NoMatch);
+    }
+
+    #[test]
+    fn unknown_facts_stay_unknown() {
+        let mut f = Facts::new();
+        f.processes = None;
+        f.os_build = None;
+        f.launch_options = "-insecure";
+        let ctx = f.ctx();
+        let process = serde_json::json!({"check": "process", "names": ["x64dbg"]});
+        let os = serde_json::json!({"check": "os", "min_build": 22000});
+        let insecure = serde_json::json!({"check": "launch_option", "option": "-insecure"});
+        let missing = serde_json::json!({"check": "launch_option", "option": "-tools"});
+
+        for c in [&process, &os] {
+            assert_eq!(eval(&cond(c.clone()), &ctx), Outcome::Unknown);
+            // Not over an unknown fact is unknown too, not true
+            assert_eq!(eval(&cond(serde_json::json!({"check": "not", "of": c})), &ctx), Outcome::Unknown);
+        }
+        let all = |of: serde_json::Value| eval(&cond(serde_json::json!({"check": "all", "of": of})), &ctx);
+        let any = |of: serde_json::Value| eval(&cond(serde_json::json!({"check": "any", "of": of})), &ctx);
+        assert_eq!(all(serde_json::json!([os, insecure])), Outcome::Unknown);
+        assert_eq!(all(serde_json::json!([os, missing])), Outcome::NoMatch);
+        assert_eq!(any(serde_json::json!([os, insecure])), Outcome::Match(vec!["Launch option: -insecure".into()]));
+        assert_eq!(any(serde_json::json!([os, missing])), Outcome::Unknown);
+    }
+
+    #[test]
+    fn windows_build_picks_the_faceit_rule() {
+        let mut f = Facts::new();
+        f.enable("w_spectre");
+        let only = ["faceit".to_string()];
+
+        f.os_build = Some(22631);
+        let report = check(builtin(), &f.ctx(), &only);
+        assert_eq!(report.rulesets.len(), 1);
+        assert_eq!(fired(&report, "faceit"), ["faceit_spectre_win11"]);
+        assert_eq!(report.rulesets[0].blocking.len(), 1);
+
+        f.os_build = Some(19045);
+        assert_eq!(fired(&check(builtin(), &f.ctx(), &only), "faceit"), ["faceit_spectre"]);
+
+        // Unknown build: neither the Windows 11 rule nor its "not" fires
+        f.os_build = None;
+        assert!(fired(&check(builtin(), &f.ctx(), &only), "faceit").is_empty());
+    }
+
+    #[test]
+    fn unread_process_list_skips_process_rules() {
+        let mut f = Facts::new();
+        f.processes = Some(vec!["cheatengine-x86_64.exe".into()]);
+        let report = check(builtin(), &f.ctx(), &[]);
+        assert!(report.processes_checked);
+        assert_eq!(fired(&report, "faceit"), ["faceit_debug_tools"]);
+
+        f.processes = None;
+        let report = check(builtin(), &f.ctx(), &[]);
+        assert!(!report.processes_checked);
+        assert!(report.rulesets.iter().all(|rs| rs.blocking.is_empty() && rs.warnings.is_empty()));
+    }
+}
@@ -0,0 +1,270 @@
+// ────────────────────────────────────────────────────────────────────
+// CS2 crosshair share codes. Byte 0 is a checksum over the rest, bytes
+// 2–15 hold the cl_crosshair* values packed as below.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::sharecode::{self, LEN};
+use std::collections::HashMap;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+pub struct Crosshair {
+    pub style: u8,
+    pub size: f64,
+    pub gap: f64,
+    pub thickness: f64,
+    pub color: u8,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+    pub use_alpha: bool,
+    pub dot: bool,
+    pub outline: bool,
+    pub outline_thickness: f64,
+    pub t_style: bool,
+    pub follow_recoil: bool,
+    pub fixed_gap: f64,
+    pub gap_use_weapon_value: bool,
+    pub split_distance: u8,
+    pub inner_split_alpha: f64,
+    pub outer_split_alpha: f64,
+    pub split_size_ratio: f64,
+}
+
+impl Default for Crosshair {
+    /// CS2's defaults
+    fn default() -> Self {
+        Crosshair {
+            style: 2,
+            size: 5.0,
+            gap: 1.0,
+            thickness: 0.5,
+            color: 1,
+            red: 50,
+            green: 250,
+            blue: 50,
+            alpha: 200,
+            use_alpha: true,
+            dot: true,
+            outline: true,
+            outline_thickness: 1.0,
+            t_style: false,
+            follow_recoil: false,
+            fixed_gap: 3.0,
+            gap_use_weapon_value: false,
+            split_distance: 7,
+            inner_split_alpha: 1.0,
+            outer_split_alpha: 0.5,
+            split_size_ratio: 0.35,
+        }
+    }
+}
+
+/// Accepts the code with or without the CSGO- prefix and dashes.
+pub fn decode(code: &str) -> Result<Crosshair, String> {
+    let bytes = sharecode::to_bytes(code).map_err(|e| e.replace("share code", "crosshair code"))?;
+    if bytes[0] != checksum(&bytes) {
+        return Err("Invalid crosshair code: checksum mismatch".into());
+    }
+
+    Ok(Crosshair {
+        gap: bytes[2] as i8 as f64 / 10.0,
+        outline_thickness: bytes[3] as f64 / 2.0,
+        red: bytes[4],
+        green: bytes[5],
+        blue: bytes[6],
+        alpha: bytes[7],
+        split_distance: bytes[8] & 0x7f,
+        follow_recoil: bytes[8] & 0x80 != 0,
+        fixed_gap: bytes[9] as i8 as f64 / 10.0,
+        color: bytes[10] & 0x07,
+        outline: bytes[10] & 0x08 != 0,
+        inner_split_alpha: (bytes[10] >> 4) as f64 / 10.0,
+        outer_split_alpha: (bytes[11] & 0x0f) as f64 / 10.0,
+        split_size_ratio: (bytes[11] >> 4) as f64 / 10.0,
+        thickness: bytes[12] as f64 / 10.0,
+        style: (bytes[13] & 0x0f) >> 1,
+        dot: bytes[13] & 0x10 != 0,
+        gap_use_weapon_value: bytes[13] & 0x20 != 0,
+        use_alpha: bytes[13] & 0x40 != 0,
+        t_style: bytes[13] & 0x80 != 0,
+        size: (((bytes[15] & 0x1f) as u16) << 8 | bytes[14] as u16) as f64 / 10.0,
+    })
+}
+
+pub fn encode(c: &Crosshair) -> String {
+    let tenths = |v: f64| (v * 10.0).round() as i64;
+    let size = tenths(c.size).clamp(0, 0x1fff) as u16;
+    let mut bytes = [
+        0,
+        1,
+        tenths(c.gap).clamp(-128, 127) as i8 as u8,
+        (c.outline_thickness * 2.0).round().clamp(0.0, 255.0) as u8,
+        c.red,
+        c.green,
+        c.blue,
+        c.alpha,
+        (c.split_distance & 0x7f) | if c.follow_recoil { 0x80 } else { 0 },
+        tenths(c.fixed_gap).clamp(-128, 127) as i8 as u8,
+        (c.color & 0x07)
+            | if c.outline { 0x08 } else { 0 }
+            | (tenths(c.inner_split_alpha).clamp(0, 15) as u8) << 4,
+        (tenths(c.outer_split_alpha).clamp(0, 15) as u8) | (tenths(c.split_size_ratio).clamp(0, 15) as u8) << 4,
+        tenths(c.thickness).clamp(0, 255) as u8,
+        ((c.style & 0x07) << 1)
+            | if c.dot { 0x10 } else { 0 }
+            | if c.gap_use_weapon_value { 0x20 } else { 0 }
+            | if c.use_alpha { 0x40 } else { 0 }
+            | if c.t_style { 0x80 } else { 0 },
+        (size & 0xff) as u8,
+        (size >> 8) as u8,
+        0,
+        0,
+    ];
+    bytes[0] = checksum(&bytes);
+    sharecode::from_bytes(bytes)
+}
+
+fn checksum(bytes: &[u8; LEN]) -> u8 {
+    bytes[1..].iter().fold(0u8, |acc, b| acc.wrapping_add(*b))
+}
+
+fn flag(on: bool) -> String {
+    if on { "1" } else { "0" }.to_string()
+}
+
+impl Crosshair {
+    /// The cl_crosshair* cvars this crosshair sets, in console order.
+    pub fn cvars(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("cl_crosshairstyle", self.style.to_string()),
+            ("cl_crosshairsize", self.size.to_string()),
+            ("cl_crosshairgap", self.gap.to_string()),
+            ("cl_crosshairthickness", self.thickness.to_string()),
+            ("cl_crosshaircolor", self.color.to_string()),
+            ("cl_crosshaircolor_r", self.red.to_string()),
+            ("cl_crosshaircolor_g", self.green.to_string()),
+            ("cl_crosshaircolor_b", self.blue.to_string()),
+            ("cl_crosshairalpha", self.alpha.to_string()),
+            ("cl_crosshairusealpha", flag(self.use_alpha)),
+            ("cl_crosshairdot", flag(self.dot)),
+            ("cl_crosshair_drawoutline", flag(self.outline)),
+            ("cl_crosshair_outlinethickness", self.outline_thickness.to_string()),
+            ("cl_crosshair_t", flag(self.t_style)),
+            ("cl_crosshair_recoil", flag(self.follow_recoil)),
+            ("cl_fixedcrosshairgap", self.fixed_gap.to_string()),
+            ("cl_crosshairgap_useweaponvalue", flag(self.gap_use_weapon_value)),
+            ("cl_crosshair_dynamic_splitdist", self.split_distance.to_string()),
+            ("cl_crosshair_dynamic_splitalpha_innermod", self.inner_split_alpha.to_string()),
+            ("cl_crosshair_dynamic_splitalpha_outermod", self.outer_split_alpha.to_string()),
+            ("cl_crosshair_dynamic_maxdist_splitratio", self.split_size_ratio.to_string()),
+        ]
+    }
+
+    /// Build from cvar values (names case-insensitive); cvars that are
+    /// missing or unreadable keep CS2's default.
+    pub fn from_cvars(values: &HashMap<String, String>) -> Self {
+        let values: HashMap<String, &str> =
+            values.iter().map(|(k, v)| (k.to_ascii_lowercase(), v.trim())).collect();
+        let num = |name: &str| values.get(name).and_then(|v| v.parse::<f64>().ok());
+        let byte = |name: &str, default: u8| num(name).map_or(default, |v| v.clamp(0.0, 255.0) as u8);
+        let float = |name: &str, default: f64| num(name).unwrap_or(default);
+        let boolean = |name: &str, default: bool| num(name).map_or(default, |v| v != 0.0);
+
+        let d = Crosshair::default();
+        Crosshair {
+            style: byte("cl_crosshairstyle", d.style),
+            size: float("cl_crosshairsize", d.size),
+            gap: float("cl_crosshairgap", d.gap),
+            thickness: float("cl_crosshairthickness", d.thickness),
+            color: byte("cl_crosshaircolor", d.color),
+            red: byte("cl_crosshaircolor_r", d.red),
+            green: byte("cl_crosshaircolor_g", d.green),
+            blue: byte("cl_crosshaircolor_b", d.blue),
+            alpha: byte("cl_crosshairalpha", d.alpha),
+            use_alpha: boolean("cl_crosshairusealpha", d.use_alpha),
+            dot: boolean("cl_crosshairdot", d.dot),
+            outline: boolean("cl_crosshair_drawoutline", d.outline),
+            outline_thickness: float("cl_crosshair_outlinethickness", d.outline_thickness),
+            t_style: boolean("cl_crosshair_t", d.t_style),
+            follow_recoil: boolean("cl_crosshair_recoil", d.follow_recoil),
+            fixed_gap: float("cl_fixedcrosshairgap", d.fixed_gap),
+            gap_use_weapon_value: boolean("cl_crosshairgap_useweaponvalue", d.gap_use_weapon_value),
+            split_distance: byte("cl_crosshair_dynamic_splitdist", d.split_distance),
+            inner_split_alpha: float("cl_crosshair_dynamic_splitalpha_innermod", d.inner_split_alpha),
+            outer_split_alpha: float("cl_crosshair_dynamic_splitalpha_outermod", d.outer_split_alpha),
+            split_size_ratio: float("cl_crosshair_dynamic_maxdist_splitratio", d.split_size_ratio),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = "CSGO-O4Jsi-V36wY-rTMGK-9w7qF-jQ8WB";
+
+    #[test]
+    fn decodes_a_known_code() {
+        let c = decode(CODE).unwrap();
+        assert_eq!((c.style, c.size, c.gap, c.thickness), (2, 33.0, 1.0, 4.1));
+        assert_eq!((c.color, c.red, c.green, c.blue, c.alpha), (5, 50, 250, 84, 200));
+        assert_eq!((c.use_alpha, c.dot, c.outline, c.t_style, c.follow_recoil), (false, true, false, true, false));
+        assert_eq!((c.outline_thickness, c.fixed_gap, c.split_distance), (1.5, -10.0, 127));
+        assert_eq!((c.inner_split_alpha, c.outer_split_alpha, c.split_size_ratio), (0.6, 0.8, 0.3));
+        // Without the prefix and dashes, with stray whitespace
+        assert_eq!(decode(" O4JsiV36wYrTMGK9w7qFjQ8WB ").unwrap(), c);
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        assert_eq!(encode(&decode(CODE).unwrap()), CODE);
+        let c = Crosshair {
+            style: 4,
+            size: 2.5,
+            gap: -3.2,
+            thickness: 0.6,
+            color: 4,
+            red: 0,
+            green: 255,
+            blue: 128,
+            alpha: 255,
+            use_alpha: false,
+            dot: false,
+            outline: true,
+            outline_thickness: 0.5,
+            t_style: false,
+            follow_recoil: true,
+            fixed_gap: 3.0,
+            gap_use_weapon_value: true,
+            split_distance: 3,
+            inner_split_alpha: 0.0,
+            outer_split_alpha: 1.0,
+            split_size_ratio: 1.0,
+        };
+        assert_eq!(decode(&encode(&c)).unwrap(), c);
+        // Codes store tenths: the default 0.35 split ratio comes back rounded
+        let d = decode(&encode(&Crosshair::default())).unwrap();
+        assert_eq!(d, Crosshair { split_size_ratio: 0.4, ..Crosshair::default() });
+    }
+
+    #[test]
+    fn cvars_survive_the_code() {
+        let c = decode(CODE).unwrap();
+        let values: HashMap<String, String> = c.cvars().into_iter().map(|(n, v)| (n.to_string(), v)).collect();
+        assert_eq!(values["cl_crosshairsize"], "33");
+        assert_eq!(values["cl_crosshairgap"], "1");
+        assert_eq!(Crosshair::from_cvars(&values), c);
+    }
+
+    #[test]
+    fn rejects_bad_codes() {
+        // First digit changed: the bytes still decode but no longer add up
+        let err = decode("CSGO-P4Jsi-V36wY-rTMGK-9w7qF-jQ8WB").unwrap_err();
+        assert!(err.contains("checksum"), "{}", err);
+        // 0, 1, I and l are not in the alphabet
+        let err = decode("CSGO-O4Jsi-V36wY-rTMGK-9w7qF-jQ8W0").unwrap_err();
+        assert!(err.contains("unexpected character '0'"), "{}", err);
+        assert!(decode("CSGO-O4Jsi-V36wY-rTMGK-9w7qF").unwrap_err().contains("expected 25 characters"));
+    }
+}
@@ -0,0 +1,245 @@
+// ────────────────────────────────────────────────────────────────────
+// CS2 cvar / concommand database used by the cfg linter. The builtin
+// list (cvars.json) covers what the CFG editor offers plus common
+// commands; a fuller one is built from a `cvarlist` console dump.
+// ────────────────────────────────────────────────────────────────────
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CvarType {
+    Bool,
+    Int,
+    Float,
+    String,
+    /// Concommand – takes arguments but holds no value
+    Command,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Cvar {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: CvarType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Needs sv_cheats 1
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cheat: bool,
+    /// Value is hidden from the console (passwords)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub protected: bool,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// CS:GO command that CS2 ignores or no longer knows.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Removed {
+    pub name: String,
+    pub note: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Database {
+    /// "builtin" or "cvarlist" once a console dump was imported
+    pub source: String,
+    pub cvars: Vec<Cvar>,
+    #[serde(default)]
+    pub removed: Vec<Removed>,
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+}
+
+static BUILTIN: OnceLock<Database> = OnceLock::new();
+
+pub fn builtin() -> &'static Database {
+    BUILTIN.get_or_init(|| {
+        let db: Database =
+            serde_json::from_str(include_str!("cvars.json")).expect("cvars.json is malformed");
+        db.indexed()
+    })
+}
+
+impl Database {
+    fn indexed(mut self) -> Self {
+        self.index = self
+            .cvars
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.name.to_ascii_lowercase(), i))
+            .collect();
+        self
+    }
+
+    /// Load a database written by `save` (e.g. after a cvarlist import).
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read cvar database: {}", e))?;
+        let db: Database = serde_json::from_str(text.trim_start_matches('\u{FEFF}'))
+            .map_err(|e| format!("Cvar database parse error: {}", e))?;
+        Ok(db.indexed())
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Serialize error: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write cvar database: {}", e))
+    }
+
+    /// Case-insensitive, like the console.
+    pub fn get(&self, name: &str) -> Option<&Cvar> {
+        self.index.get(&name.to_ascii_lowercase()).map(|&i| &self.cvars[i])
+    }
+
+    pub fn removed(&self, name: &str) -> Option<&Removed> {
+        self.removed.iter().find(|r| r.name.eq_ignore_ascii_case(name))
+    }
+
+    /// A dump lists every cvar, so unknown names are likely typos; the
+    /// builtin list is only a subset.
+    pub fn is_complete(&self) -> bool {
+        self.source == "cvarlist"
+    }
+}
+
+/// Build a database from `cvarlist` console output. Lines look like
+/// `fps_max : 400 : , "cl", "a" : Frame rate limiter`; concommands show
+/// `cmd` as their value. Ranges and the removed list aren't part of the
+/// dump and are carried over from `base`.
+pub fn from_cvarlist(text: &str, base: &Database) -> Result<Database, String> {
+    let mut cvars: Vec<Cvar> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for line in text.lines() {
+        let parts: Vec<&str> = line.splitn(4, ':').map(str::trim).collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        let name = parts[0];
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            continue;
+        }
+        let value = parts[1];
+        let flags: Vec<String> = parts[2]
+            .split(',')
+            .map(|f| f.trim().trim_matches('"').to_ascii_lowercase())
+            .filter(|f| !f.is_empty())
+            .collect();
+        let description = parts.get(3).copied().unwrap_or("").to_string();
+
+        let kind = if value == "cmd" {
+            CvarType::Command
+        } else if value == "true" || value == "false" {
+            CvarType::Bool
+        } else if value.parse::<i64>().is_ok() {
+            CvarType::Int
+        } else if value.parse::<f64>().is_ok() {
+            CvarType::Float
+        } else {
+            CvarType::String
+        };
+        let known = base.get(name);
+        let cvar = Cvar {
+            name: name.to_string(),
+            // The builtin type is more precise for 0/1 toggles the dump shows as ints
+            kind: match known {
+                Some(k) if kind != CvarType::Command => k.kind,
+                _ => kind,
+            },
+            default: match kind {
+                CvarType::Command => None,
+                CvarType::Bool => Some(if value == "true" { "1" } else { "0" }.to_string()),
+                _ => Some(value.to_string()),
+            },
+            min: known.and_then(|k| k.min),
+            max: known.and_then(|k| k.max),
+            cheat: flags.iter().any(|f| f == "cheat"),
+            protected: flags.iter().any(|f| f == "prot" || f == "protected"),
+            description: if description.is_empty() {
+                known.map(|k| k.description.clone()).unwrap_or_default()
+            } else {
+                description
+            },
+        };
+        match seen.get(&name.to_ascii_lowercase()) {
+            Some(&i) => cvars[i] = cvar,
+            None => {
+                seen.insert(name.to_ascii_lowercase(), cvars.len());
+                cvars.push(cvar);
+            }
+        }
+    }
+
+    if cvars.is_empty() {
+        return Err("No cvars found – paste the console output of `cvarlist`.".into());
+    }
+    // Keep builtin entries the dump doesn't show (e.g. +/- actions filtered out)
+    for c in &base.cvars {
+        if !seen.contains_key(&c.name.to_ascii_lowercase()) {
+            cvars.push(c.clone());
+        }
+    }
+    cvars.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Database {
+        source: "cvarlist".into(),
+        cvars,
+        removed: base.removed.clone(),
+        index: HashMap::new(),
+    }
+    .indexed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_lookups_ignore_case() {
+        let db = builtin();
+        assert!(!db.is_complete());
+        assert_eq!(db.get("FPS_Max").map(|c| c.kind), Some(CvarType::Float));
+        assert!(db.get("noclip").is_some_and(|c| c.cheat));
+        assert!(db.removed("CL_INTERP").is_some());
+        assert!(db.get("not_a_cvar").is_none());
+    }
+
+    #[test]
+    fn parses_a_cvarlist_dump() {
+        let base = builtin();
+        let db = from_cvarlist(include_str!("../fixtures/cvars/cvarlist.txt"), base).unwrap();
+        assert!(db.is_complete());
+
+        // Range and the more precise type come from the builtin entry
+        let fps = db.get("fps_max").unwrap();
+        assert_eq!((fps.kind, fps.default.as_deref()), (CvarType::Float, Some("400")));
+        assert_eq!((fps.min, fps.max), (Some(0.0), Some(1000.0)));
+        assert_eq!(fps.description, "Frame rate limiter");
+
+        let attack = db.get("+attack").unwrap();
+        assert_eq!((attack.kind, attack.default.as_deref()), (CvarType::Command, None));
+        assert!(db.get("god").is_some_and(|c| c.kind == CvarType::Command && c.cheat));
+        assert!(db.get("rcon_password").is_some_and(|c| c.protected && c.kind == CvarType::String));
+        assert_eq!(db.get("name").map(|c| c.kind), Some(CvarType::String));
+
+        // New in the dump: typed from the value
+        let unscope = db.get("cl_sniper_delay_unscope").unwrap();
+        assert_eq!((unscope.kind, unscope.default.as_deref()), (CvarType::Bool, Some("0")));
+        assert!(base.get("cl_sniper_delay_unscope").is_none());
+
+        // Builtin entries the dump leaves out are kept, sorted by name
+        assert!(db.get("volume").is_some());
+        assert_eq!(db.cvars.len(), base.cvars.len() + 2);
+        assert!(db.cvars.windows(2).all(|w| w[0].name <= w[1].name));
+        assert_eq!(db.removed.len(), base.removed.len());
+
+        assert!(from_cvarlist("Unknown command: cvarlist\n", base).is_err());
+    }
+}
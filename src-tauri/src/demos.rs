@@ -0,0 +1,272 @@
+// ────────────────────────────────────────────────────────────────────
+// Match share codes and replay downloads. A match code holds the match
+// id, outcome (reservation) id and token; the demo URL comes from a
+// replay endpoint – a URL template, so a local stand-in server can
+// take Valve's place in tests.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::sharecode;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MatchCode {
+    pub match_id: u64,
+    pub outcome_id: u64,
+    pub token: u16,
+}
+
+/// What the UI sees; ids are strings because they don't fit a JS number.
+#[derive(serde::Serialize)]
+pub struct MatchInfo {
+    pub share_code: String,
+    pub match_id: String,
+    pub outcome_id: String,
+    pub token: u16,
+}
+
+impl MatchCode {
+    pub fn info(&self) -> MatchInfo {
+        MatchInfo {
+            share_code: encode(self),
+            match_id: self.match_id.to_string(),
+            outcome_id: self.outcome_id.to_string(),
+            token: self.token,
+        }
+    }
+
+    /// `<match>_<outcome>.dem`, the name Valve's replay servers use.
+    pub fn demo_name(&self) -> String {
+        format!("{}_{}.dem", self.match_id, self.outcome_id)
+    }
+}
+
+/// The code's bytes hold match id, outcome id and token back to back,
+/// each little-endian.
+pub fn decode(code: &str) -> Result<MatchCode, String> {
+    let bytes = sharecode::to_bytes(code).map_err(|e| e.replace("share code", "match share code"))?;
+    let u64_at = |i: usize| {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(&bytes[i..i + 8]);
+        u64::from_le_bytes(b)
+    };
+    Ok(MatchCode {
+        match_id: u64_at(0),
+        outcome_id: u64_at(8),
+        token: u16::from_le_bytes([bytes[16], bytes[17]]),
+    })
+}
+
+pub fn encode(m: &MatchCode) -> String {
+    let mut bytes = [0u8; sharecode::LEN];
+    bytes[0..8].copy_from_slice(&m.match_id.to_le_bytes());
+    bytes[8..16].copy_from_slice(&m.outcome_id.to_le_bytes());
+    bytes[16..18].copy_from_slice(&m.token.to_le_bytes());
+    sharecode::from_bytes(bytes)
+}
+
+/// Fill `{match_id}`, `{outcome_id}`, `{token}` and `{share_code}` in a
+/// replay endpoint template.
+pub fn endpoint_url(template: &str, m: &MatchCode) -> String {
+    template
+        .replace("{match_id}", &m.match_id.to_string())
+        .replace("{outcome_id}", &m.outcome_id.to_string())
+        .replace("{token}", &m.token.to_string())
+        .replace("{share_code}", &encode(m))
+}
+
+fn is_demo_url(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.ends_with(".dem") || path.ends_with(".dem.bz2")
+}
+
+/// The endpoint either points at the demo itself or answers with its
+/// URL, as JSON `{"url": "..."}` or plain text.
+pub async fn resolve_url(client: &reqwest::Client, template: &str, m: &MatchCode) -> Result<String, String> {
+    let url = endpoint_url(template, m);
+    if is_demo_url(&url) {
+        return Ok(url);
+    }
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Replay endpoint error: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Replay endpoint returned {}", resp.status()));
+    }
+    let body = resp.text().await.map_err(|e| format!("Replay endpoint read error: {}", e))?;
+    let body = body.trim();
+    let resolved = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(v) => v["url"].as_str().map(str::to_string),
+        Err(_) => Some(body.to_string()),
+    };
+    match resolved {
+        Some(u) if u.starts_with("http://") || u.starts_with("https://") => Ok(u),
+        _ => Err("Replay endpoint did not return a demo URL (the match may have expired)".into()),
+    }
+}
+
+/// Download a demo into `folder`, decompressing `.bz2` replays.
+pub async fn download(client: &reqwest::Client, url: &str, folder: &Path, name: &str) -> Result<PathBuf, String> {
+    let resp = client.get(url).send().await.map_err(|e| format!("Download error: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Download failed: {}", resp.status()));
+    }
+    let bytes = resp.bytes().await.map_err(|e| format!("Read error: {}", e))?;
+
+    // Valve serves bzip2; a stand-in may hand out the plain demo
+    let demo = if bytes.starts_with(b"BZh") {
+        let mut out = Vec::new();
+        bzip2::read::BzDecoder::new(&bytes[..])
+            .read_to_end(&mut out)
+            .map_err(|e| format!("Decompress error: {}", e))?;
+        out
+    } else {
+        bytes.to_vec()
+    };
+
+    std::fs::create_dir_all(folder).map_err(|e| format!("Dir create failed: {}", e))?;
+    let dest = folder.join(name);
+    // Write next to the target first so an interrupted download never
+    // leaves a truncated .dem behind
+    let partial = folder.join(format!("{}.part", name));
+    std::fs::write(&partial, &demo).map_err(|e| format!("Write error: {}", e))?;
+    std::fs::rename(&partial, &dest).map_err(|e| format!("Write error: {}", e))?;
+    Ok(dest)
+}
+
+/// Index entry for a demo file, as listed by `scan_demos`.
+pub fn entry(path: &Path) -> Option<serde_json::Value> {
+    if path.extension().map(|x| x == "dem") != Some(true) {
+        return None;
+    }
+    let meta = std::fs::metadata(path).ok();
+    let size_mb = meta.as_ref().map(|m| m.len() as f64 / 1_048_576.0).unwrap_or(0.0);
+    let modified = meta.as_ref().and_then(|m| m.modified().ok())
+        .map(|t| {
+            let dur = t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+            dur.as_secs()
+        }).unwrap_or(0);
+    let fname = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    // try to extract map name from filename (common pattern: mapname_date_...)
+    let map_hint = fname.split(['_', '-']).next()
+        .unwrap_or("").to_string();
+    Some(serde_json::json!({
+        "name": fname,
+        "path": path.to_string_lossy(),
+        "size_mb": (size_mb * 10.0).round() / 10.0,
+        "modified": modified,
+        "map_hint": map_hint,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    const CODE: &str = "CSGO-GADqf-jjyJ8-cSP2r-smZRo-TO2xK";
+    const DEMO: &[u8] = b"PBDEMS2\0 stand-in demo";
+
+    /// A replay server stand-in on a free local port. Serves one request
+    /// per connection:
+    /// - `/replay/*` answers with the demo URL as JSON
+    /// - `/demos/ok.dem` and `/demos/ok.dem.bz2` serve the demo
+    /// - `/demos/cut.dem` announces more bytes than it sends
+    /// - anything else is a 404
+    fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let host = base.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                // Read the whole request head so closing doesn't reset the connection
+                let lines: Vec<String> = BufReader::new(&stream)
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|l| !l.is_empty())
+                    .collect();
+                let path = lines.first().and_then(|l| l.split_whitespace().nth(1)).unwrap_or("").to_string();
+                let (status, length, body): (&str, Option<usize>, Vec<u8>) = match path.as_str() {
+                    p if p.starts_with("/replay/") => {
+                        ("200 OK", None, format!(r#"{{"url": "{}/demos/ok.dem.bz2"}}"#, host).into_bytes())
+                    }
+                    "/demos/ok.dem" => ("200 OK", None, DEMO.to_vec()),
+                    "/demos/ok.dem.bz2" => {
+                        let mut enc = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::fast());
+                        enc.write_all(DEMO).unwrap();
+                        ("200 OK", None, enc.finish().unwrap())
+                    }
+                    "/demos/cut.dem" => ("200 OK", Some(DEMO.len() * 100), DEMO.to_vec()),
+                    _ => ("404 Not Found", None, b"not found".to_vec()),
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    length.unwrap_or(body.len())
+                );
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(&body);
+            }
+        });
+        base
+    }
+
+    fn client() -> reqwest::Client {
+        reqwest::Client::builder().no_proxy().build().unwrap()
+    }
+
+    fn folder(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aimcamp-demos-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn decodes_a_match_share_code() {
+        let m = decode(CODE).unwrap();
+        assert_eq!(m.match_id, 3230642215713767580);
+        assert_eq!(m.outcome_id, 3230647599455273103);
+        assert_eq!(m.token, 55788);
+        assert_eq!(encode(&m), CODE);
+        assert_eq!(m.demo_name(), "3230642215713767580_3230647599455273103.dem");
+        assert_eq!(
+            endpoint_url("https://replay.example/{match_id}_{outcome_id}.dem.bz2?t={token}", &m),
+            "https://replay.example/3230642215713767580_3230647599455273103.dem.bz2?t=55788"
+        );
+        assert!(decode("CSGO-GADqf-jjyJ8-cSP2r-smZRo-TO2x").is_err());
+    }
+
+    #[tokio::test]
+    async fn resolves_and_downloads_a_demo() {
+        let base = serve();
+        let m = decode(CODE).unwrap();
+        let url = resolve_url(&client(), &format!("{}/replay/{{share_code}}", base), &m).await.unwrap();
+        assert_eq!(url, format!("{}/demos/ok.dem.bz2", base));
+
+        let dir = folder("ok");
+        let path = download(&client(), &url, &dir, &m.demo_name()).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), DEMO);
+        let plain = download(&client(), &format!("{}/demos/ok.dem", base), &dir, "plain.dem").await.unwrap();
+        assert_eq!(std::fs::read(&plain).unwrap(), DEMO);
+        assert_eq!(entry(&path).unwrap()["name"], m.demo_name());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_and_truncated_downloads_leave_no_file() {
+        let base = serve();
+        let dir = folder("fail");
+        let m = decode(CODE).unwrap();
+
+        let err = download(&client(), &format!("{}/demos/gone.dem", base), &dir, "gone.dem").await.unwrap_err();
+        assert!(err.contains("404"), "{}", err);
+        let err = resolve_url(&client(), &format!("{}/gone/{{match_id}}", base), &m).await.unwrap_err();
+        assert!(err.contains("404"), "{}", err);
+
+        let err = download(&client(), &format!("{}/demos/cut.dem", base), &dir, "cut.dem").await.unwrap_err();
+        assert!(err.starts_with("Read error"), "{}", err);
+        assert!(!dir.join("cut.dem").exists() && !dir.join("cut.dem.part").exists());
+    }
+}
@@ -0,0 +1,18 @@
+# Largest memory BAR of a Win32_VideoController in MB (0 when unknown)
+function Get-DeviceBarMb($Gpu) {
+    $dev = Get-CimInstance Win32_PnPEntity -Filter "PNPDeviceID='$($Gpu.PNPDeviceID -replace '\\', '\\')'"
+    $mb = 0
+    foreach ($r in @($dev | Get-CimAssociatedInstance -ResultClassName Win32_DeviceMemoryAddress)) {
+        $size = ([uint64]$r.EndingAddress - [uint64]$r.StartingAddress + 1) / 1MB
+        if ($size -gt $mb) { $mb = [math]::Round($size) }
+    }
+    return $mb
+}
+
+# Largest memory BAR of the first matching GPU in MB ($null when absent)
+function Get-GpuBarMb($Match) {
+    $gpu = Get-CimInstance Win32_VideoController | Where-Object { $_.Name -match $Match } | Select-Object -First 1
+    if (-not $gpu) { return $null }
+    return Get-DeviceBarMb $gpu
+}
+
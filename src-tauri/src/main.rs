@@ -2,6 +2,30 @@ use tauri::api::dialog::FileDialogBuilder;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::process::Command;
+use benchcmp::percentile;
+
+mod autoexec;
+mod benchcmp;
+mod bisect;
+mod bottleneck;
+mod capture;
+mod cfg;
+mod compat;
+mod crosshair;
+mod cvars;
+mod demos;
+mod migrate;
+mod profiles;
+mod preflight;
+mod ps;
+mod report;
+mod script_config;
+mod sharecode;
+mod snapshot;
+mod steam;
+mod tweaks;
+mod vdf;
+mod video;
 // base64 Engine trait used via associated function calls
 
 // ────────────────────────────────────────────────────────────────────
@@ -9,23 +33,27 @@ use std::process::Command;
 // PowerShell commands in the generated .ps1 file.
 // ────────────────────────────────────────────────────────────────────
 
-#[derive(serde::Deserialize)]
+/// Deserialized through `migrate::upgrade`, so payloads from older agents
+/// (profiles, shared schemas) are brought up to the current schema first.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(try_from = "serde_json::Value")]
 struct OptimizationConfig {
+    schema_version: u32,
     bios: BiosConfig,
     windows: WindowsConfig,
     network: NetworkConfig,
     nvidia: NvidiaConfig,
+    amd: AmdConfig,
+    intel: IntelConfig,
     services: ServicesConfig,
     autoexec: AutoexecConfig,
     launch_options: LaunchOptionsConfig,
     extras: ExtrasConfig,
-    #[serde(default)]
     theme_primary: String,
-    #[serde(default)]
     theme_secondary: String,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct BiosConfig {
     disable_svm: bool,
     disable_c_states: bool,
@@ -35,7 +63,7 @@ struct BiosConfig {
     enable_above_4g: bool,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct WindowsConfig {
     ultimate_power_plan: bool,
     disable_game_dvr: bool,
@@ -56,47 +84,32 @@ struct WindowsConfig {
     disable_core_parking: bool,
     clean_temp_files: bool,
     cs2_high_performance_gpu: bool,
-    #[serde(default)]
     disable_delivery_optim: bool,
-    #[serde(default)]
     disable_widgets: bool,
-    #[serde(default)]
     disable_memory_compression: bool,
-    #[serde(default)]
     disable_connected_ux: bool,
-    #[serde(default)]
     disable_spectre: bool,
-    #[serde(default)]
     disable_last_access: bool,
-    #[serde(default)]
     disable_8dot3: bool,
-    #[serde(default)]
     mmcss_gaming: bool,
-    #[serde(default)]
     disable_large_cache: bool,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct NetworkConfig {
     disable_nagle: bool,
     optimize_tcp: bool,
     flush_dns: bool,
     disable_wifi_power_save: bool,
-    #[serde(default)]
     disable_network_throttle: bool,
-    #[serde(default)]
     disable_ecn: bool,
-    #[serde(default)]
     enable_rss: bool,
-    #[serde(default)]
     disable_netbios: bool,
-    #[serde(default)]
     disable_lmhosts: bool,
-    #[serde(default)]
     enable_ctcp: bool,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct NvidiaConfig {
     prefer_max_perf: bool,
     disable_vsync: bool,
@@ -104,21 +117,34 @@ struct NvidiaConfig {
     threaded_optimization: bool,
     disable_anisotropic: bool,
     shader_cache_clear: bool,
-    #[serde(default)]
     force_reflex: bool,
-    #[serde(default)]
     disable_sharpening: bool,
-    #[serde(default)]
     texture_filter_perf: bool,
-    #[serde(default)]
     pre_rendered_frames_1: bool,
-    #[serde(default)]
     disable_ambient_occlusion: bool,
-    #[serde(default)]
     disable_fxaa: bool,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct AmdConfig {
+    anti_lag: bool,
+    disable_enhanced_sync: bool,
+    disable_chill: bool,
+    surface_format_optimization: bool,
+    shader_cache_reset: bool,
+    rebar_check: bool,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct IntelConfig {
+    low_latency: bool,
+    disable_speed_sync: bool,
+    disable_endurance_gaming: bool,
+    intel_shader_cache_reset: bool,
+    intel_rebar_check: bool,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
 struct ServicesConfig {
     disable_sysmain: bool,
     disable_diagtrack: bool,
@@ -126,23 +152,16 @@ struct ServicesConfig {
     disable_print_spooler: bool,
     disable_fax: bool,
     disable_xbox_services: bool,
-    #[serde(default)]
     disable_cdp: bool,
-    #[serde(default)]
     disable_wpn: bool,
-    #[serde(default)]
     disable_diagnostic_policy: bool,
-    #[serde(default)]
     disable_remote_registry: bool,
-    #[serde(default)]
     disable_maps_broker: bool,
-    #[serde(default)]
     disable_phone_service: bool,
-    #[serde(default)]
     disable_retail_demo: bool,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct AutoexecConfig {
     enabled: bool,
     fps_max: String,
@@ -152,22 +171,26 @@ struct AutoexecConfig {
     cl_updaterate: String,
     cl_cmdrate: String,
     m_rawinput: bool,
+    /// Crosshair share code; its cl_crosshair* cvars are written after m_rawinput
+    crosshair_code: String,
+    /// cl_crosshair* cvars an imported cfg set; only these are written.
+    /// Empty writes every cvar of the code.
+    crosshair_cvars: Vec<String>,
     custom_commands: String,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct LaunchOptionsConfig {
     novid: bool,
     nojoy: bool,
     high_priority: bool,
-    #[serde(default)]
     allow_third_party: bool,
     threads: String,
     exec_autoexec: bool,
     custom_args: String,
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, serde::Serialize)]
 struct ExtrasConfig {
     faceit_admin: bool,
     disable_steam_overlay: bool,
@@ -176,20 +199,80 @@ struct ExtrasConfig {
     gpu_priority: bool,
     priority_separation: bool,
     cs2_process_priority: bool,
-    #[serde(default)]
     disable_telemetry_tasks: bool,
-    #[serde(default)]
     timer_resolution: bool,
-    #[serde(default)]
     msi_mode_gpu: bool,
-    #[serde(default)]
     pcie_link_state_off: bool,
-    #[serde(default)]
     interrupt_moderation_off: bool,
-    #[serde(default)]
     enable_large_pages: bool,
 }
 
+impl TryFrom<serde_json::Value> for OptimizationConfig {
+    type Error = String;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, String> {
+        let mut v = migrate::upgrade(value)?;
+        fn field<T: serde::de::DeserializeOwned>(v: &mut serde_json::Value, name: &str) -> Result<T, String> {
+            let raw = v.get_mut(name).map(serde_json::Value::take).unwrap_or_default();
+            serde_json::from_value(raw).map_err(|e| format!("Config field {}: {}", name, e))
+        }
+        Ok(Self {
+            schema_version: migrate::current_version(),
+            bios: field(&mut v, "bios")?,
+            windows: field(&mut v, "windows")?,
+            network: field(&mut v, "network")?,
+            nvidia: field(&mut v, "nvidia")?,
+            amd: field(&mut v, "amd")?,
+            intel: field(&mut v, "intel")?,
+            services: field(&mut v, "services")?,
+            autoexec: field(&mut v, "autoexec")?,
+            launch_options: field(&mut v, "launch_options")?,
+            extras: field(&mut v, "extras")?,
+            theme_primary: field(&mut v, "theme_primary")?,
+            theme_secondary: field(&mut v, "theme_secondary")?,
+        })
+    }
+}
+
+impl OptimizationConfig {
+    /// Reject free-form fields that could not be written to a script as a
+    /// plain value (configs can come from a shared schema ZIP). Only the
+    /// fields `section` (all when None) actually writes are checked, and
+    /// empty values count as not set.
+    fn validate(&self, section: Option<&str>) -> Result<(), ps::ValidationError> {
+        let writes = |id: &str| section.map_or(true, |s| s == id);
+        let ae = &self.autoexec;
+        if ae.enabled && writes("autoexec") {
+            for (field, value) in [
+                ("autoexec.fps_max", &ae.fps_max),
+                ("autoexec.rate", &ae.rate),
+                ("autoexec.cl_interp", &ae.cl_interp),
+                ("autoexec.cl_interp_ratio", &ae.cl_interp_ratio),
+                ("autoexec.cl_updaterate", &ae.cl_updaterate),
+                ("autoexec.cl_cmdrate", &ae.cl_cmdrate),
+            ] {
+                if !value.trim().is_empty() {
+                    ps::number(field, value)?;
+                }
+            }
+            if !ae.crosshair_code.trim().is_empty() {
+                ps::crosshair_code("autoexec.crosshair_code", &ae.crosshair_code)?;
+            }
+            for line in ae.custom_commands.lines() {
+                ps::text_line("autoexec.custom_commands", line)?;
+            }
+        }
+        if writes("launch") {
+            let lo = &self.launch_options;
+            if !lo.threads.trim().is_empty() {
+                ps::number("launch_options.threads", &lo.threads)?;
+            }
+            ps::text_line("launch_options.custom_args", &lo.custom_args)?;
+        }
+        Ok(())
+    }
+}
+
 // ────────────────────────────────────────────────────────────────────
 // Map hex color to nearest PS console color
 // ────────────────────────────────────────────────────────────────────
@@ -325,6 +408,7 @@ Write-Host ''
 
 fn ps1_section(s: &mut String, num: u8, title: &str, _icon: &str) {
     s.push_str(&format!("\n# ---- SECTION {}: {} ----\n", num, title));
+    s.push_str(&format!("$_aimSection = {}\n", ps::quote(title)));
     s.push_str("Write-Host ''\n");
     s.push_str(&format!("$sLine = '  >>==[ SECTION {} ]=====[ {} ]==>>'\n", num, title));
     s.push_str("foreach ($ch in $sLine.ToCharArray()) { Write-Host $ch -ForegroundColor $tc -NoNewline; Start-Sleep -Milliseconds 8 }\n");
@@ -332,27 +416,47 @@ fn ps1_section(s: &mut String, num: u8, title: &str, _icon: &str) {
     s.push_str("Write-Host '  ----------------------------------------------------------------' -ForegroundColor $td\n");
 }
 
-fn ps1_cmd(s: &mut String, description: &str, commands: &[&str]) {
+fn ps1_cmd(s: &mut String, id: &str, description: &str, commands: &[&str]) {
     s.push_str(&format!("\n    # {}\n", description));
     s.push_str("    $spin = @('|','/','-','\\')\n");
     s.push_str(&format!("    for ($j=0;$j -lt 8;$j++) {{ Write-Host \"`r    [ $($spin[$j % 4]) ] {}\" -ForegroundColor $ts -NoNewline; Start-Sleep -Milliseconds 60 }}\n", description));
-    s.push_str(&format!("    Write-Host \"`r    [ OK ] {}\" -ForegroundColor $tc\n", description));
+    s.push_str("    $_aimSw = [Diagnostics.Stopwatch]::StartNew(); $_aimE0 = $Error.Count; $_aimFail = $null; $global:LASTEXITCODE = 0\n");
+    s.push_str("    try {\n");
     for c in commands {
-        s.push_str("    ");
+        s.push_str("        ");
         s.push_str(c);
         s.push('\n');
     }
+    s.push_str("    } catch { $_aimFail = $_.Exception.Message }\n");
+    s.push_str("    $_aimWarn = if ($Error.Count -gt $_aimE0) { $Error[0].ToString() } else { $null }\n");
+    // A native tool (reg, bcdedit, powercfg, sc) that exits non-zero failed,
+    // even though nothing was thrown
+    s.push_str("    $_aimCode = $global:LASTEXITCODE\n");
+    s.push_str("    if (-not $_aimFail -and $_aimCode) { $_aimFail = if ($_aimWarn) { $_aimWarn } else { \"Exit code $_aimCode\" } }\n");
+    let (id, desc) = (ps::quote(id), ps::quote(description.trim_end_matches("...")));
+    s.push_str("    if ($_aimFail) {\n");
+    s.push_str(&format!("        Write-Host \"`r    [FAIL] {}\" -ForegroundColor Red\n", description));
+    s.push_str(&format!("        Write-AimStep {} {} 'failed' $_aimCode $_aimFail $_aimSw.ElapsedMilliseconds\n", id, desc));
+    s.push_str("    } else {\n");
+    s.push_str(&format!("        Write-Host \"`r    [ OK ] {}\" -ForegroundColor $tc\n", description));
+    s.push_str("        $_aimStatus = if ($_aimWarn) { 'warning' } else { 'ok' }\n");
+    s.push_str(&format!("        Write-AimStep {} {} $_aimStatus $_aimCode $_aimWarn $_aimSw.ElapsedMilliseconds\n", id, desc));
+    s.push_str("    }\n");
 }
 
 fn ps1_info(s: &mut String, message: &str) {
     s.push_str(&format!("    # {}\n", message));
     s.push_str(&format!(
-        "    Write-Host '    [ i ] {}' -ForegroundColor $ts\n",
-        message
+        "    Write-Host {} -ForegroundColor $ts\n",
+        ps::quote(&format!("    [ i ] {}", message))
     ));
 }
 
 fn ps1_footer(s: &mut String) {
+    ps1_footer_with(s, "All optimizations applied", "Set CS2 launch options in Steam.");
+}
+
+fn ps1_footer_with(s: &mut String, headline: &str, hint: &str) {
     s.push_str(r#"
 
 # ---- Done ----
@@ -374,11 +478,12 @@ Write-Host ''
 $doneBox = @(
 '  +================================================================+',
 '  |                                                                |',
-'  |   [*]  aim.camp Player Agent -- All optimizations applied     |',
-'  |                                                                |',
-'  |   >>  Restart your PC to apply changes.                        |',
-'  |   >>  Set CS2 launch options in Steam.                         |',
-'  |                                                                |',
+"#);
+    s.push_str(&format!("'  |   [*]  aim.camp Player Agent -- {:<30}|',\n", headline));
+    s.push_str("'  |                                                                |',\n");
+    s.push_str("'  |   >>  Restart your PC to apply changes.                        |',\n");
+    s.push_str(&format!("'  |   >>  {:<57}|',\n", hint));
+    s.push_str(r#"'  |                                                                |',
 '  +================================================================+'
 )
 $doneColors = @($tc,$td,$tc,$td,$ts,$ts,$td,$tc)
@@ -404,22 +509,17 @@ Read-Host -Prompt '  Press Enter to exit'
 }
 
 // ────────────────────────────────────────────────────────────────────
-// Script generator
+// Shared script header: theme, banner, admin guard, helpers
 // ────────────────────────────────────────────────────────────────────
-#[tauri::command]
-async fn generate_script(config: OptimizationConfig, section: Option<String>) -> Result<String, String> {
-    let mut s = String::with_capacity(32_000);
-    let run_all = section.is_none();
-    let sf = section.as_deref();
-
-    let pc = if config.theme_primary.is_empty() { "#84cc16".to_string() } else { config.theme_primary.clone() };
-    let sc = if config.theme_secondary.is_empty() { "#22d3ee".to_string() } else { config.theme_secondary.clone() };
-    let ps_primary = hex_to_ps_color(&pc);
-    let ps_dark = hex_to_ps_dark(&pc);
-    let ps_secondary = hex_to_ps_color(&sc);
+fn ps1_header(s: &mut String, theme_primary: &str, theme_secondary: &str) {
+    let pc = if theme_primary.is_empty() { "#84cc16" } else { theme_primary };
+    let sc = if theme_secondary.is_empty() { "#22d3ee" } else { theme_secondary };
+    let ps_primary = hex_to_ps_color(pc);
+    let ps_dark = hex_to_ps_dark(pc);
+    let ps_secondary = hex_to_ps_color(sc);
 
     // Banner + boot animation
-    ps1_banner(&mut s, ps_primary, ps_dark, ps_secondary);
+    ps1_banner(s, ps_primary, ps_dark, ps_secondary);
 
     s.push_str("#Requires -RunAsAdministrator\n\n");
     s.push_str("$ErrorActionPreference = 'SilentlyContinue'\n\n");
@@ -427,706 +527,227 @@ async fn generate_script(config: OptimizationConfig, section: Option<String>) ->
     s.push_str("function Ensure-RegPath($Path) {\n");
     s.push_str("    if (!(Test-Path $Path)) { New-Item -Path $Path -Force | Out-Null }\n");
     s.push_str("}\n\n");
+    s.push_str(tweaks::GPU_BAR_PS1);
+
+    // OS build / edition for tweaks limited to certain Windows versions
+    s.push_str("$_aimOs = Get-ItemProperty 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion'\n");
+    s.push_str("$_aimBuild = [int]$_aimOs.CurrentBuildNumber\n");
+    s.push_str("$_aimEdition = [string]$_aimOs.EditionID\n\n");
+
+    // Per-step JSON-lines log; only active when the runner sets $_aimStepLog
+    s.push_str("function Write-AimStep($Id, $Desc, $Status, $Code, $Err, $Ms) {\n");
+    s.push_str("    if (-not $_aimStepLog) { return }\n");
+    s.push_str("    $rec = [ordered]@{ step = $Id; section = $_aimSection; description = $Desc; status = $Status; exit_code = $Code; error = $Err; duration_ms = $Ms }\n");
+    s.push_str("    Add-Content -Path $_aimStepLog -Value ($rec | ConvertTo-Json -Compress) -Encoding UTF8\n");
+    s.push_str("}\n\n");
+}
 
-    // 1. BIOS
-    if run_all || sf == Some("bios") {
-    ps1_section(&mut s, 1, "BIOS -- Hardware Checks", "HW");
-
-    s.push_str("    # Check virtualization state\n");
-    s.push_str("    $vmfw = (Get-CimInstance -ClassName Win32_Processor | Select-Object -First 1).VirtualizationFirmwareEnabled\n");
-    s.push_str("    if ($vmfw) { Write-Host '    ⚠ Virtualization (SVM/VT-x) is ACTIVE in BIOS' -ForegroundColor Yellow }\n");
-    s.push_str("    else       { Write-Host '    ✔ Virtualization (SVM/VT-x) is disabled — OK' -ForegroundColor Green  }\n\n");
-
-    if config.bios.disable_svm {
-        ps1_info(&mut s, "Disable SVM Mode / VT-x in BIOS");
-        ps1_cmd(&mut s, "Disable Hyper-V in Windows", &[
-            "Disable-WindowsOptionalFeature -Online -FeatureName Microsoft-Hyper-V-All -NoRestart -ErrorAction SilentlyContinue | Out-Null",
-            "bcdedit /set hypervisorlaunchtype off 2>$null",
-        ]);
-    }
-    if config.bios.disable_c_states {
-        ps1_info(&mut s, "Disable C-States in BIOS");
-        ps1_cmd(&mut s, "Force CPU processor state to 100%", &[
-            "powercfg /setacvalueindex SCHEME_CURRENT SUB_PROCESSOR PROCTHROTTLEMIN 100",
-            "powercfg /setacvalueindex SCHEME_CURRENT SUB_PROCESSOR PROCTHROTTLEMAX 100",
-            "powercfg /setactive SCHEME_CURRENT",
-        ]);
-    }
-    if config.bios.disable_cool_n_quiet {
-        ps1_info(&mut s, "Disable Cool'n'Quiet / Speed Shift in BIOS");
-    }
-    if config.bios.enable_xmp {
-        ps1_info(&mut s, "Enable XMP/DOCP in BIOS for rated RAM speed");
-        s.push_str("    $ramSpeed = (Get-CimInstance -ClassName Win32_PhysicalMemory | Select-Object -First 1).Speed\n");
-        s.push_str("    Write-Host \"    ⚡ Current RAM speed: $ramSpeed MHz\" -ForegroundColor White\n");
-    }
-    if config.bios.enable_resize_bar {
-        ps1_info(&mut s, "Enable ReSize BAR in BIOS (if GPU supports it)");
-    }
-    if config.bios.enable_above_4g {
-        ps1_info(&mut s, "Enable Above 4G Decoding in BIOS");
-    }
-    } // end bios
-
-    // 2. Windows
-    if run_all || sf == Some("windows") {
-    ps1_section(&mut s, 2, "Windows -- Performance Tweaks", "OS");
-
-    if config.windows.ultimate_power_plan {
-        ps1_cmd(&mut s, "Activate Ultimate/High Performance power plan...", &[
-            "powercfg -duplicatescheme e9a42b02-d5df-448d-aa00-03f14749eb61 2>$null",
-            "$plans = powercfg /list",
-            "if ($plans -match 'e9a42b02') { powercfg -setactive e9a42b02-d5df-448d-aa00-03f14749eb61 }",
-            "else { powercfg -setactive 8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c }",
-            "Write-Host '    ✔ Power plan set.' -ForegroundColor Green",
-        ]);
-    }
-    if config.windows.disable_game_dvr {
-        ps1_cmd(&mut s, "Disable Game DVR (recording & captures)...", &[
-            "Ensure-RegPath 'HKCU:\\System\\GameConfigStore'",
-            "Set-ItemProperty -Path 'HKCU:\\System\\GameConfigStore' -Name 'GameDVR_Enabled' -Value 0 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKCU:\\System\\GameConfigStore' -Name 'GameDVR_FSEBehavior' -Value 2 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKCU:\\System\\GameConfigStore' -Name 'GameDVR_FSEBehaviorMode' -Value 2 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKCU:\\System\\GameConfigStore' -Name 'GameDVR_HonorUserFSEBehaviorMode' -Value 1 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKCU:\\System\\GameConfigStore' -Name 'GameDVR_DXGIHonorFSEWindowsCompatible' -Value 1 -Type DWord -Force",
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\GameDVR'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\GameDVR' -Name 'AllowGameDVR' -Value 0 -Type DWord -Force",
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\GameDVR'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\GameDVR' -Name 'AppCaptureEnabled' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_game_bar {
-        ps1_cmd(&mut s, "Disable Game Bar overlay...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\GameBar'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\GameBar' -Name 'AllowAutoGameMode' -Value 0 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\GameBar' -Name 'ShowStartupPanel' -Value 0 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\GameBar' -Name 'UseNexusForGameBarEnabled' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_game_mode {
-        ps1_cmd(&mut s, "Disable Windows Game Mode...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\GameBar'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\GameBar' -Name 'AutoGameModeEnabled' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_hibernation {
-        ps1_cmd(&mut s, "Disable Hibernation & Fast Startup...", &[
-            "powercfg -h off",
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Power'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Power' -Name 'HiberbootEnabled' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_mouse_accel {
-        ps1_cmd(&mut s, "Disable mouse acceleration (Enhance Pointer Precision)...", &[
-            "Set-ItemProperty -Path 'HKCU:\\Control Panel\\Mouse' -Name 'MouseSpeed' -Value '0' -Force",
-            "Set-ItemProperty -Path 'HKCU:\\Control Panel\\Mouse' -Name 'MouseThreshold1' -Value '0' -Force",
-            "Set-ItemProperty -Path 'HKCU:\\Control Panel\\Mouse' -Name 'MouseThreshold2' -Value '0' -Force",
-            "Set-ItemProperty -Path 'HKCU:\\Control Panel\\Mouse' -Name 'MouseSensitivity' -Value '10' -Force",
-        ]);
-    }
-    if config.windows.disable_fullscreen_optim {
-        ps1_cmd(&mut s, "Disable Fullscreen Optimizations for CS2...", &[
-            "$cs2Paths = @(",
-            "    'C:\\Program Files (x86)\\Steam\\steamapps\\common\\Counter-Strike Global Offensive\\game\\bin\\win64\\cs2.exe',",
-            "    'D:\\SteamLibrary\\steamapps\\common\\Counter-Strike Global Offensive\\game\\bin\\win64\\cs2.exe'",
-            ")",
-            "foreach ($p in $cs2Paths) {",
-            "    if (Test-Path $p) {",
-            "        $regPath = 'HKCU:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\AppCompatFlags\\Layers'",
-            "        Ensure-RegPath $regPath",
-            "        Set-ItemProperty -Path $regPath -Name $p -Value '~ DISABLEDXMAXIMIZEDWINDOWEDMODE' -Type String -Force",
-            "        Write-Host \"    ✔ Fullscreen Optimizations disabled for: $p\" -ForegroundColor Green",
-            "    }",
-            "}",
-        ]);
-    }
-    if config.windows.disable_visual_effects {
-        ps1_cmd(&mut s, "Disable Windows visual effects (best performance)...", &[
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\VisualEffects' -Name 'VisualFXSetting' -Value 2 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKCU:\\Control Panel\\Desktop' -Name 'UserPreferencesMask' -Value ([byte[]](0x90,0x12,0x03,0x80,0x10,0x00,0x00,0x00)) -Type Binary -Force",
-            "Set-ItemProperty -Path 'HKCU:\\Control Panel\\Desktop' -Name 'DragFullWindows' -Value '0' -Force",
-            "Set-ItemProperty -Path 'HKCU:\\Control Panel\\Desktop\\WindowMetrics' -Name 'MinAnimate' -Value '0' -Force",
-        ]);
-    }
-    if config.windows.disable_transparency {
-        ps1_cmd(&mut s, "Disable transparency effects...", &[
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize' -Name 'EnableTransparency' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_background_apps {
-        ps1_cmd(&mut s, "Disable background apps...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\BackgroundAccessApplications'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\BackgroundAccessApplications' -Name 'GlobalUserDisabled' -Value 1 -Type DWord -Force",
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Search'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Search' -Name 'BackgroundAppGlobalToggle' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_notifications {
-        ps1_cmd(&mut s, "Disable notifications & tips...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\PushNotifications'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\PushNotifications' -Name 'ToastEnabled' -Value 0 -Type DWord -Force",
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Policies\\Microsoft\\Windows\\Explorer'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Policies\\Microsoft\\Windows\\Explorer' -Name 'DisableNotificationCenter' -Value 1 -Type DWord -Force",
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\ContentDeliveryManager'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\ContentDeliveryManager' -Name 'SubscribedContent-338389Enabled' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_cortana {
-        ps1_cmd(&mut s, "Disable Cortana...", &[
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\Windows Search'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\Windows Search' -Name 'AllowCortana' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_search_indexing {
-        ps1_cmd(&mut s, "Disable Windows Search Indexing service...", &[
-            "Stop-Service -Name 'WSearch' -Force -ErrorAction SilentlyContinue",
-            "Set-Service -Name 'WSearch' -StartupType Disabled -ErrorAction SilentlyContinue",
-        ]);
-    }
-    if config.windows.hardware_gpu_scheduling {
-        ps1_cmd(&mut s, "Enable Hardware Accelerated GPU Scheduling...", &[
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\GraphicsDrivers'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\GraphicsDrivers' -Name 'HwSchMode' -Value 2 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_hpet {
-        ps1_cmd(&mut s, "Disable HPET (High Precision Event Timer)...", &[
-            "bcdedit /deletevalue useplatformclock 2>$null",
-            "bcdedit /set useplatformtick yes 2>$null",
-            "bcdedit /set disabledynamictick yes 2>$null",
-        ]);
-    }
-    if config.windows.disable_power_throttling {
-        ps1_cmd(&mut s, "Disable Power Throttling...", &[
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Power\\PowerThrottling'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Power\\PowerThrottling' -Name 'PowerThrottlingOff' -Value 1 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_core_parking {
-        ps1_cmd(&mut s, "Disable Core Parking (keep all cores active)...", &[
-            "powercfg -setacvalueindex SCHEME_CURRENT SUB_PROCESSOR CPMINCORES 100",
-            "powercfg -setactive SCHEME_CURRENT",
-        ]);
-    }
-    if config.windows.clean_temp_files {
-        ps1_cmd(&mut s, "Clean temporary files...", &[
-            "Remove-Item -Path \"$env:TEMP\\*\" -Recurse -Force -ErrorAction SilentlyContinue",
-            "Remove-Item -Path 'C:\\Windows\\Temp\\*' -Recurse -Force -ErrorAction SilentlyContinue",
-            "Remove-Item -Path 'C:\\Windows\\Prefetch\\*' -Recurse -Force -ErrorAction SilentlyContinue",
-            "Write-Host '    ✔ Temp files cleaned.' -ForegroundColor Green",
-        ]);
-    }
-    if config.windows.cs2_high_performance_gpu {
-        ps1_cmd(&mut s, "Set CS2 to High Performance GPU in Windows settings...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\DirectX\\UserGpuPreferences'",
-            "$cs2Exes = @(",
-            "    'C:\\Program Files (x86)\\Steam\\steamapps\\common\\Counter-Strike Global Offensive\\game\\bin\\win64\\cs2.exe',",
-            "    'D:\\SteamLibrary\\steamapps\\common\\Counter-Strike Global Offensive\\game\\bin\\win64\\cs2.exe'",
-            ")",
-            "foreach ($exe in $cs2Exes) {",
-            "    if (Test-Path $exe) {",
-            "        Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\DirectX\\UserGpuPreferences' -Name $exe -Value 'GpuPreference=2;' -Force",
-            "        Write-Host \"    ✔ CS2 set to High Performance GPU: $exe\" -ForegroundColor Green",
-            "    }",
-            "}",
-        ]);
-    }
-
-    if config.windows.disable_delivery_optim {
-        ps1_cmd(&mut s, "Disable Delivery Optimization (P2P uploads)...", &[
-            "Stop-Service -Name 'DoSvc' -Force -ErrorAction SilentlyContinue",
-            "Set-Service -Name 'DoSvc' -StartupType Disabled -ErrorAction SilentlyContinue",
-        ]);
-    }
-    if config.windows.disable_widgets {
-        ps1_cmd(&mut s, "Disable Windows 11 Widgets...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Advanced'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Advanced' -Name 'TaskbarDa' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_memory_compression {
-        ps1_cmd(&mut s, "Disable Memory Compression...", &[
-            "Disable-MMAgent -MemoryCompression -ErrorAction SilentlyContinue",
-        ]);
-    }
-    if config.windows.disable_connected_ux {
-        ps1_cmd(&mut s, "Disable Connected User Experiences and Telemetry...", &[
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\DataCollection'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\DataCollection' -Name 'AllowTelemetry' -Value 0 -Type DWord -Force",
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\DataCollection'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\DataCollection' -Name 'ConnectedUserExperiences' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_spectre {
-        ps1_cmd(&mut s, "Disable Spectre/Meltdown CPU mitigations (security risk)...", &[
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management' -Name 'FeatureSettingsOverride' -Value 3 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management' -Name 'FeatureSettingsOverrideMask' -Value 3 -Type DWord -Force",
-            "Write-Host '    ⚠ CPU mitigations disabled. This is a security tradeoff for 5-30% perf gain.' -ForegroundColor Yellow",
-        ]);
-    }
-    if config.windows.disable_last_access {
-        ps1_cmd(&mut s, "Disable NTFS Last Access Timestamp...", &[
-            "fsutil behavior set disablelastaccess 1 2>$null",
-        ]);
-    }
-    if config.windows.disable_8dot3 {
-        ps1_cmd(&mut s, "Disable 8.3 Short Filename Creation...", &[
-            "fsutil behavior set disable8dot3 1 2>$null",
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\FileSystem'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\FileSystem' -Name 'NtfsDisable8dot3NameCreation' -Value 1 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.mmcss_gaming {
-        ps1_cmd(&mut s, "Set MMCSS Multimedia Scheduler for gaming priority...", &[
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile' -Name 'SystemResponsiveness' -Value 0 -Type DWord -Force",
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'Scheduling Category' -Value 'High' -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'SFIO Priority' -Value 'High' -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'Background Only' -Value 'False' -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'Clock Rate' -Value 10000 -Type DWord -Force",
-        ]);
-    }
-    if config.windows.disable_large_cache {
-        ps1_cmd(&mut s, "Disable Large System Cache...", &[
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management' -Name 'LargeSystemCache' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    } // end windows
-
-    // ── 3. Network ──────────────────────────────────────────────────
-    if run_all || sf == Some("network") {
-    ps1_section(&mut s, 3, "Network -- Latency & TCP", "NET");
-
-    if config.network.disable_nagle {
-        ps1_cmd(&mut s, "Disable Nagle algorithm (reduce network latency)...", &[
-            "$adapters = Get-ChildItem 'HKLM:\\SYSTEM\\CurrentControlSet\\Services\\Tcpip\\Parameters\\Interfaces'",
-            "foreach ($adapter in $adapters) {",
-            "    Set-ItemProperty -Path $adapter.PSPath -Name 'TcpAckFrequency' -Value 1 -Type DWord -Force",
-            "    Set-ItemProperty -Path $adapter.PSPath -Name 'TCPNoDelay' -Value 1 -Type DWord -Force",
-            "    Set-ItemProperty -Path $adapter.PSPath -Name 'TcpDelAckTicks' -Value 0 -Type DWord -Force",
-            "}",
-            "Write-Host '    ✔ Nagle disabled on all adapters.' -ForegroundColor Green",
-        ]);
-    }
-    if config.network.optimize_tcp {
-        ps1_cmd(&mut s, "Optimize global TCP settings...", &[
-            "netsh int tcp set global autotuninglevel=normal",
-            "netsh int tcp set global chimney=disabled 2>$null",
-            "netsh int tcp set global ecncapability=disabled",
-            "netsh int tcp set global timestamps=disabled",
-            "netsh int tcp set global rss=enabled",
-            "netsh int tcp set global dca=enabled 2>$null",
-            "netsh int tcp set supplemental Internet congestionprovider=ctcp 2>$null",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Services\\Tcpip\\Parameters' -Name 'DefaultTTL' -Value 64 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Services\\Tcpip\\Parameters' -Name 'MaxUserPort' -Value 65534 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Services\\Tcpip\\Parameters' -Name 'TcpTimedWaitDelay' -Value 30 -Type DWord -Force",
-        ]);
-    }
-    if config.network.flush_dns {
-        ps1_cmd(&mut s, "Flush DNS cache...", &[
-            "ipconfig /flushdns | Out-Null",
-            "Write-Host '    ✔ DNS cache flushed.' -ForegroundColor Green",
-        ]);
-    }
-    if config.network.disable_wifi_power_save {
-        ps1_cmd(&mut s, "Disable Wi-Fi power saving...", &[
-            "$wifiAdapters = Get-NetAdapter | Where-Object { $_.InterfaceDescription -match 'Wi-Fi|Wireless|WLAN' }",
-            "foreach ($a in $wifiAdapters) {",
-            "    Set-NetAdapterPowerManagement -Name $a.Name -WakeOnMagicPacket Disabled -ErrorAction SilentlyContinue",
-            "    powercfg /setacvalueindex SCHEME_CURRENT 19cbb8fa-5279-450e-9fac-8a3d5fedd0c1 12bbebe6-58d6-4636-95bb-3217ef867c1a 0 2>$null",
-            "}",
-            "powercfg /setactive SCHEME_CURRENT",
-        ]);
-    }
-    if config.network.disable_network_throttle {
-        ps1_cmd(&mut s, "Disable Network Throttling Index...", &[
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile' -Name 'NetworkThrottlingIndex' -Value 0xFFFFFFFF -Type DWord -Force",
-        ]);
-    }
-    if config.network.disable_ecn {
-        ps1_cmd(&mut s, "Disable ECN Capability...", &[
-            "netsh int tcp set global ecncapability=disabled 2>$null",
-        ]);
-    }
-    if config.network.enable_rss {
-        ps1_cmd(&mut s, "Enable Receive Side Scaling (RSS)...", &[
-            "netsh int tcp set global rss=enabled 2>$null",
-        ]);
-    }
-    if config.network.disable_netbios {
-        ps1_cmd(&mut s, "Disable NetBIOS over TCP/IP on all adapters...", &[
-            "$adapters = Get-ChildItem 'HKLM:\\SYSTEM\\CurrentControlSet\\Services\\NetBT\\Parameters\\Interfaces'",
-            "foreach ($adapter in $adapters) {",
-            "    Set-ItemProperty -Path $adapter.PSPath -Name 'NetbiosOptions' -Value 2 -Type DWord -Force",
-            "}",
-            "Write-Host '    ✔ NetBIOS disabled on all adapters.' -ForegroundColor Green",
-        ]);
-    }
-    if config.network.disable_lmhosts {
-        ps1_cmd(&mut s, "Disable LMHOSTS Lookup...", &[
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Services\\NetBT\\Parameters' -Name 'EnableLMHOSTS' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.network.enable_ctcp {
-        ps1_cmd(&mut s, "Enable Compound TCP (CTCP) congestion control...", &[
-            "netsh int tcp set supplemental Internet congestionprovider=ctcp 2>$null",
-        ]);
-    }
-    } // end network
-
-    // ── 4. NVIDIA ───────────────────────────────────────────────────
-    if run_all || sf == Some("nvidia") {
-    ps1_section(&mut s, 4, "NVIDIA -- Registry-Level GPU Tweaks", "GPU");
-
-    s.push_str("    # ── Find NVIDIA GPU registry path ──\n");
-    s.push_str("    $nvBasePath = 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Class\\{4d36e968-e325-11ce-bfc1-08002be10318}'\n");
-    s.push_str("    $nvKeys = @('0000','0001','0002','0003')\n");
-    s.push_str("    $nvPath = ''\n");
-    s.push_str("    foreach ($k in $nvKeys) {\n");
-    s.push_str("        $test = Join-Path $nvBasePath $k\n");
-    s.push_str("        if (Test-Path $test) {\n");
-    s.push_str("            $desc = (Get-ItemProperty -Path $test -Name 'DriverDesc' -ErrorAction SilentlyContinue).DriverDesc\n");
-    s.push_str("            if ($desc -match 'NVIDIA|GeForce|RTX|GTX') { $nvPath = $test; break }\n");
-    s.push_str("        }\n");
-    s.push_str("    }\n\n");
-
-    if config.nvidia.prefer_max_perf {
-        ps1_cmd(&mut s, "NVIDIA: Power Mode = Max Performance...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'PerfLevelSrc' -Value 0x2222 -Type DWord -Force",
-            "    Set-ItemProperty -Path $nvPath -Name 'PowerMizerEnable' -Value 1 -Type DWord -Force",
-            "    Set-ItemProperty -Path $nvPath -Name 'PowerMizerLevel' -Value 1 -Type DWord -Force",
-            "    Set-ItemProperty -Path $nvPath -Name 'PowerMizerLevelAC' -Value 1 -Type DWord -Force",
-            "    Write-Host '    ✔ NVIDIA Power Mode = Max Performance' -ForegroundColor Green",
-            "} else { Write-Host '    ⚠ NVIDIA GPU not found in registry.' -ForegroundColor Yellow }",
-        ]);
-    }
-    if config.nvidia.disable_vsync {
-        ps1_cmd(&mut s, "NVIDIA: Disable global V-Sync...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\NVIDIA Corporation\\Global\\NVTweak'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\NVIDIA Corporation\\Global\\NVTweak' -Name 'Gestalt' -Value 1 -Type DWord -Force",
-        ]);
-    }
-    if config.nvidia.low_latency_ultra {
-        ps1_cmd(&mut s, "NVIDIA: Ultra Low Latency mode...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'RMDelayCycles' -Value 31 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Low Latency configured.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    if config.nvidia.threaded_optimization {
-        ps1_cmd(&mut s, "NVIDIA: Enable Threaded Optimization...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'ThreadedOptimization' -Value 1 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Threaded Optimization enabled.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    if config.nvidia.disable_anisotropic {
-        ps1_cmd(&mut s, "NVIDIA: Set anisotropic filtering to App-controlled...", &[
-            "Write-Host '    💡 Recommended: Set Anisotropic Filtering to Application-controlled in NVIDIA Panel' -ForegroundColor Yellow",
-        ]);
-    }
-    if config.nvidia.shader_cache_clear {
-        ps1_cmd(&mut s, "Clear NVIDIA shader cache...", &[
-            "$shaderPaths = @(",
-            "    (Join-Path $env:LOCALAPPDATA 'NVIDIA\\DXCache'),",
-            "    (Join-Path $env:LOCALAPPDATA 'NVIDIA\\GLCache'),",
-            "    (Join-Path $env:LOCALAPPDATA 'Temp\\NVIDIA Corporation\\NV_Cache')",
-            ")",
-            "foreach ($sp in $shaderPaths) {",
-            "    if (Test-Path $sp) { Remove-Item -Path \"$sp\\*\" -Recurse -Force -ErrorAction SilentlyContinue }",
-            "}",
-            "Write-Host '    ✔ NVIDIA shader cache cleared.' -ForegroundColor Green",
-        ]);
-    }
-    if config.nvidia.force_reflex {
-        ps1_cmd(&mut s, "NVIDIA: Force Reflex On+Boost...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'ReflexMode' -Value 3 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Reflex On+Boost enabled.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    if config.nvidia.disable_sharpening {
-        ps1_cmd(&mut s, "NVIDIA: Disable Image Sharpening...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'SharpenEnabled' -Value 0 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Image Sharpening disabled.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    if config.nvidia.texture_filter_perf {
-        ps1_cmd(&mut s, "NVIDIA: Texture Filtering Quality = High Performance...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'TextureFilterQuality' -Value 0 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Texture Filtering = High Performance.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    if config.nvidia.pre_rendered_frames_1 {
-        ps1_cmd(&mut s, "NVIDIA: Max Pre-Rendered Frames = 1...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'MaxFrameAllowed' -Value 1 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Pre-Rendered Frames = 1.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    if config.nvidia.disable_ambient_occlusion {
-        ps1_cmd(&mut s, "NVIDIA: Disable Ambient Occlusion...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'AmbientOcclusionMode' -Value 0 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Set-ItemProperty -Path $nvPath -Name 'AmbientOcclusion' -Value 0 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Ambient Occlusion disabled.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    if config.nvidia.disable_fxaa {
-        ps1_cmd(&mut s, "NVIDIA: Disable Global FXAA...", &[
-            "if ($nvPath) {",
-            "    Set-ItemProperty -Path $nvPath -Name 'FXAAEnable' -Value 0 -Type DWord -Force -ErrorAction SilentlyContinue",
-            "    Write-Host '    ✔ NVIDIA Global FXAA disabled.' -ForegroundColor Green",
-            "}",
-        ]);
-    }
-    } // end nvidia
-
-    // ── 5. Services ─────────────────────────────────────────────────
-    if run_all || sf == Some("services") {
-    ps1_section(&mut s, 5, "Services -- Disable Unnecessary", "SVC");
-
-    let services: Vec<(&str, &str, bool)> = vec![
-        ("SysMain", "SysMain (Superfetch)", config.services.disable_sysmain),
-        ("DiagTrack", "DiagTrack (Telemetry)", config.services.disable_diagtrack),
-        ("WSearch", "Windows Search", config.services.disable_wsearch),
-        ("Spooler", "Print Spooler", config.services.disable_print_spooler),
-        ("Fax", "Fax Service", config.services.disable_fax),
-        ("CDPSvc", "Connected Devices Platform", config.services.disable_cdp),
-        ("DPS", "Diagnostic Policy", config.services.disable_diagnostic_policy),
-        ("RemoteRegistry", "Remote Registry", config.services.disable_remote_registry),
-        ("MapsBroker", "MapsBroker", config.services.disable_maps_broker),
-        ("PhoneSvc", "Phone Service", config.services.disable_phone_service),
-        ("RetailDemo", "RetailDemo Service", config.services.disable_retail_demo),
-    ];
+fn ps1_preamble(s: &mut String, sec: &tweaks::Section) {
+    for line in &sec.preamble {
+        if !line.is_empty() {
+            s.push_str("    ");
+            s.push_str(line);
+        }
+        s.push('\n');
+    }
+}
 
-    for (svc, name, enabled) in &services {
-        if *enabled {
-            ps1_cmd(&mut s, &format!("Disable {}...", name), &[
-                &format!("Stop-Service -Name '{}' -Force -ErrorAction SilentlyContinue", svc),
-                &format!("Set-Service -Name '{}' -StartupType Disabled -ErrorAction SilentlyContinue", svc),
-            ]);
+// ────────────────────────────────────────────────────────────────────
+// Section emitters – toggles come from the tweak catalog, autoexec and
+// launch options are value-driven and rendered here.
+// ────────────────────────────────────────────────────────────────────
+fn ps1_tweak(s: &mut String, tweak: &tweaks::Tweak) {
+    let Some(req) = &tweak.os else {
+        return ps1_tweak_body(s, tweak);
+    };
+    // Build / edition guard evaluated on the target machine
+    s.push_str(&format!("\n    if (-not ({})) {{\n", req.to_ps1()));
+    s.push_str(&format!(
+        "        Write-Host ('    [ - ] Not applicable on build {{0}} ({{1}}): {{2}}' -f $_aimBuild, $_aimEdition, {}) -ForegroundColor DarkGray\n",
+        ps::quote(tweak.name())
+    ));
+    s.push_str(&format!(
+        "        Write-AimStep {} {} 'skipped' $null (\"Not applicable on build $_aimBuild ($_aimEdition)\") 0\n",
+        ps::quote(&tweak.key),
+        ps::quote(tweak.name())
+    ));
+    s.push_str("    } else {\n");
+    let mut body = String::new();
+    ps1_tweak_body(&mut body, tweak);
+    for line in body.lines() {
+        if !line.is_empty() {
+            s.push_str("    ");
         }
+        s.push_str(line);
+        s.push('\n');
     }
+    s.push_str("    }\n");
+}
 
-    if config.services.disable_xbox_services {
-        ps1_cmd(&mut s, "Disable all Xbox services...", &[
-            "$xboxServices = @('XblAuthManager','XblGameSave','XboxGipSvc','XboxNetApiSvc')",
-            "foreach ($svc in $xboxServices) {",
-            "    Stop-Service -Name $svc -Force -ErrorAction SilentlyContinue",
-            "    Set-Service -Name $svc -StartupType Disabled -ErrorAction SilentlyContinue",
-            "}",
-            "Write-Host '    ✔ Xbox services disabled.' -ForegroundColor Green",
-        ]);
-    }
-    if config.services.disable_wpn {
-        ps1_cmd(&mut s, "Disable WpnUserService (push notifications)...", &[
-            "Get-Service -Name 'WpnUserService*' -ErrorAction SilentlyContinue | Stop-Service -Force -ErrorAction SilentlyContinue",
-            "Get-Service -Name 'WpnUserService*' -ErrorAction SilentlyContinue | Set-Service -StartupType Disabled -ErrorAction SilentlyContinue",
-        ]);
-    }
-    } // end services
-
-    // ── 6. autoexec.cfg ────────────────────────────────────────────
-    if run_all || sf == Some("autoexec") {
-    if config.autoexec.enabled {
-        ps1_section(&mut s, 6, "CS2 -- autoexec.cfg Generation", "CFG");
-
-        s.push_str("    $autoexecLines = @()\n");
-        s.push_str("    $autoexecLines += '// ═══════════════════════════════════════════════════════'\n");
-        s.push_str("    $autoexecLines += '// ★  aim.camp Player Agent — CS2 autoexec.cfg  ★'\n");
-        s.push_str("    $autoexecLines += '// ═══════════════════════════════════════════════════════'\n");
-        s.push_str(&format!("    $autoexecLines += 'fps_max {}'\n", config.autoexec.fps_max));
-        s.push_str(&format!("    $autoexecLines += 'rate {}'\n", config.autoexec.rate));
-        s.push_str(&format!("    $autoexecLines += 'cl_interp {}'\n", config.autoexec.cl_interp));
-        s.push_str(&format!("    $autoexecLines += 'cl_interp_ratio {}'\n", config.autoexec.cl_interp_ratio));
-        s.push_str(&format!("    $autoexecLines += 'cl_updaterate {}'\n", config.autoexec.cl_updaterate));
-        s.push_str(&format!("    $autoexecLines += 'cl_cmdrate {}'\n", config.autoexec.cl_cmdrate));
-
-        if config.autoexec.m_rawinput {
-            s.push_str("    $autoexecLines += 'm_rawinput 1'\n");
+fn ps1_tweak_body(s: &mut String, tweak: &tweaks::Tweak) {
+    if let Some(info) = &tweak.info {
+        ps1_info(s, info);
+    }
+    match &tweak.label {
+        Some(label) => {
+            let cmds: Vec<&str> = tweak.apply.iter().map(|c| c.as_str()).collect();
+            ps1_cmd(s, &tweak.key, label, &cmds);
         }
-        for line in config.autoexec.custom_commands.lines() {
-            let trimmed = line.trim();
-            if !trimmed.is_empty() {
-                s.push_str(&format!("    $autoexecLines += '{}'\n", trimmed.replace('\'', "''")));
+        None => {
+            for line in &tweak.apply {
+                s.push_str("    ");
+                s.push_str(line);
+                s.push('\n');
             }
         }
+    }
+}
+
+fn ps1_autoexec_section(
+    s: &mut String,
+    sec: &tweaks::Section,
+    ae: &AutoexecConfig,
+    account: Option<&str>,
+) -> Result<(), String> {
+    if !ae.enabled {
+        return Ok(());
+    }
+    ps1_section(s, sec.num, &sec.title, &sec.icon);
+    let dir = autoexec_dir(account)?;
+    let lines = autoexec_lines(ae, dir.as_deref(), account);
+    s.push_str("    $autoexecLines = @()\n");
+    for line in lines.content.lines() {
+        s.push_str(&format!("    $autoexecLines += {}\n", ps::quote(line)));
+    }
+    for c in &lines.conflicts {
+        let msg = format!("    ⚠ Kept your {} ({})", c.name, c.reason.to_lowercase());
+        s.push_str(&format!("    Write-Host {} -ForegroundColor Yellow\n", ps::quote(&msg)));
+    }
 
-        s.push_str("\n    # ── Auto-detect CS2 cfg folder ──\n");
-        s.push_str("    $steamBase = @('C:\\Program Files (x86)\\Steam','D:\\Steam','D:\\SteamLibrary','E:\\SteamLibrary','F:\\SteamLibrary')\n");
+    if let (Some(_), Some(dir)) = (account, &dir) {
+        s.push_str("\n    # ── The chosen Steam account's CS2 cfg folder ──\n");
+        s.push_str(&format!("    $cfgFolder = {}\n", ps::quote(&dir.to_string_lossy())));
+    } else {
+        s.push_str("\n    # ── Find the CS2 cfg folder via Steam's library list ──\n");
+        s.push_str("    $steamPath = (Get-ItemProperty -Path 'HKCU:\\Software\\Valve\\Steam' -Name 'SteamPath' -ErrorAction SilentlyContinue).SteamPath\n");
+        s.push_str("    if (-not $steamPath) {\n");
+        s.push_str("        $steamPath = (Get-ItemProperty -Path 'HKLM:\\SOFTWARE\\WOW6432Node\\Valve\\Steam' -Name 'InstallPath' -ErrorAction SilentlyContinue).InstallPath\n");
+        s.push_str("    }\n");
+        s.push_str("    $steamBase = @()\n");
+        s.push_str("    if ($steamPath) {\n");
+        s.push_str("        $steamBase += $steamPath\n");
+        s.push_str("        $libVdf = Join-Path $steamPath 'steamapps\\libraryfolders.vdf'\n");
+        s.push_str("        if (Test-Path $libVdf) {\n");
+        s.push_str("            foreach ($m in (Select-String -Path $libVdf -Pattern '^\\s*\"(?:path|\\d+)\"\\s+\"([A-Za-z]:.*)\"\\s*$')) {\n");
+        s.push_str("                $steamBase += ($m.Matches[0].Groups[1].Value -replace '\\\\\\\\', '\\')\n");
+        s.push_str("            }\n");
+        s.push_str("        }\n");
+        s.push_str("    }\n");
         s.push_str("    $cfgFolder = ''\n");
         s.push_str("    foreach ($base in $steamBase) {\n");
         s.push_str("        $test = Join-Path $base 'steamapps\\common\\Counter-Strike Global Offensive\\game\\csgo\\cfg'\n");
         s.push_str("        if (Test-Path $test) { $cfgFolder = $test; break }\n");
         s.push_str("    }\n");
-        s.push_str("    if ($cfgFolder -eq '') {\n");
-        s.push_str("        $steamPath = (Get-ItemProperty -Path 'HKLM:\\SOFTWARE\\WOW6432Node\\Valve\\Steam' -Name 'InstallPath' -ErrorAction SilentlyContinue).InstallPath\n");
-        s.push_str("        if ($steamPath) {\n");
-        s.push_str("            $test = Join-Path $steamPath 'steamapps\\common\\Counter-Strike Global Offensive\\game\\csgo\\cfg'\n");
-        s.push_str("            if (Test-Path $test) { $cfgFolder = $test }\n");
-        s.push_str("        }\n");
-        s.push_str("    }\n");
-        s.push_str("    if ($cfgFolder -eq '') {\n");
-        s.push_str("        Write-Host '    ⚠ CS2 cfg folder not found. autoexec.cfg created in current directory.' -ForegroundColor Yellow\n");
-        s.push_str("        $cfgFolder = '.'\n");
-        s.push_str("    } else {\n");
-        s.push_str("        Write-Host \"    ✔ CS2 folder found: $cfgFolder\" -ForegroundColor Green\n");
-        s.push_str("    }\n");
-        s.push_str("    $autoexecPath = Join-Path $cfgFolder 'autoexec.cfg'\n");
-        s.push_str("    $autoexecLines | Set-Content -Path $autoexecPath -Encoding UTF8\n");
-        s.push_str("    Write-Host \"    ✔ autoexec.cfg written to: $autoexecPath\" -ForegroundColor Green\n");
-    }
-    } // end autoexec
-
-    // ── 7. Launch options ─────────────────────────────────────────
-    if run_all || sf == Some("launch") {
-    ps1_section(&mut s, 7, "CS2 -- Launch Options", "RUN");
-    {
-        let mut opts: Vec<String> = Vec::new();
-        if config.launch_options.exec_autoexec { opts.push("+exec autoexec.cfg".into()); }
-        if config.launch_options.novid          { opts.push("-novid".into()); }
-        if config.launch_options.nojoy          { opts.push("-nojoy".into()); }
-        if config.launch_options.high_priority  { opts.push("-high".into()); }
-        if config.launch_options.allow_third_party { opts.push("-allow_third_party_software".into()); }
-        if !config.launch_options.threads.is_empty() {
-            opts.push(format!("-threads {}", config.launch_options.threads));
+    }
+    s.push_str("    if ($cfgFolder -eq '') {\n");
+    s.push_str("        Write-Host '    ⚠ CS2 cfg folder not found. autoexec.cfg created in current directory.' -ForegroundColor Yellow\n");
+    s.push_str("        $cfgFolder = '.'\n");
+    s.push_str("    } else {\n");
+    s.push_str("        Write-Host \"    ✔ CS2 folder found: $cfgFolder\" -ForegroundColor Green\n");
+    s.push_str("    }\n");
+    s.push_str("    $autoexecPath = Join-Path $cfgFolder 'autoexec.cfg'\n");
+    s.push_str("    $autoexecLines | Set-Content -Path $autoexecPath -Encoding UTF8\n");
+    s.push_str("    if ($?) {\n");
+    s.push_str("        Write-Host \"    ✔ autoexec.cfg written to: $autoexecPath\" -ForegroundColor Green\n");
+    // The next merge compares against what this run generated, recorded
+    // only once the file is really there (also for scripts run later)
+    s.push_str(&format!(
+        "        [IO.File]::WriteAllText({}, {}, (New-Object Text.UTF8Encoding $false))\n",
+        ps::quote(&last_autoexec_path(account)?.to_string_lossy()),
+        ps::quote(&generated_autoexec(ae))
+    ));
+    s.push_str("    } else {\n");
+    s.push_str("        Write-Host \"    ✖ autoexec.cfg could not be written to: $autoexecPath\" -ForegroundColor Red\n");
+    s.push_str("    }\n");
+    Ok(())
+}
+
+fn launch_options_string(lo: &LaunchOptionsConfig) -> String {
+    let mut opts: Vec<String> = Vec::new();
+    if lo.exec_autoexec { opts.push("+exec autoexec.cfg".into()); }
+    if lo.novid          { opts.push("-novid".into()); }
+    if lo.nojoy          { opts.push("-nojoy".into()); }
+    if lo.high_priority  { opts.push("-high".into()); }
+    if lo.allow_third_party { opts.push("-allow_third_party_software".into()); }
+    if !lo.threads.is_empty() {
+        opts.push(format!("-threads {}", lo.threads.trim()));
+    }
+    if !lo.custom_args.is_empty() {
+        opts.push(lo.custom_args.trim().to_string());
+    }
+    opts.join(" ")
+}
+
+fn ps1_launch_section(s: &mut String, sec: &tweaks::Section, lo: &LaunchOptionsConfig) {
+    ps1_section(s, sec.num, &sec.title, &sec.icon);
+    let opts_str = launch_options_string(lo);
+    s.push_str("    Write-Host '    Set these in CS2 Steam properties:' -ForegroundColor White\n");
+    s.push_str(&format!("    Write-Host {} -ForegroundColor Green\n", ps::quote(&format!("    {}", opts_str))));
+    s.push_str(&format!("    Set-Clipboard -Value {}\n", ps::quote(&opts_str)));
+    s.push_str("    Write-Host '    (Copied to clipboard!)' -ForegroundColor Cyan\n");
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Script generator
+// ────────────────────────────────────────────────────────────────────
+#[tauri::command]
+async fn generate_script(
+    config: OptimizationConfig,
+    section: Option<String>,
+    skip: Option<Vec<String>>,
+    account: Option<String>,
+    acknowledge: Option<Vec<String>>,
+) -> Result<String, ps::CommandError> {
+    config.validate(section.as_deref())?;
+    let mut s = String::with_capacity(32_000);
+    let run_all = section.is_none();
+    let sf = section.as_deref();
+    let skip = skip.unwrap_or_default();
+
+    let cfg_value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    require_acknowledged(tweaks::catalog().selected(&cfg_value, sf, &skip), acknowledge)?;
+    s.push_str(&script_config::header(&cfg_value, sf));
+    ps1_header(&mut s, &config.theme_primary, &config.theme_secondary);
+    let catalog = tweaks::catalog();
+
+    for sec in &catalog.sections {
+        if !(run_all || sf == Some(sec.id.as_str())) {
+            continue;
         }
-        if !config.launch_options.custom_args.is_empty() {
-            opts.push(config.launch_options.custom_args.clone());
+        // Driver sections of the other GPU vendors stay out of the script
+        if sec.vendor.is_some() && !catalog.section_tweaks(&sec.id).any(|t| t.is_enabled(&cfg_value)) {
+            continue;
         }
-        let opts_str = opts.join(" ");
-        s.push_str("    Write-Host '    Set these in CS2 Steam properties:' -ForegroundColor White\n");
-        s.push_str(&format!("    Write-Host '    {}' -ForegroundColor Green\n", opts_str));
-        s.push_str(&format!("    Set-Clipboard -Value '{}'\n", opts_str));
-        s.push_str("    Write-Host '    (Copied to clipboard!)' -ForegroundColor Cyan\n");
-    }
-    } // end launch
-
-    // ── 8. Extras / FACEIT ─────────────────────────────────────────
-    if run_all || sf == Some("extras") {
-    ps1_section(&mut s, 8, "Extras & FACEIT", "EXT");
-
-    if config.extras.disable_steam_overlay {
-        ps1_cmd(&mut s, "Disable Steam Overlay via registry...", &[
-            "Ensure-RegPath 'HKCU:\\SOFTWARE\\Valve\\Steam'",
-            "Set-ItemProperty -Path 'HKCU:\\SOFTWARE\\Valve\\Steam' -Name 'GameOverlayDisabled' -Value 1 -Type DWord -Force",
-        ]);
-    }
-    if config.extras.disable_discord_overlay {
-        ps1_cmd(&mut s, "Disable Discord Overlay (kill hook)...", &[
-            "Write-Host '    💡 Open Discord > Settings > Game Overlay > Disable in-game overlay' -ForegroundColor Yellow",
-            "Get-Process -Name 'DiscordHook*' -ErrorAction SilentlyContinue | Stop-Process -Force -ErrorAction SilentlyContinue",
-        ]);
-    }
-    if config.extras.system_responsiveness {
-        ps1_cmd(&mut s, "Set SystemResponsiveness for gaming (0 = max foreground priority)...", &[
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile' -Name 'SystemResponsiveness' -Value 0 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile' -Name 'NetworkThrottlingIndex' -Value 0xFFFFFFFF -Type DWord -Force",
-        ]);
-    }
-    if config.extras.gpu_priority {
-        ps1_cmd(&mut s, "Set GPU priority & scheduling for games...", &[
-            "Ensure-RegPath 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games'",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'GPU Priority' -Value 8 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'Priority' -Value 6 -Type DWord -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'Scheduling Category' -Value 'High' -Force",
-            "Set-ItemProperty -Path 'HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games' -Name 'SFIO Priority' -Value 'High' -Force",
-        ]);
-    }
-    if config.extras.priority_separation {
-        ps1_cmd(&mut s, "Optimize Win32PrioritySeparation for foreground apps...", &[
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\PriorityControl' -Name 'Win32PrioritySeparation' -Value 38 -Type DWord -Force",
-        ]);
-    }
-    if config.extras.cs2_process_priority {
-        ps1_cmd(&mut s, "Set CS2 process to High priority (if running)...", &[
-            "$cs2 = Get-Process -Name 'cs2' -ErrorAction SilentlyContinue",
-            "if ($cs2) {",
-            "    $cs2.PriorityClass = 'High'",
-            "    Write-Host '    ✔ CS2 set to High priority.' -ForegroundColor Green",
-            "} else {",
-            "    Write-Host '    ⚠ CS2 not running. Priority will be applied via -high in launch options.' -ForegroundColor Yellow",
-            "}",
-        ]);
-    }
-    if config.extras.disable_telemetry_tasks {
-        ps1_cmd(&mut s, "Disable Windows telemetry scheduled tasks...", &[
-            "Get-ScheduledTask -TaskPath '\\Microsoft\\Windows\\Application Experience\\' -ErrorAction SilentlyContinue | Where-Object { $_.TaskName -match 'CompatTelRunner|ProgramDataUpdater' } | Disable-ScheduledTask -ErrorAction SilentlyContinue | Out-Null",
-            "Get-ScheduledTask -TaskPath '\\Microsoft\\Windows\\Customer Experience Improvement Program\\' -ErrorAction SilentlyContinue | Disable-ScheduledTask -ErrorAction SilentlyContinue | Out-Null",
-        ]);
-    }
-    if config.extras.timer_resolution {
-        ps1_cmd(&mut s, "Set global timer resolution to 0.5ms...", &[
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\kernel'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\kernel' -Name 'GlobalTimerResolutionRequests' -Value 1 -Type DWord -Force",
-            "bcdedit /set useplatformclock false 2>$null",
-            "bcdedit /set disabledynamictick yes 2>$null",
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\TimeIncrement'",
-            "Write-Host '    ✔ Timer resolution configured. Reboot required.' -ForegroundColor Green",
-        ]);
-    }
-    if config.extras.msi_mode_gpu {
-        ps1_cmd(&mut s, "Enable MSI Mode for GPU (reduce DPC latency)...", &[
-            "$gpuDev = Get-PnpDevice -Class Display -Status OK -ErrorAction SilentlyContinue | Select-Object -First 1",
-            "if ($gpuDev) {",
-            "    $instanceId = $gpuDev.InstanceId -replace '\\\\', '\\'",
-            "    $msiPath = \"HKLM:\\SYSTEM\\CurrentControlSet\\Enum\\$instanceId\\Device Parameters\\Interrupt Management\\MessageSignaledInterruptProperties\"",
-            "    Ensure-RegPath $msiPath",
-            "    Set-ItemProperty -Path $msiPath -Name 'MSISupported' -Value 1 -Type DWord -Force",
-            "    Write-Host '    ✔ MSI Mode enabled for GPU.' -ForegroundColor Green",
-            "} else { Write-Host '    ⚠ No GPU found for MSI Mode.' -ForegroundColor Yellow }",
-        ]);
-    }
-    if config.extras.pcie_link_state_off {
-        ps1_cmd(&mut s, "Disable PCIe Active State Power Management (ASPM)...", &[
-            "powercfg /setacvalueindex SCHEME_CURRENT SUB_PCIEXPRESS ASPM 0 2>$null",
-            "powercfg /setactive SCHEME_CURRENT",
-        ]);
-    }
-    if config.extras.interrupt_moderation_off {
-        ps1_cmd(&mut s, "Disable network adapter Interrupt Moderation...", &[
-            "$netAdapters = Get-NetAdapter | Where-Object { $_.Status -eq 'Up' }",
-            "foreach ($a in $netAdapters) {",
-            "    Set-NetAdapterAdvancedProperty -Name $a.Name -RegistryKeyword '*InterruptModeration' -RegistryValue 0 -ErrorAction SilentlyContinue",
-            "}",
-            "Write-Host '    ✔ Interrupt Moderation disabled on active adapters.' -ForegroundColor Green",
-        ]);
-    }
-    if config.extras.enable_large_pages {
-        ps1_cmd(&mut s, "Enable Large Pages privilege for current user...", &[
-            "# Grant SeLockMemoryPrivilege via secpol",
-            "$currentUser = [System.Security.Principal.WindowsIdentity]::GetCurrent().Name",
-            "Write-Host \"    ⚠ To enable Large Pages, grant SeLockMemoryPrivilege to $currentUser in Local Security Policy.\" -ForegroundColor Yellow",
-            "Write-Host '    ⚠ secpol.msc > Local Policies > User Rights Assignment > Lock pages in memory' -ForegroundColor Yellow",
-            "Ensure-RegPath 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management'",
-            "Set-ItemProperty -Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management' -Name 'LargePageMinimum' -Value 0 -Type DWord -Force",
-        ]);
-    }
-    if config.extras.faceit_admin {
-        ps1_cmd(&mut s, "Check FACEIT Anti-Cheat...", &[
-            "$faceit = Get-Service -Name 'FACEITService' -ErrorAction SilentlyContinue",
-            "if ($faceit) { Write-Host '    ✔ FACEIT AC found and running.' -ForegroundColor Green }",
-            "else { Write-Host '    ⚠ FACEIT AC not found. Make sure it is installed and running as Admin.' -ForegroundColor Yellow }",
-            "Write-Host '    💡 Always run FACEIT AC as Administrator.' -ForegroundColor Yellow",
-        ]);
-    }
-    } // end extras
+        match sec.id.as_str() {
+            "autoexec" => ps1_autoexec_section(&mut s, sec, &config.autoexec, account.as_deref())?,
+            "launch" => ps1_launch_section(&mut s, sec, &config.launch_options),
+            _ => {
+                ps1_section(&mut s, sec.num, &sec.title, &sec.icon);
+                ps1_preamble(&mut s, sec);
+                for tweak in catalog.section_tweaks(&sec.id) {
+                    if !tweak.is_enabled(&cfg_value) {
+                        continue;
+                    }
+                    if skip.contains(&tweak.key) {
+                        ps1_info(&mut s, &format!("Already applied, skipped: {}", tweak.name()));
+                    } else {
+                        ps1_tweak(&mut s, tweak);
+                    }
+                }
+            }
+        }
+    }
 
     // ── Galaxy Footer ───────────────────────────────────────────────
     ps1_footer(&mut s);
@@ -1134,6 +755,95 @@ async fn generate_script(config: OptimizationConfig, section: Option<String>) ->
     Ok(s)
 }
 
+/// High-risk tweaks need an explicit acknowledgement by key.
+fn require_acknowledged<'a>(
+    selected: impl Iterator<Item = &'a tweaks::Tweak>,
+    acknowledge: Option<Vec<String>>,
+) -> Result<(), ps::CommandError> {
+    let pending = tweaks::unacknowledged(selected, &acknowledge.unwrap_or_default());
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<&str> = pending.iter().map(|t| t.name.as_str()).collect();
+    Err(ps::CommandError::Unacknowledged {
+        message: format!("High-risk tweaks need to be acknowledged: {}", names.join(", ")),
+        tweaks: pending,
+    })
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Revert script – restores Windows defaults for exactly the toggles
+// set in the given config, section by section like generate_script.
+// ────────────────────────────────────────────────────────────────────
+fn ps1_revert_tweak(s: &mut String, tweak: &tweaks::Tweak) {
+    if let Some(info) = &tweak.info {
+        ps1_info(s, &format!("Undo manually in BIOS if changed: {}", info));
+    }
+    if !tweak.revert.is_empty() {
+        let cmds: Vec<&str> = tweak.revert.iter().map(|c| c.as_str()).collect();
+        ps1_cmd(s, &tweak.key, &format!("Revert: {}", tweak.name()), &cmds);
+    } else if tweak.reversibility == tweaks::Reversibility::Manual && tweak.info.is_none() {
+        ps1_info(s, &format!("Not reverted automatically, restore a snapshot to undo: {}", tweak.name()));
+    } else if tweak.info.is_none() {
+        ps1_info(s, &format!("Nothing to revert: {}", tweak.name()));
+    }
+}
+
+#[tauri::command]
+async fn generate_revert_script(
+    config: OptimizationConfig,
+    section: Option<String>,
+) -> Result<String, ps::CommandError> {
+    config.validate(section.as_deref())?;
+    let mut s = String::with_capacity(16_000);
+    let run_all = section.is_none();
+    let sf = section.as_deref();
+
+    let cfg_value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    s.push_str(&script_config::header(&cfg_value, sf));
+    ps1_header(&mut s, &config.theme_primary, &config.theme_secondary);
+    let catalog = tweaks::catalog();
+
+    for sec in &catalog.sections {
+        if !(run_all || sf == Some(sec.id.as_str())) {
+            continue;
+        }
+        let enabled: Vec<&tweaks::Tweak> = catalog
+            .section_tweaks(&sec.id)
+            .filter(|t| t.is_enabled(&cfg_value))
+            .collect();
+        let has_note = match sec.id.as_str() {
+            "autoexec" => config.autoexec.enabled,
+            "launch" => {
+                let lo = &config.launch_options;
+                lo.novid || lo.nojoy || lo.high_priority || lo.allow_third_party || lo.exec_autoexec
+                    || !lo.threads.is_empty() || !lo.custom_args.is_empty()
+            }
+            _ => false,
+        };
+        if enabled.is_empty() && !has_note {
+            continue;
+        }
+
+        ps1_section(&mut s, sec.num, &format!("{} (revert)", sec.title), &sec.icon);
+        if has_note {
+            if let Some(note) = &sec.revert_note {
+                ps1_info(&mut s, note);
+            }
+            continue;
+        }
+        ps1_preamble(&mut s, sec);
+        for tweak in enabled {
+            ps1_revert_tweak(&mut s, tweak);
+        }
+    }
+
+    ps1_footer_with(&mut s, "Defaults restored", "Remove CS2 launch options in Steam.");
+
+    Ok(s)
+}
+
 // ────────────────────────────────────────────────────────────────────
 // Save script to file
 // ────────────────────────────────────────────────────────────────────
@@ -1179,30 +889,34 @@ async fn save_script(script_content: String) -> Result<(), String> {
 // Save CFG file
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
-async fn save_cfg(cfg_content: String) -> Result<(), String> {
+async fn save_cfg(cfg_content: String, account: Option<String>) -> Result<(), String> {
     let (tx, rx) = std::sync::mpsc::channel::<Option<Result<(), String>>>();
 
-    FileDialogBuilder::new()
+    let mut dialog = FileDialogBuilder::new()
         .set_file_name("autoexec.cfg")
-        .add_filter("CS2 Config", &["cfg"])
-        .save_file(move |file_path| {
-            if let Some(path) = file_path {
-                match File::create(&path) {
-                    Ok(mut file) => {
-                        if let Err(e) = file.write_all(cfg_content.as_bytes()) {
-                            let _ = tx.send(Some(Err(format!("Failed to write CFG: {}", e))));
-                            return;
-                        }
-                        let _ = tx.send(Some(Ok(())));
-                    }
-                    Err(e) => {
-                        let _ = tx.send(Some(Err(format!("Failed to create file: {}", e))));
+        .add_filter("CS2 Config", &["cfg"]);
+    // Start in the chosen account's cfg folder (or the game's)
+    if let Some(dir) = autoexec_dir(account.as_deref())? {
+        dialog = dialog.set_directory(dir);
+    }
+    dialog.save_file(move |file_path| {
+        if let Some(path) = file_path {
+            match File::create(&path) {
+                Ok(mut file) => {
+                    if let Err(e) = file.write_all(cfg_content.as_bytes()) {
+                        let _ = tx.send(Some(Err(format!("Failed to write CFG: {}", e))));
+                        return;
                     }
+                    let _ = tx.send(Some(Ok(())));
+                }
+                Err(e) => {
+                    let _ = tx.send(Some(Err(format!("Failed to create file: {}", e))));
                 }
-            } else {
-                let _ = tx.send(None); // User cancelled
             }
-        });
+        } else {
+            let _ = tx.send(None); // User cancelled
+        }
+    });
 
     match rx.recv() {
         Ok(Some(Ok(()))) => Ok(()),
@@ -1248,6 +962,294 @@ async fn load_cfg() -> Result<ImportResult, String> {
     }
 }
 
+// ────────────────────────────────────────────────────────────────────
+// CFG lint — parse a cfg and check it against the cvar database. An
+// imported `cvarlist` dump replaces the builtin database.
+// ────────────────────────────────────────────────────────────────────
+fn cvar_db_path() -> Result<std::path::PathBuf, String> {
+    let mut dir = dirs_next::data_local_dir()
+        .ok_or_else(|| "Cannot find local data dir".to_string())?;
+    dir.push("aimcamp-player-agent");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Dir create failed: {}", e))?;
+    Ok(dir.join("cvars.json"))
+}
+
+#[tauri::command]
+async fn parse_cfg(content: String) -> Result<cfg::CfgFile, String> {
+    Ok(cfg::parse(&content))
+}
+
+#[tauri::command]
+async fn lint_cfg(content: String) -> Result<Vec<cfg::LintIssue>, String> {
+    let path = cvar_db_path()?;
+    let file = cfg::parse(&content);
+    if path.exists() {
+        Ok(cfg::lint(&file, &cvars::Database::load(&path)?))
+    } else {
+        Ok(cfg::lint(&file, cvars::builtin()))
+    }
+}
+
+/// Build the cvar database from `cvarlist` output; returns the entry count.
+#[tauri::command]
+async fn import_cvarlist(content: String) -> Result<usize, String> {
+    let db = cvars::from_cvarlist(&content, cvars::builtin())?;
+    db.save(&cvar_db_path()?)?;
+    Ok(db.cvars.len())
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Autoexec round-trip — map a player's cfg onto AutoexecConfig and merge
+// regenerated lines into the autoexec.cfg already in the CS2 folder
+// ────────────────────────────────────────────────────────────────────
+const CS2_CFG_SUBDIR: &str = "game\\csgo\\cfg";
+
+fn cs2_cfg_dir() -> Option<std::path::PathBuf> {
+    let root = steam::root()?;
+    Some(steam::cs2_dir(&root)?.join(CS2_CFG_SUBDIR)).filter(|p| p.is_dir())
+}
+
+/// CS2 cfg folder of one Steam account, by SteamID64.
+fn account_cfg_dir(steam_id64: &str) -> Result<std::path::PathBuf, String> {
+    let root = steam::root().ok_or_else(|| "Steam install not found".to_string())?;
+    let dir = steam::cfg_dir(&root, steam::account_id(steam_id64)?);
+    if dir.is_dir() {
+        Ok(dir)
+    } else {
+        Err(format!("Steam account {} has no CS2 cfg folder yet — start CS2 on it once", steam_id64))
+    }
+}
+
+/// Where autoexec.cfg goes: the chosen account's cfg folder, else the game's.
+fn autoexec_dir(account: Option<&str>) -> Result<Option<std::path::PathBuf>, String> {
+    match account {
+        Some(id) => account_cfg_dir(id).map(Some),
+        None => Ok(cs2_cfg_dir()),
+    }
+}
+
+/// What the agent last generated for this target, the merge base.
+fn last_autoexec_path(account: Option<&str>) -> Result<std::path::PathBuf, String> {
+    let mut dir = dirs_next::data_local_dir()
+        .ok_or_else(|| "Cannot find local data dir".to_string())?;
+    dir.push("aimcamp-player-agent");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Dir create failed: {}", e))?;
+    Ok(match account {
+        // Only a valid SteamID64 ends up in the file name
+        Some(id) => {
+            steam::account_id(id)?;
+            dir.join(format!("autoexec.{}.generated.cfg", id.trim()))
+        }
+        None => dir.join("autoexec.generated.cfg"),
+    })
+}
+
+fn generated_autoexec(ae: &AutoexecConfig) -> String {
+    let mut content = autoexec::generate(ae).join("\n");
+    content.push('\n');
+    content
+}
+
+/// The autoexec the script writes: the generated lines merged into the
+/// player's current file when there is one.
+fn autoexec_lines(ae: &AutoexecConfig, dir: Option<&std::path::Path>, account: Option<&str>) -> autoexec::Merge {
+    let generated = generated_autoexec(ae);
+    let current = dir.and_then(|d| std::fs::read_to_string(d.join("autoexec.cfg")).ok());
+    match current {
+        Some(current) => {
+            let base = last_autoexec_path(account).ok().and_then(|p| std::fs::read_to_string(p).ok());
+            autoexec::merge(base.as_deref(), &current, &generated)
+        }
+        None => autoexec::Merge { content: generated, conflicts: vec![], has_base: false },
+    }
+}
+
+/// Fill AutoexecConfig from a cfg; unmapped lines become custom commands.
+#[tauri::command]
+async fn import_autoexec(content: String, current: AutoexecConfig) -> Result<AutoexecConfig, String> {
+    let mut ae = current;
+    autoexec::apply_cfg(&mut ae, &cfg::parse(&content));
+    Ok(ae)
+}
+
+/// Preview what regenerating would do to `current` (the player's file).
+#[tauri::command]
+async fn merge_autoexec(
+    autoexec: AutoexecConfig,
+    current: String,
+    account: Option<String>,
+) -> Result<autoexec::Merge, String> {
+    let base = std::fs::read_to_string(last_autoexec_path(account.as_deref())?).ok();
+    Ok(autoexec::merge(base.as_deref(), &current, &generated_autoexec(&autoexec)))
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Steam launch options — written straight into localconfig.vdf
+// ────────────────────────────────────────────────────────────────────
+#[derive(serde::Serialize)]
+struct LaunchOptionsWrite {
+    path: String,
+    /// None when the file already had these options
+    backup: Option<String>,
+}
+
+#[tauri::command]
+async fn list_steam_accounts() -> Result<Vec<steam::Account>, String> {
+    let root = steam::root().ok_or_else(|| "Steam install not found".to_string())?;
+    steam::list_accounts(&root)
+}
+
+/// Set CS2's launch options for the chosen Steam account, or every
+/// account on this PC. Steam rewrites localconfig.vdf when it exits, so
+/// it must be closed first.
+#[tauri::command]
+async fn apply_launch_options(
+    launch_options: LaunchOptionsConfig,
+    account: Option<String>,
+) -> Result<Vec<LaunchOptionsWrite>, String> {
+    let root = steam::root().ok_or_else(|| "Steam install not found".to_string())?;
+    if steam::is_running("steam.exe") {
+        return Err("Close Steam first — it overwrites launch options when it exits".into());
+    }
+    let files = match account.as_deref() {
+        Some(id) => {
+            let path = steam::localconfig(&root, steam::account_id(id)?);
+            if !path.is_file() {
+                return Err(format!("Steam account {} has no localconfig.vdf", id));
+            }
+            vec![path]
+        }
+        None => steam::localconfigs(&root),
+    };
+    if files.is_empty() {
+        return Err("No Steam accounts found (userdata has no localconfig.vdf)".into());
+    }
+    let opts = launch_options_string(&launch_options);
+    files
+        .iter()
+        .map(|path| {
+            let backup = steam::write_launch_options(path, &opts)?;
+            Ok(LaunchOptionsWrite {
+                path: path.to_string_lossy().to_string(),
+                backup: backup.map(|b| b.to_string_lossy().to_string()),
+            })
+        })
+        .collect()
+}
+
+// ────────────────────────────────────────────────────────────────────
+// CS2 video settings — cs2_video.txt and cs2_machine_convars.vcfg
+// ────────────────────────────────────────────────────────────────────
+/// cs2_video.txt of the chosen account, else of the account that signed
+/// in last.
+fn video_file(account: Option<&str>) -> Result<std::path::PathBuf, String> {
+    let dir = match account {
+        Some(id) => account_cfg_dir(id)?,
+        None => {
+            let root = steam::root().ok_or_else(|| "Steam install not found".to_string())?;
+            steam::list_accounts(&root)?
+                .into_iter()
+                .find_map(|a| a.cfg_dir)
+                .map(std::path::PathBuf::from)
+                .ok_or_else(|| "No Steam account has run CS2 yet".to_string())?
+        }
+    };
+    Ok(dir.join(video::VIDEO_FILE))
+}
+
+fn machine_convars_file() -> Option<std::path::PathBuf> {
+    cs2_cfg_dir().map(|d| d.join(video::MACHINE_FILE)).filter(|p| p.is_file())
+}
+
+fn read_vdf(path: &std::path::Path) -> Result<(String, vdf::Document), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let doc = vdf::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok((text, doc))
+}
+
+#[derive(serde::Serialize)]
+struct VideoState {
+    settings: video::VideoSettings,
+    video_path: String,
+    /// None when CS2 hasn't created its machine convars yet
+    machine_path: Option<String>,
+}
+
+#[tauri::command]
+async fn read_video_settings(account: Option<String>) -> Result<VideoState, String> {
+    let path = video_file(account.as_deref())?;
+    let (_, doc) = read_vdf(&path)?;
+    let machine_path = machine_convars_file();
+    let machine = match &machine_path {
+        Some(p) => Some(read_vdf(p)?.1),
+        None => None,
+    };
+    Ok(VideoState {
+        settings: video::read(&doc, machine.as_ref()),
+        video_path: path.to_string_lossy().to_string(),
+        machine_path: machine_path.map(|p| p.to_string_lossy().to_string()),
+    })
+}
+
+#[tauri::command]
+async fn list_video_presets() -> Result<Vec<video::Preset>, String> {
+    Ok(video::presets().to_vec())
+}
+
+/// Write the fields `settings` sets into CS2's files, backing each file
+/// up first; returns the backups. CS2 saves cs2_video.txt when it exits,
+/// so it has to be closed.
+#[tauri::command]
+async fn write_video_settings(settings: video::VideoSettings, account: Option<String>) -> Result<Vec<String>, String> {
+    if steam::is_running("cs2.exe") {
+        return Err("Close CS2 first — it overwrites its video settings when it exits".into());
+    }
+    let path = video_file(account.as_deref())?;
+    let (text, mut doc) = read_vdf(&path)?;
+    let machine_path = machine_convars_file();
+    let mut machine = match &machine_path {
+        Some(p) => Some(read_vdf(p)?),
+        None => None,
+    };
+    video::apply(&settings, &mut doc, machine.as_mut().map(|(_, m)| m))?;
+
+    let mut backups = Vec::new();
+    if doc.to_string() != text {
+        backups.push(vdf::save(&path, &doc)?);
+    }
+    if let (Some(p), Some((text, m))) = (&machine_path, &machine) {
+        if m.to_string() != *text {
+            backups.push(vdf::save(p, m)?);
+        }
+    }
+    Ok(backups.iter().map(|b| b.to_string_lossy().to_string()).collect())
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Crosshair share codes
+// ────────────────────────────────────────────────────────────────────
+#[derive(serde::Serialize)]
+struct CvarValue {
+    name: String,
+    value: String,
+}
+
+#[tauri::command]
+async fn decode_crosshair(code: String) -> Result<Vec<CvarValue>, String> {
+    let xh = crosshair::decode(&code)?;
+    Ok(xh
+        .cvars()
+        .into_iter()
+        .map(|(name, value)| CvarValue { name: name.into(), value })
+        .collect())
+}
+
+/// Encode cl_crosshair* values; missing ones count as CS2's defaults.
+#[tauri::command]
+async fn encode_crosshair(cvars: std::collections::HashMap<String, String>) -> Result<String, String> {
+    Ok(crosshair::encode(&crosshair::Crosshair::from_cvars(&cvars)))
+}
+
 // ────────────────────────────────────────────────────────────────────
 // Import .ps1 file
 // ────────────────────────────────────────────────────────────────────
@@ -1290,34 +1292,71 @@ async fn import_script() -> Result<ImportResult, String> {
     }
 }
 
+// ────────────────────────────────────────────────────────────────────
+// Import config: rebuild an OptimizationConfig from a script's content
+// ────────────────────────────────────────────────────────────────────
+#[derive(serde::Serialize)]
+struct ImportedScriptConfig {
+    source: script_config::ImportSource,
+    generator: Option<String>,
+    section: Option<String>,
+    config: OptimizationConfig,
+    /// UI key → checked, ready for the toggle grid
+    toggles: std::collections::BTreeMap<String, bool>,
+}
+
+#[tauri::command]
+async fn import_script_config(content: String) -> Result<ImportedScriptConfig, String> {
+    let imported = script_config::import(&content)?;
+    let config: OptimizationConfig = serde_json::from_value(imported.config)
+        .map_err(|e| format!("Script config: {}", e))?;
+    let cfg_value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    Ok(ImportedScriptConfig {
+        source: imported.source,
+        generator: imported.generator,
+        section: imported.section,
+        toggles: script_config::toggles(&cfg_value),
+        config,
+    })
+}
+
 // ────────────────────────────────────────────────────────────────────
 // Run: execute a .ps1 as admin using PowerShell Start-Process -Verb RunAs
 // The user picks any .ps1 file and it gets launched in an elevated shell.
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
-async fn run_script_as_admin(script_path: String) -> Result<String, String> {
-    let path = if script_path.is_empty() {
-        return Err("No script path provided.".into());
+async fn run_script_as_admin(script_path: String, preflight: Option<bool>) -> Result<String, ps::CommandError> {
+    let path = ps::file_path("script_path", &script_path, "ps1")?;
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read script: {}", e))?;
+
+    // Our own scripts carry their config, which says what they will touch;
+    // for any other script the pre-flight step only sets a restore point.
+    // Older headers name tweaks the way their schema version did
+    let imported = script_config::import(&content)
+        .ok()
+        .and_then(|i| Some((migrate::upgrade(i.config).ok()?, i.section)));
+    let selected: Vec<&tweaks::Tweak> = imported
+        .as_ref()
+        .map(|(config, section)| tweaks::catalog().selected(config, section.as_deref(), &[]).collect())
+        .unwrap_or_default();
+    // Generating the script required acknowledging these; record them as
+    // run_config_as_admin does
+    let acknowledged: Vec<String> = selected
+        .iter()
+        .filter(|t| t.risk == tweaks::Risk::High)
+        .map(|t| t.key.clone())
+        .collect();
+    let prelude = if preflight.unwrap_or(true) {
+        let profile = std::path::Path::new(&path).file_stem().map(|n| n.to_string_lossy().to_string());
+        preflight::script(&selected, profile.as_deref(), &backup_dir()?.to_string_lossy())
     } else {
-        script_path
+        String::new()
     };
 
-    // Use cmd to call PowerShell's Start-Process with -Verb RunAs
-    let result = Command::new("powershell")
-        .args(&[
-            "-NoProfile",
-            "-Command",
-            &format!(
-                "Start-Process powershell -Verb RunAs -ArgumentList '-NoProfile -ExecutionPolicy Bypass -File \"{}\"'",
-                path.replace('\'', "''")
-            ),
-        ])
-        .spawn();
-
-    match result {
-        Ok(_) => Ok(format!("Script launched as Administrator: {}", path)),
-        Err(e) => Err(format!("Failed to launch: {}", e)),
-    }
+    let wrapped = report_wrapper(&prelude, &format!("& {}", ps::quote(&path)), None, &acknowledged);
+    run_ps1_elevated(&wrapped, "aimcamp_run_file.ps1")?;
+    Ok(format!("Script launched as Administrator: {}", path))
 }
 
 // ────────────────────────────────────────────────────────────────────
@@ -1346,13 +1385,14 @@ async fn pick_ps1_file() -> Result<String, String> {
 }
 
 // ────────────────────────────────────────────────────────────────────
-// Schema ZIP: import a .zip containing schema.pla / sys.ps1 / game.cfg
+// Schema ZIP: import a .zip containing schema.pla / sys.ps1 / game.cfg / cs2_video.json
 // ────────────────────────────────────────────────────────────────────
 #[derive(serde::Serialize)]
 struct SchemaZipContents {
     pla: Option<String>,
     ps1: Option<String>,
     cfg: Option<String>,
+    video: Option<video::VideoSettings>,
 }
 
 #[tauri::command]
@@ -1382,6 +1422,7 @@ async fn import_schema_zip() -> Result<SchemaZipContents, String> {
     let mut pla: Option<String> = None;
     let mut ps1: Option<String> = None;
     let mut cfg: Option<String> = None;
+    let mut video: Option<video::VideoSettings> = None;
 
     for i in 0..archive.len() {
         let mut entry = archive.by_index(i).map_err(|e| format!("ZIP entry error: {}", e))?;
@@ -1390,19 +1431,37 @@ async fn import_schema_zip() -> Result<SchemaZipContents, String> {
         entry.read_to_string(&mut content).map_err(|e| format!("Read error: {}", e))?;
 
         if name.ends_with(".pla") {
+            // Refuse schemas exported by a newer agent instead of half-loading them
+            let version = serde_json::from_str::<serde_json::Value>(&content)
+                .ok()
+                .and_then(|v| v.get("schema_version").and_then(|n| n.as_u64()));
+            if let Some(version) = version {
+                migrate::check_version(version as u32)?;
+            }
             pla = Some(content);
         } else if name.ends_with(".ps1") {
             ps1 = Some(content);
         } else if name.ends_with(".cfg") {
             cfg = Some(content);
+        } else if name.ends_with("video.json") {
+            let settings: video::VideoSettings =
+                serde_json::from_str(&content).map_err(|e| format!("Invalid video settings: {}", e))?;
+            settings.validate()?;
+            video = Some(settings);
         }
     }
 
-    Ok(SchemaZipContents { pla, ps1, cfg })
+    Ok(SchemaZipContents { pla, ps1, cfg, video })
 }
 
 #[tauri::command]
-async fn export_schema_zip(pla: String, ps1: Option<String>, cfg: Option<String>, schema_name: String) -> Result<(), String> {
+async fn export_schema_zip(
+    pla: String,
+    ps1: Option<String>,
+    cfg: Option<String>,
+    video: Option<video::VideoSettings>,
+    schema_name: String,
+) -> Result<(), String> {
     let (tx, rx) = std::sync::mpsc::channel::<Option<String>>();
 
     let default_name = format!("{}.zip", schema_name.replace(' ', "_"));
@@ -1443,6 +1502,12 @@ async fn export_schema_zip(pla: String, ps1: Option<String>, cfg: Option<String>
         zip_writer.write_all(cfg_content.as_bytes()).map_err(|e| format!("ZIP write error: {}", e))?;
     }
 
+    if let Some(settings) = video {
+        let json = serde_json::to_string_pretty(&settings).map_err(|e| format!("Serialize error: {}", e))?;
+        zip_writer.start_file("cs2_video.json", options).map_err(|e| format!("ZIP write error: {}", e))?;
+        zip_writer.write_all(json.as_bytes()).map_err(|e| format!("ZIP write error: {}", e))?;
+    }
+
     zip_writer.finish().map_err(|e| format!("ZIP finalize error: {}", e))?;
     Ok(())
 }
@@ -1452,7 +1517,7 @@ async fn export_schema_zip(pla: String, ps1: Option<String>, cfg: Option<String>
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
 async fn check_system_state() -> Result<serde_json::Value, String> {
-    let ps_script = include_str!("check_state.ps1");
+    let ps_script = tweaks::state_check_script();
     let temp_dir = std::env::temp_dir();
     let script_path = temp_dir.join("aimcamp_syscheck.ps1");
     std::fs::write(&script_path, ps_script)
@@ -1476,62 +1541,196 @@ async fn check_system_state() -> Result<serde_json::Value, String> {
         .map_err(|e| format!("JSON parse error: {} — raw: {}", e, stdout))
 }
 
+// ────────────────────────────────────────────────────────────────────
+// Dry-run: what would this config change on the current machine?
+// ────────────────────────────────────────────────────────────────────
+#[tauri::command]
+async fn plan_changes(
+    config: OptimizationConfig,
+    section: Option<String>,
+    os_build: Option<u32>,
+    os_edition: Option<String>,
+) -> Result<tweaks::ChangePlan, String> {
+    let cfg_value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    let state = check_system_state().await?;
+    Ok(tweaks::plan(&cfg_value, &state, section.as_deref(), os_build, os_edition.as_deref()))
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Competitive compatibility — check a profile, the running processes and
+// a cfg against the FACEIT / ESEA / matchmaking rule sets. Imported rules
+// in the data folder replace the builtin ones.
+// ────────────────────────────────────────────────────────────────────
+fn compat_rules_path() -> Result<std::path::PathBuf, String> {
+    let mut dir = dirs_next::data_local_dir()
+        .ok_or_else(|| "Cannot find local data dir".to_string())?;
+    dir.push("aimcamp-player-agent");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Dir create failed: {}", e))?;
+    Ok(dir.join("compat_rules.json"))
+}
+
+/// `cfg` is the cfg to check; without one, the autoexec the profile writes.
+#[tauri::command]
+async fn check_competitive_compat(
+    config: OptimizationConfig,
+    cfg: Option<String>,
+    os_build: Option<u32>,
+    rulesets: Option<Vec<String>>,
+) -> Result<compat::Report, String> {
+    let (imported_rules, imported_db);
+    let path = compat_rules_path()?;
+    let rules = if path.exists() {
+        imported_rules = compat::Rules::load(&path)?;
+        &imported_rules
+    } else {
+        compat::builtin()
+    };
+    let path = cvar_db_path()?;
+    let db = if path.exists() {
+        imported_db = cvars::Database::load(&path)?;
+        &imported_db
+    } else {
+        cvars::builtin()
+    };
+
+    let cfg_value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    let cfg_text = match cfg {
+        Some(text) => text,
+        None if config.autoexec.enabled => generated_autoexec(&config.autoexec),
+        None => String::new(),
+    };
+    // A single process comes back as an object rather than an array
+    let processes: Option<Vec<String>> = list_processes().await.ok().map(|v| {
+        let items = match v {
+            serde_json::Value::Array(items) => items,
+            other => vec![other],
+        };
+        items
+            .iter()
+            .filter_map(|p| p.get("name").and_then(|n| n.as_str()).map(str::to_string))
+            .collect()
+    });
+
+    let ctx = compat::Context {
+        config: &cfg_value,
+        launch_options: &launch_options_string(&config.launch_options),
+        processes: processes.as_deref(),
+        cfg: &cfg::parse(&cfg_text),
+        cvars: db,
+        os_build,
+    };
+    Ok(compat::check(rules, &ctx, &rulesets.unwrap_or_default()))
+}
+
+/// Replace the compatibility rules with an updated rule file; returns
+/// the number of rules.
+#[tauri::command]
+async fn import_compat_rules(content: String) -> Result<usize, String> {
+    let rules = compat::Rules::parse(&content)?;
+    std::fs::write(compat_rules_path()?, content)
+        .map_err(|e| format!("Failed to write compatibility rules: {}", e))?;
+    Ok(rules.rulesets.iter().map(|rs| rs.rules.len()).sum())
+}
+
 // ────────────────────────────────────────────────────────────────────
 // Generate + run script content directly as admin (no file dialog)
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
-async fn run_config_as_admin(config: OptimizationConfig, section: Option<String>) -> Result<String, String> {
-    let script = generate_script(config, section).await?;
+async fn run_config_as_admin(
+    config: OptimizationConfig,
+    section: Option<String>,
+    profile: Option<String>,
+    skip: Option<Vec<String>>,
+    account: Option<String>,
+    acknowledge: Option<Vec<String>>,
+    preflight: Option<bool>,
+) -> Result<String, ps::CommandError> {
+    let cfg_value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    let selected: Vec<&tweaks::Tweak> = tweaks::catalog()
+        .selected(&cfg_value, section.as_deref(), skip.as_deref().unwrap_or_default())
+        .collect();
+    // Recorded in the execution report
+    let acknowledged: Vec<String> = selected
+        .iter()
+        .filter(|t| t.risk == tweaks::Risk::High)
+        .map(|t| t.key.clone())
+        .collect();
+    let snapshot_id = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let snapshot_path = snapshots_dir()?.join(format!("{}.json", snapshot_id));
+    let mut prelude = snapshot::capture_script(
+        &cfg_value,
+        section.as_deref(),
+        skip.as_deref().unwrap_or_default(),
+        &snapshot_id,
+        profile.as_deref(),
+        &snapshot_path.to_string_lossy(),
+    );
+    if preflight.unwrap_or(true) {
+        prelude.push_str(&preflight::script(&selected, profile.as_deref(), &backup_dir()?.to_string_lossy()));
+    }
+
+    let script = generate_script(config, section, skip, account, acknowledge).await?;
 
-    // Wrap script with timing/statistics that writes a report JSON
-    let report_path = std::env::temp_dir().join("aimcamp_script_report.json");
-    let report_path_str = report_path.to_string_lossy().to_string().replace('\\', "\\\\");
+    let wrapped = report_wrapper(&prelude, &script, Some(&snapshot_id), &acknowledged);
+    run_ps1_elevated(&wrapped, "aimcamp_run.ps1")?;
+    Ok("Script launched as Administrator from current config".into())
+}
+
+/// Wrap an elevated run so it leaves a report for get_script_report.
+/// Every ps1_cmd step appends a JSON line to the steps log; the wrapper
+/// only records timing, errors that escape a step, the pre-flight outcome
+/// ($_pf, when `prelude` ran it) and the acknowledged high-risk tweaks.
+fn report_wrapper(prelude: &str, body: &str, snapshot_id: Option<&str>, acknowledged: &[String]) -> String {
+    let report_path = std::env::temp_dir().join(report::REPORT_FILE);
+    let steps_path = std::env::temp_dir().join(report::STEPS_FILE);
+    for stale in [&report_path, &steps_path] {
+        let _ = std::fs::remove_file(stale);
+    }
+    let acknowledged: Vec<String> = acknowledged.iter().map(|k| ps::quote(k)).collect();
 
-    let wrapped = format!(
+    format!(
         r#"$_aimcamp_start = Get-Date
 $_aimcamp_errors = @()
-$_aimcamp_sections = @()
-$_aimcamp_cmds = 0
+$_aimStepLog = {}
+$_pf = $null
 
-# Override Write-Host to count sections/commands
-$_origEAP = $ErrorActionPreference
-
-# Trap errors
+# Trap errors outside of steps
 trap {{
     $_aimcamp_errors += $_.Exception.Message
     continue
 }}
-
+{}
 {}
 
 $_aimcamp_end = Get-Date
 $_aimcamp_duration = ($_aimcamp_end - $_aimcamp_start).TotalSeconds
 
-# Count sections and commands from script content
-$_scriptLines = @'
-{}
-'@
-$_aimcamp_sections = ([regex]::Matches($_scriptLines, 'SECTION \d+') | ForEach-Object {{ $_.Value }})
-$_aimcamp_cmds = ([regex]::Matches($_scriptLines, '\[ OK \]')).Count
-
 $_report = @{{
-    status        = if ($_aimcamp_errors.Count -eq 0) {{ 'success' }} else {{ 'partial' }}
     duration_secs = [math]::Round($_aimcamp_duration, 1)
-    sections_run  = $_aimcamp_sections.Count
-    commands_run  = $_aimcamp_cmds
     errors        = @($_aimcamp_errors)
     start_time    = $_aimcamp_start.ToString('o')
     end_time      = $_aimcamp_end.ToString('o')
+    snapshot_id   = {}
+    acknowledged  = @({})
+    preflight     = $_pf
 }}
-$_report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
+$_report | ConvertTo-Json -Depth 4 | Set-Content -Path {} -Encoding UTF8
 "#,
-        script,
-        script.replace('\'', "''"),
-        report_path_str
-    );
+        ps::quote(&steps_path.to_string_lossy()),
+        prelude,
+        body,
+        snapshot_id.map_or("$null".to_string(), ps::quote),
+        acknowledged.join(", "),
+        ps::quote(&report_path.to_string_lossy())
+    )
+}
 
-    let temp_dir = std::env::temp_dir();
-    let script_path = temp_dir.join("aimcamp_run.ps1");
+/// Write a script to temp (UTF-8 BOM) and launch it through a UAC prompt.
+fn run_ps1_elevated(content: &str, file_name: &str) -> Result<(), String> {
+    let script_path = std::env::temp_dir().join(file_name);
 
     // Write with UTF-8 BOM
     let bom: [u8; 3] = [0xEF, 0xBB, 0xBF];
@@ -1539,25 +1738,153 @@ $_report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
         .map_err(|e| format!("Failed to write temp script: {}", e))?;
     file.write_all(&bom)
         .map_err(|e| format!("BOM write error: {}", e))?;
-    file.write_all(wrapped.as_bytes())
+    file.write_all(content.as_bytes())
         .map_err(|e| format!("Script write error: {}", e))?;
 
     let path_str = script_path.to_string_lossy().to_string();
-    let result = Command::new("powershell")
-        .args(&[
+    Command::new("powershell")
+        .args([
             "-NoProfile",
             "-Command",
             &format!(
-                "Start-Process powershell -Verb RunAs -ArgumentList '-NoProfile -ExecutionPolicy Bypass -File \"{}\"'",
-                path_str.replace('\'', "''")
+                "Start-Process powershell -Verb RunAs -ArgumentList {}",
+                ps::quote(&format!("-NoProfile -ExecutionPolicy Bypass -File \"{}\"", path_str))
             ),
         ])
-        .spawn();
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to launch: {}", e))
+}
 
-    match result {
-        Ok(_) => Ok("Script launched as Administrator from current config".into()),
-        Err(e) => Err(format!("Failed to launch: {}", e)),
+/// Dated folder for the pre-flight .reg exports of one run.
+fn backup_dir() -> Result<std::path::PathBuf, String> {
+    let mut dir = dirs_next::data_local_dir()
+        .ok_or_else(|| "Cannot find local data dir".to_string())?;
+    dir.push("aimcamp-player-agent");
+    dir.push("backups");
+    dir.push(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+    Ok(dir)
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Snapshots — list and restore pre-apply system state
+// ────────────────────────────────────────────────────────────────────
+fn snapshots_dir() -> Result<std::path::PathBuf, String> {
+    let mut dir = dirs_next::data_local_dir()
+        .ok_or_else(|| "Cannot find local data dir".to_string())?;
+    dir.push("aimcamp-player-agent");
+    dir.push("snapshots");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Dir create failed: {}", e))?;
+    Ok(dir)
+}
+
+#[tauri::command]
+async fn list_snapshots() -> Result<Vec<snapshot::SnapshotInfo>, String> {
+    snapshot::list(&snapshots_dir()?)
+}
+
+#[tauri::command]
+async fn restore_snapshot(id: String) -> Result<String, String> {
+    let snap = snapshot::load(&snapshots_dir()?, &id)?;
+    let plan = snapshot::restore_plan(&snap);
+    if plan.is_empty() {
+        return Err(format!("Snapshot {} has nothing to restore", id));
+    }
+
+    let mut s = String::with_capacity(16_000);
+    ps1_header(&mut s, "", "");
+    ps1_section(&mut s, 1, &format!("Restore snapshot {}", snap.id), "RST");
+    if let Some(profile) = &snap.profile {
+        ps1_info(&mut s, &format!("Profile applied at the time: {}", profile));
+    }
+    for (key, name, lines) in &plan {
+        let cmds: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+        ps1_cmd(&mut s, key, &format!("Restore: {}", name), &cmds);
     }
+    ps1_footer_with(&mut s, "Snapshot restored", "Check CS2 launch options in Steam.");
+
+    run_ps1_elevated(&s, "aimcamp_restore.ps1")?;
+    Ok(format!("Restoring snapshot {} as Administrator ({} tweaks)", snap.id, plan.len()))
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Profiles — named configs with revision history, stored on disk
+// ────────────────────────────────────────────────────────────────────
+fn profile_store() -> Result<profiles::Store, String> {
+    let mut dir = dirs_next::data_local_dir()
+        .ok_or_else(|| "Cannot find local data dir".to_string())?;
+    dir.push("aimcamp-player-agent");
+    dir.push("profiles");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Dir create failed: {}", e))?;
+    Ok(profiles::Store::new(dir))
+}
+
+#[derive(serde::Serialize)]
+struct LoadedProfile {
+    name: String,
+    revision: u32,
+    saved: String,
+    config: OptimizationConfig,
+    ui: Option<serde_json::Value>,
+}
+
+#[tauri::command]
+async fn save_profile(
+    name: String,
+    config: OptimizationConfig,
+    ui: Option<serde_json::Value>,
+) -> Result<profiles::ProfileInfo, String> {
+    let value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    profile_store()?.save(&name, value, ui)
+}
+
+#[tauri::command]
+async fn list_profiles() -> Result<Vec<profiles::ProfileInfo>, String> {
+    Ok(profile_store()?.list())
+}
+
+#[tauri::command]
+async fn list_profile_revisions(name: String) -> Result<Vec<profiles::RevisionInfo>, String> {
+    Ok(profile_store()?.find(&name)?.revision_list())
+}
+
+#[tauri::command]
+async fn load_profile(name: String, revision: Option<u32>) -> Result<LoadedProfile, String> {
+    let profile = profile_store()?.find(&name)?;
+    let rev = profile.revision(revision)?;
+    let config: OptimizationConfig = serde_json::from_value(rev.config.clone())
+        .map_err(|e| format!("Profile '{}' revision {}: {}", profile.name, rev.revision, e))?;
+    Ok(LoadedProfile {
+        name: profile.name.clone(),
+        revision: rev.revision,
+        saved: rev.saved.clone(),
+        config,
+        ui: rev.ui.clone(),
+    })
+}
+
+#[tauri::command]
+async fn rename_profile(name: String, new_name: String) -> Result<profiles::ProfileInfo, String> {
+    profile_store()?.rename(&name, &new_name)
+}
+
+#[tauri::command]
+async fn delete_profile(name: String) -> Result<(), String> {
+    profile_store()?.delete(&name)
+}
+
+#[tauri::command]
+async fn duplicate_profile(name: String, new_name: String) -> Result<profiles::ProfileInfo, String> {
+    profile_store()?.duplicate(&name, &new_name)
+}
+
+#[tauri::command]
+async fn diff_profiles(
+    a: profiles::ProfileRef,
+    b: profiles::ProfileRef,
+) -> Result<Vec<profiles::ProfileChange>, String> {
+    profile_store()?.diff(&a, &b)
 }
 
 // ────────────────────────────────────────────────────────────────────
@@ -1839,13 +2166,14 @@ try {
 // Install a specific driver update via Windows Update API (as admin)
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
-async fn install_driver_update(update_id: String) -> Result<serde_json::Value, String> {
+async fn install_driver_update(update_id: String) -> Result<serde_json::Value, ps::CommandError> {
+    let update_id = ps::quote(&ps::guid("update_id", &update_id)?);
     let report_path = std::env::temp_dir().join("aimcamp_driver_install_report.json");
-    let report_path_str = report_path.to_string_lossy().to_string();
+    let report_path_str = ps::quote(&report_path.to_string_lossy());
 
     let script = format!(r#"
 $ErrorActionPreference = 'Stop'
-$report = @{{ update_id = '{}'; status = 'starting'; steps = @(); error = $null; start_time = (Get-Date).ToString('o') }}
+$report = @{{ update_id = {}; status = 'starting'; steps = @(); error = $null; start_time = (Get-Date).ToString('o') }}
 
 try {{
     $session = New-Object -ComObject Microsoft.Update.Session
@@ -1857,13 +2185,13 @@ try {{
     $result = $searcher.Search("IsInstalled=0 AND Type='Driver'")
     $target = $null
     foreach ($u in $result.Updates) {{
-        if ($u.Identity.UpdateID -eq '{}') {{ $target = $u; break }}
+        if ($u.Identity.UpdateID -eq {}) {{ $target = $u; break }}
     }}
 
     if (-not $target) {{
         $report.status = 'not_found'
         $report.error = 'Update not found. It may have already been installed.'
-        $report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
+        $report | ConvertTo-Json -Depth 3 | Set-Content -Path {} -Encoding UTF8
         exit 1
     }}
 
@@ -1875,7 +2203,7 @@ try {{
 
     # Download
     $report.status = 'downloading'
-    $report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
+    $report | ConvertTo-Json -Depth 3 | Set-Content -Path {} -Encoding UTF8
     $dl = New-Object -ComObject Microsoft.Update.UpdateColl
     $dl.Add($target) | Out-Null
     $downloader = $session.CreateUpdateDownloader()
@@ -1885,7 +2213,7 @@ try {{
 
     # Install
     $report.status = 'installing'
-    $report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
+    $report | ConvertTo-Json -Depth 3 | Set-Content -Path {} -Encoding UTF8
     $inst = New-Object -ComObject Microsoft.Update.UpdateColl
     $inst.Add($target) | Out-Null
     $installer = $session.CreateUpdateInstaller()
@@ -1907,7 +2235,7 @@ try {{
 }}
 
 $report.end_time = (Get-Date).ToString('o')
-$report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
+$report | ConvertTo-Json -Depth 3 | Set-Content -Path {} -Encoding UTF8
 "#, update_id, update_id, report_path_str, report_path_str, report_path_str, report_path_str);
 
     let temp_dir = std::env::temp_dir();
@@ -1925,8 +2253,8 @@ $report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
             "-NoProfile",
             "-Command",
             &format!(
-                "Start-Process powershell -Verb RunAs -ArgumentList '-NoProfile -ExecutionPolicy Bypass -File \"{}\"' -Wait",
-                path_str.replace('\'', "''")
+                "Start-Process powershell -Verb RunAs -ArgumentList {} -Wait",
+                ps::quote(&format!("-NoProfile -ExecutionPolicy Bypass -File \"{}\"", path_str))
             ),
         ])
         .output()
@@ -1939,7 +2267,7 @@ $report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
         let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
         let _ = std::fs::remove_file(&report_path);
         serde_json::from_str(content.trim())
-            .map_err(|e| format!("Report parse error: {}", e))
+            .map_err(|e| format!("Report parse error: {}", e).into())
     } else {
         Ok(serde_json::json!({ "status": "unknown", "error": "No report file generated. The UAC prompt may have been declined." }))
     }
@@ -1949,16 +2277,9 @@ $report | ConvertTo-Json -Depth 3 | Set-Content -Path '{}' -Encoding UTF8
 // Get script execution report
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
-async fn get_script_report() -> Result<serde_json::Value, String> {
-    let report_path = std::env::temp_dir().join("aimcamp_script_report.json");
-    if !report_path.exists() {
-        return Ok(serde_json::json!({ "status": "no_report" }));
-    }
-    let content = std::fs::read_to_string(&report_path)
-        .map_err(|e| format!("Failed to read report: {}", e))?;
-    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
-    serde_json::from_str(content.trim())
-        .map_err(|e| format!("Report parse error: {}", e))
+async fn get_script_report() -> Result<report::ScriptReport, String> {
+    let dir = std::env::temp_dir();
+    report::load(&dir.join(report::REPORT_FILE), &dir.join(report::STEPS_FILE))
 }
 
 // ────────────────────────────────────────────────────────────────────
@@ -2227,7 +2548,7 @@ async fn send_feedback_discord_with_image(
 }
 
 // ────────────────────────────────────────────────────────────────────
-// Benchmark / Frame‑time Analysis — parse PresentMon CSV & CapFrameX JSON
+// Benchmark / Frame‑time Analysis — metrics over capture::Capture (see capture.rs)
 // ────────────────────────────────────────────────────────────────────
 
 #[derive(serde::Serialize)]
@@ -2252,21 +2573,20 @@ struct BenchmarkResult {
     stutter_count: usize,
     stutter_pct: f64,
     dropped_frames: usize,
+    /// Tool the capture came from
+    format: capture::Format,
     frametimes: Vec<f64>,
     timestamps: Vec<f64>,
     fps_values: Vec<f64>,
+    /// Extra per-frame series, downsampled like `frametimes`
+    series: capture::Series,
+    /// CPU/GPU limit, latency and present modes over all frames
+    analysis: bottleneck::Analysis,
 }
 
-fn percentile(sorted: &[f64], p: f64) -> f64 {
-    if sorted.is_empty() { return 0.0; }
-    let idx = (p / 100.0 * (sorted.len() as f64 - 1.0)).max(0.0);
-    let lo = idx.floor() as usize;
-    let hi = (lo + 1).min(sorted.len() - 1);
-    let frac = idx - lo as f64;
-    sorted[lo] * (1.0 - frac) + sorted[hi] * frac
-}
-
-fn calc_metrics(frametimes: &[f64], timestamps: &[f64], dropped: usize, process: &str, file_name: &str) -> BenchmarkResult {
+fn calc_metrics(c: &capture::Capture) -> BenchmarkResult {
+    let (frametimes, timestamps, dropped) = (&c.frametimes, &c.timestamps, c.dropped);
+    let (process, file_name) = (&c.process, &c.file_name);
     let n = frametimes.len();
     if n == 0 {
         return BenchmarkResult {
@@ -2274,8 +2594,9 @@ fn calc_metrics(frametimes: &[f64], timestamps: &[f64], dropped: usize, process:
             duration_secs: 0.0, frame_count: 0, avg_fps: 0.0, min_fps: 0.0, max_fps: 0.0,
             p01_fps: 0.0, p1_fps: 0.0, p5_fps: 0.0, median_fps: 0.0, p95_fps: 0.0, p99_fps: 0.0,
             avg_frametime: 0.0, p95_frametime: 0.0, p99_frametime: 0.0, p999_frametime: 0.0,
-            stutter_count: 0, stutter_pct: 0.0, dropped_frames: dropped,
-            frametimes: vec![], timestamps: vec![], fps_values: vec![],
+            stutter_count: 0, stutter_pct: 0.0, dropped_frames: dropped, format: c.format,
+            frametimes: vec![], timestamps: vec![], fps_values: vec![], series: capture::Series::default(),
+            analysis: bottleneck::analyze(c),
         };
     }
 
@@ -2295,23 +2616,21 @@ fn calc_metrics(frametimes: &[f64], timestamps: &[f64], dropped: usize, process:
     let stutter_count = frametimes.iter().filter(|&&ft| ft > stutter_threshold).count();
 
     // Downsample for frontend if > 5000 points
-    let (ds_ft, ds_ts, ds_fps) = if n > 5000 {
+    let indices: Vec<usize> = if n > 5000 {
         let step = n as f64 / 5000.0;
-        let mut dft = Vec::with_capacity(5000);
-        let mut dts = Vec::with_capacity(5000);
-        let mut dfps = Vec::with_capacity(5000);
+        let mut idx = Vec::with_capacity(5000);
         let mut i = 0.0;
         while (i as usize) < n {
-            let idx = i as usize;
-            dft.push(frametimes[idx]);
-            dts.push(timestamps[idx]);
-            dfps.push(fps_vals[idx]);
+            idx.push(i as usize);
             i += step;
         }
-        (dft, dts, dfps)
+        idx
     } else {
-        (frametimes.to_vec(), timestamps.to_vec(), fps_vals.clone())
+        (0..n).collect()
     };
+    let ds_ft: Vec<f64> = indices.iter().map(|&i| frametimes[i]).collect();
+    let ds_ts: Vec<f64> = indices.iter().map(|&i| timestamps[i]).collect();
+    let ds_fps: Vec<f64> = indices.iter().map(|&i| fps_vals[i]).collect();
 
     BenchmarkResult {
         file_name: file_name.to_string(),
@@ -2334,9 +2653,12 @@ fn calc_metrics(frametimes: &[f64], timestamps: &[f64], dropped: usize, process:
         stutter_count,
         stutter_pct: stutter_count as f64 / n as f64 * 100.0,
         dropped_frames: dropped,
+        format: c.format,
         frametimes: ds_ft,
         timestamps: ds_ts,
         fps_values: ds_fps,
+        series: c.series.pick(&indices),
+        analysis: bottleneck::analyze(c),
     }
 }
 
@@ -2345,9 +2667,9 @@ async fn pick_benchmark_file() -> Result<String, String> {
     use tauri::api::dialog::blocking::FileDialogBuilder;
     FileDialogBuilder::new()
         .add_filter("Benchmark Files", &["csv", "json"])
-        .add_filter("PresentMon CSV", &["csv"])
+        .add_filter("PresentMon / FrameView / OCAT / MangoHud CSV", &["csv"])
         .add_filter("CapFrameX JSON", &["json"])
-        .set_title("Selecionar ficheiro de benchmark (PresentMon CSV ou CapFrameX JSON)")
+        .set_title("Selecionar ficheiro de benchmark (PresentMon, FrameView, OCAT, MangoHud ou CapFrameX)")
         .pick_file()
         .map(|p| p.to_string_lossy().to_string())
         .ok_or_else(|| "Cancelled".to_string())
@@ -2355,165 +2677,32 @@ async fn pick_benchmark_file() -> Result<String, String> {
 
 #[tauri::command]
 async fn parse_benchmark_file(path: String) -> Result<BenchmarkResult, String> {
-    let file_name = std::path::Path::new(&path)
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
-
-    if path.to_lowercase().ends_with(".csv") {
-        parse_presentmon_csv(&path, &file_name)
-    } else if path.to_lowercase().ends_with(".json") {
-        parse_capframex_json(&path, &file_name)
-    } else {
-        Err("Formato não suportado. Usa ficheiros .csv (PresentMon) ou .json (CapFrameX).".into())
-    }
+    Ok(calc_metrics(&capture::read(&path)?))
 }
 
-fn parse_presentmon_csv(path: &str, file_name: &str) -> Result<BenchmarkResult, String> {
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| format!("Erro ao ler ficheiro: {}", e))?;
-    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
-    let mut lines = content.lines();
-
-    // Parse header to find column indices
-    let header = lines.next().ok_or("Ficheiro CSV vazio")?;
-    let cols: Vec<&str> = header.split(',').collect();
-
-    let find_col = |names: &[&str]| -> Option<usize> {
-        for name in names {
-            if let Some(i) = cols.iter().position(|c| c.trim().eq_ignore_ascii_case(name)) {
-                return Some(i);
-            }
-        }
-        None
-    };
-
-    let col_app = find_col(&["Application", "application"]);
-    let col_time = find_col(&["TimeInSeconds", "timeinseconds"]);
-    let col_ft = find_col(&["MsBetweenPresents", "msbetweenpresents", "FrameTime"]);
-    let col_dropped = find_col(&["Dropped", "dropped"]);
-
-    let col_ft = col_ft.ok_or("Coluna 'MsBetweenPresents' ou 'FrameTime' não encontrada no CSV")?;
-
-    let mut frametimes: Vec<f64> = Vec::new();
-    let mut timestamps: Vec<f64> = Vec::new();
-    let mut process = String::new();
-    let mut dropped_count = 0usize;
-
-    for line in lines {
-        let fields: Vec<&str> = line.split(',').collect();
-        if fields.len() <= col_ft { continue; }
-
-        let ft: f64 = match fields[col_ft].trim().parse() {
-            Ok(v) if v > 0.0 => v,
-            _ => continue,
+/// Compare baseline and candidate captures (several runs per side are
+/// pooled). The bootstrap runs on a blocking thread.
+#[tauri::command]
+async fn compare_benchmarks(baseline: Vec<String>, candidate: Vec<String>) -> Result<benchcmp::Comparison, String> {
+    tokio::task::spawn_blocking(move || {
+        let runs = |paths: &[String]| -> Result<Vec<benchcmp::Run>, String> {
+            paths
+                .iter()
+                .map(|p| {
+                    let c = capture::read(p)?;
+                    Ok(benchcmp::Run {
+                        duration_secs: c.duration_secs(),
+                        file_name: c.file_name,
+                        process_name: c.process,
+                        frametimes: c.frametimes,
+                    })
+                })
+                .collect()
         };
-
-        let ts = col_time
-            .and_then(|i| fields.get(i))
-            .and_then(|v| v.trim().parse::<f64>().ok())
-            .unwrap_or_else(|| if timestamps.is_empty() { 0.0 } else { timestamps.last().unwrap() + ft / 1000.0 });
-
-        if process.is_empty() {
-            if let Some(i) = col_app {
-                if let Some(v) = fields.get(i) {
-                    let v = v.trim();
-                    if !v.is_empty() && v != "<unknown>" { process = v.to_string(); }
-                }
-            }
-        }
-
-        if let Some(i) = col_dropped {
-            if let Some(v) = fields.get(i) {
-                if v.trim() == "1" || v.trim().eq_ignore_ascii_case("true") {
-                    dropped_count += 1;
-                }
-            }
-        }
-
-        frametimes.push(ft);
-        timestamps.push(ts);
-    }
-
-    if frametimes.is_empty() {
-        return Err("Nenhum frame válido encontrado no ficheiro CSV".into());
-    }
-
-    if process.is_empty() { process = "Unknown".into(); }
-    Ok(calc_metrics(&frametimes, &timestamps, dropped_count, &process, file_name))
-}
-
-fn parse_capframex_json(path: &str, file_name: &str) -> Result<BenchmarkResult, String> {
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| format!("Erro ao ler ficheiro: {}", e))?;
-    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
-    let json: serde_json::Value = serde_json::from_str(content)
-        .map_err(|e| format!("JSON inválido: {}", e))?;
-
-    // CapFrameX JSON format: { "Runs": [ { "CaptureData": { "MsBetweenPresents": [...], "Dropped": [...] } } ], "Info": { "ProcessName": "..." } }
-    // Each Run is a SessionRun object with CaptureData sub-object
-    let (frametimes, dropped) = if let Some(runs) = json.get("Runs").and_then(|r| r.as_array()) {
-        // Try as array of SessionRun objects (official CapFrameX format)
-        let mut ft: Vec<f64> = Vec::new();
-        let mut dr: usize = 0;
-        let mut found_obj = false;
-        for run in runs {
-            if let Some(capture) = run.get("CaptureData") {
-                found_obj = true;
-                if let Some(ms) = capture.get("MsBetweenPresents").and_then(|m| m.as_array()) {
-                    ft.extend(ms.iter().filter_map(|v| v.as_f64()).filter(|&v| v > 0.0));
-                }
-                if let Some(dropped_arr) = capture.get("Dropped").and_then(|d| d.as_array()) {
-                    dr += dropped_arr.iter().filter(|v| v.as_bool().unwrap_or(false) || v.as_i64().unwrap_or(0) == 1).count();
-                }
-            }
-        }
-        if !found_obj {
-            // Fallback: maybe Runs is array of arrays of raw frame times
-            ft = runs.iter()
-                .filter_map(|r| r.as_array())
-                .flatten()
-                .filter_map(|v| v.as_f64())
-                .filter(|&v| v > 0.0)
-                .collect();
-        }
-        (ft, dr)
-    } else if let Some(data) = json.get("CaptureData").and_then(|d| d.get("MsBetweenPresents")).and_then(|m| m.as_array()) {
-        let ft: Vec<f64> = data.iter().filter_map(|v| v.as_f64()).filter(|&v| v > 0.0).collect();
-        let dr = json.get("CaptureData")
-            .and_then(|d| d.get("Dropped"))
-            .and_then(|d| d.as_array())
-            .map(|arr| arr.iter().filter(|v| v.as_bool().unwrap_or(false) || v.as_i64().unwrap_or(0) == 1).count())
-            .unwrap_or(0);
-        (ft, dr)
-    } else if let Some(data) = json.get("MsBetweenPresents").and_then(|m| m.as_array()) {
-        let ft: Vec<f64> = data.iter().filter_map(|v| v.as_f64()).filter(|&v| v > 0.0).collect();
-        (ft, 0)
-    } else {
-        return Err("Formato CapFrameX não reconhecido: não encontrou frame times".into());
-    };
-
-    if frametimes.is_empty() {
-        return Err("Nenhum frame válido encontrado no CapFrameX JSON".into());
-    }
-
-    // Build timestamps from cumulative frame times
-    let mut timestamps = Vec::with_capacity(frametimes.len());
-    let mut t = 0.0;
-    for &ft in &frametimes {
-        timestamps.push(t);
-        t += ft / 1000.0;
-    }
-
-    let process = json.get("Info")
-        .and_then(|i| i.get("ProcessName"))
-        .and_then(|p| p.as_str())
-        .or_else(|| json.get("ProcessName").and_then(|p| p.as_str()))
-        .unwrap_or("Unknown")
-        .to_string();
-
-    Ok(calc_metrics(&frametimes, &timestamps, dropped, &process, file_name))
+        benchcmp::compare(&runs(&baseline)?, &runs(&candidate)?)
+    })
+    .await
+    .map_err(|e| format!("Comparison failed: {}", e))?
 }
 
 #[tauri::command]
@@ -2545,6 +2734,138 @@ async fn scan_capframex_folder() -> Result<Vec<String>, String> {
     Ok(files)
 }
 
+// ────────────────────────────────────────────────────────────────────
+// Tweak bisection — one script + capture per step, session on disk
+// ────────────────────────────────────────────────────────────────────
+
+fn bisection_path() -> Result<std::path::PathBuf, String> {
+    let mut dir = dirs_next::data_local_dir()
+        .ok_or_else(|| "Cannot find local data dir".to_string())?;
+    dir.push("aimcamp-player-agent");
+    dir.push("bisection.json");
+    Ok(dir)
+}
+
+fn load_bisection() -> Result<bisect::Session, String> {
+    bisect::Session::load(&bisection_path()?)?.ok_or_else(|| "No bisection in progress".to_string())
+}
+
+/// Script for the pending step: reverts the candidates outside the group
+/// under test, then applies the group, section by section.
+fn bisection_script(session: &bisect::Session, acknowledge: Option<Vec<String>>) -> Result<String, ps::CommandError> {
+    let step = session.next.as_ref().ok_or_else(|| "The bisection is already finished".to_string())?;
+    let catalog = tweaks::catalog();
+    let apply: Vec<&tweaks::Tweak> = step.apply.iter().filter_map(|k| catalog.find(k)).collect();
+    require_acknowledged(apply.iter().copied(), acknowledge)?;
+    let revert = session.reverted();
+
+    let mut s = String::with_capacity(16_000);
+    s.push_str(&script_config::header(&session.step_config(&step.apply), None));
+    let theme = |key: &str| session.config.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    ps1_header(&mut s, &theme("theme_primary"), &theme("theme_secondary"));
+    for sec in &catalog.sections {
+        let reverts: Vec<&tweaks::Tweak> = catalog.section_tweaks(&sec.id).filter(|t| revert.contains(&t.key.as_str())).collect();
+        let applies: Vec<&tweaks::Tweak> = catalog.section_tweaks(&sec.id).filter(|t| step.apply.contains(&t.key)).collect();
+        if reverts.is_empty() && applies.is_empty() {
+            continue;
+        }
+        ps1_section(&mut s, sec.num, &sec.title, &sec.icon);
+        ps1_preamble(&mut s, sec);
+        for tweak in reverts {
+            ps1_revert_tweak(&mut s, tweak);
+        }
+        for tweak in applies {
+            ps1_tweak(&mut s, tweak);
+        }
+    }
+    let headline = format!("Bisection step {} ready", step.number);
+    let hint = if step.restart { "Restart Windows, then capture a benchmark." } else { "Capture a benchmark in CS2 now." };
+    ps1_footer_with(&mut s, &headline, hint);
+    Ok(s)
+}
+
+/// Start a new session. One still in progress is only replaced when
+/// `overwrite` is set.
+#[tauri::command]
+async fn start_bisection(
+    config: OptimizationConfig,
+    profile: Option<String>,
+    baseline_runs: Option<usize>,
+    overwrite: Option<bool>,
+) -> Result<bisect::Session, ps::CommandError> {
+    config.validate(None)?;
+    let path = bisection_path()?;
+    if !overwrite.unwrap_or(false) {
+        if let Some(step) = bisect::Session::load(&path).ok().flatten().and_then(|s| s.next) {
+            return Err(format!("A bisection is already in progress (step {})", step.number).into());
+        }
+    }
+    let cfg_value = serde_json::to_value(&config)
+        .map_err(|e| format!("Config serialize error: {}", e))?;
+    let session = bisect::Session::start(cfg_value, profile, baseline_runs.unwrap_or(2))?;
+    session.save(&path)?;
+    Ok(session)
+}
+
+#[tauri::command]
+async fn get_bisection() -> Result<Option<bisect::Session>, String> {
+    bisect::Session::load(&bisection_path()?)
+}
+
+#[tauri::command]
+async fn export_bisection_script(acknowledge: Option<Vec<String>>) -> Result<String, ps::CommandError> {
+    bisection_script(&load_bisection()?, acknowledge)
+}
+
+#[tauri::command]
+async fn run_bisection_step(acknowledge: Option<Vec<String>>, preflight: Option<bool>) -> Result<String, ps::CommandError> {
+    let session = load_bisection()?;
+    let script = bisection_script(&session, acknowledge)?;
+    let step = session.next.as_ref().ok_or_else(|| "The bisection is already finished".to_string())?;
+
+    let catalog = tweaks::catalog();
+    let prelude = if preflight.unwrap_or(true) {
+        let touched: Vec<&tweaks::Tweak> = session.candidates.iter().filter_map(|k| catalog.find(k)).collect();
+        let profile = format!("bisection step {}", step.number);
+        preflight::script(&touched, Some(&profile), &backup_dir()?.to_string_lossy())
+    } else {
+        String::new()
+    };
+    let acknowledged: Vec<String> = step
+        .apply
+        .iter()
+        .filter(|k| catalog.find(k).is_some_and(|t| t.risk == tweaks::Risk::High))
+        .cloned()
+        .collect();
+    run_ps1_elevated(&report_wrapper(&prelude, &script, None, &acknowledged), "aimcamp_bisect.ps1")?;
+    Ok(format!("Bisection step {} launched as Administrator", step.number))
+}
+
+/// Feed the capture taken after the pending step into the session.
+#[tauri::command]
+async fn record_bisection_capture(path: String) -> Result<bisect::Session, String> {
+    let mut session = load_bisection()?;
+    let result = parse_benchmark_file(path).await?;
+    session.record(bisect::Sample {
+        file_name: result.file_name,
+        avg_fps: result.avg_fps,
+        p1_fps: result.p1_fps,
+        frame_count: result.frame_count,
+        duration_secs: result.duration_secs,
+    })?;
+    session.save(&bisection_path()?)?;
+    Ok(session)
+}
+
+#[tauri::command]
+async fn cancel_bisection() -> Result<(), String> {
+    let path = bisection_path()?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove bisection session: {}", e))?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 async fn check_presentmon() -> Result<serde_json::Value, String> {
     // Check common PresentMon locations
@@ -2717,14 +3038,41 @@ fn main() {
     tauri::Builder::default()
         .invoke_handler(tauri::generate_handler![
             generate_script,
+            generate_revert_script,
+            list_snapshots,
+            restore_snapshot,
+            save_profile,
+            list_profiles,
+            list_profile_revisions,
+            load_profile,
+            rename_profile,
+            delete_profile,
+            duplicate_profile,
+            diff_profiles,
             save_script,
             import_script,
+            import_script_config,
             run_script_as_admin,
             pick_ps1_file,
             check_system_state,
+            plan_changes,
+            check_competitive_compat,
+            import_compat_rules,
             run_config_as_admin,
             save_cfg,
             load_cfg,
+            parse_cfg,
+            lint_cfg,
+            import_cvarlist,
+            import_autoexec,
+            merge_autoexec,
+            list_steam_accounts,
+            read_video_settings,
+            list_video_presets,
+            write_video_settings,
+            apply_launch_options,
+            decode_crosshair,
+            encode_crosshair,
             get_hardware_info,
             get_driver_info,
             check_driver_updates,
@@ -2748,6 +3096,8 @@ fn main() {
             restore_network_defaults,
             scan_demos,
             parse_demo_header,
+            decode_match_code,
+            download_match_demo,
             open_demo_in_cs2,
             pick_demo_folder,
             advisor_chat,
@@ -2760,7 +3110,14 @@ fn main() {
             send_feedback_discord_with_image,
             pick_benchmark_file,
             parse_benchmark_file,
+            compare_benchmarks,
             scan_capframex_folder,
+            start_bisection,
+            get_bisection,
+            export_bisection_script,
+            run_bisection_step,
+            record_bisection_capture,
+            cancel_bisection,
             check_presentmon,
             check_for_update,
             download_update,
@@ -2798,29 +3155,9 @@ async fn scan_demos(folder: String) -> Result<serde_json::Value, String> {
     use std::fs;
     let entries = fs::read_dir(&folder).map_err(|e| format!("Cannot read folder: {}", e))?;
     let mut demos: Vec<serde_json::Value> = Vec::new();
-    for entry in entries {
-        if let Ok(e) = entry {
-            let path = e.path();
-            if path.extension().map(|x| x == "dem").unwrap_or(false) {
-                let meta = fs::metadata(&path).ok();
-                let size_mb = meta.as_ref().map(|m| m.len() as f64 / 1_048_576.0).unwrap_or(0.0);
-                let modified = meta.as_ref().and_then(|m| m.modified().ok())
-                    .map(|t| {
-                        let dur = t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
-                        dur.as_secs()
-                    }).unwrap_or(0);
-                let fname = path.file_name().unwrap_or_default().to_string_lossy().to_string();
-                // try to extract map name from filename (common pattern: mapname_date_...)
-                let map_hint = fname.split(|c: char| c == '_' || c == '-').next()
-                    .unwrap_or("").to_string();
-                demos.push(serde_json::json!({
-                    "name": fname,
-                    "path": path.to_string_lossy(),
-                    "size_mb": (size_mb * 10.0).round() / 10.0,
-                    "modified": modified,
-                    "map_hint": map_hint,
-                }));
-            }
+    for entry in entries.flatten() {
+        if let Some(demo) = demos::entry(&entry.path()) {
+            demos.push(demo);
         }
     }
     // sort by modified desc
@@ -2832,6 +3169,29 @@ async fn scan_demos(folder: String) -> Result<serde_json::Value, String> {
     Ok(serde_json::json!(demos))
 }
 
+#[tauri::command]
+async fn decode_match_code(code: String) -> Result<demos::MatchInfo, String> {
+    Ok(demos::decode(&code)?.info())
+}
+
+/// Resolve a match share code through the replay endpoint, download the
+/// demo into `folder` and return its index entry.
+#[tauri::command]
+async fn download_match_demo(code: String, folder: String, endpoint: String) -> Result<serde_json::Value, String> {
+    let m = demos::decode(&code)?;
+    if endpoint.trim().is_empty() {
+        return Err("No replay endpoint configured".into());
+    }
+    let client = reqwest::Client::builder()
+        .user_agent("aimcamp-player-agent")
+        .timeout(std::time::Duration::from_secs(300))
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
+    let url = demos::resolve_url(&client, endpoint.trim(), &m).await?;
+    let path = demos::download(&client, &url, std::path::Path::new(&folder), &m.demo_name()).await?;
+    demos::entry(&path).ok_or_else(|| "Downloaded file is not a demo".to_string())
+}
+
 #[tauri::command]
 async fn parse_demo_header(path: String) -> Result<serde_json::Value, String> {
     use std::io::Read;
@@ -2937,9 +3297,15 @@ fn urlencoding(s: &str) -> String {
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
 async fn get_hardware_info() -> Result<serde_json::Value, String> {
-    let script = r#"
+    let script = [tweaks::GPU_BAR_PS1, r#"
 $cpu = Get-CimInstance Win32_Processor | Select-Object -First 1
-$gpu = Get-CimInstance Win32_VideoController | Where-Object { $_.AdapterRAM -gt 0 -or $_.Name -match 'NVIDIA|AMD|Intel' } | Select-Object -First 1
+$gpu = Get-CimInstance Win32_VideoController | Where-Object { $_.AdapterRAM -gt 0 -or $_.Name -match 'NVIDIA|AMD|Intel' } |
+    Sort-Object { if ($_.Name -match 'NVIDIA|GeForce|Radeon RX|Radeon Pro|Arc') { 0 } else { 1 } } | Select-Object -First 1
+$gpuVendor = if (-not $gpu) { 'unknown' }
+    elseif ($gpu.Name -match 'NVIDIA|GeForce' -or $gpu.AdapterCompatibility -match 'NVIDIA') { 'nvidia' }
+    elseif ($gpu.Name -match 'AMD|Radeon' -or $gpu.AdapterCompatibility -match 'Advanced Micro Devices') { 'amd' }
+    elseif ($gpu.Name -match 'Intel' -or $gpu.AdapterCompatibility -match 'Intel') { 'intel' }
+    else { 'unknown' }
 $ramMods = @(Get-CimInstance Win32_PhysicalMemory)
 $totalRam = ($ramMods | Measure-Object Capacity -Sum).Sum
 $os = Get-CimInstance Win32_OperatingSystem
@@ -2947,6 +3313,13 @@ $disks = @(Get-PhysicalDisk -ErrorAction SilentlyContinue | Select-Object -First
 $hags = (Get-ItemProperty -Path 'HKLM:\SYSTEM\CurrentControlSet\Control\GraphicsDrivers' -Name 'HwSchMode' -ErrorAction SilentlyContinue).HwSchMode
 $rebar = 'N/A'
 try { $r = (nvidia-smi --query-gpu=bar1.total --format=csv,noheader,nounits 2>$null); if($r) { $rebar = "$($r.Trim()) MB" } } catch {}
+if ($rebar -eq 'N/A' -and $gpu) {
+    # AMD / Intel: largest memory range assigned to the GPU
+    try {
+        $bar = Get-DeviceBarMb $gpu
+        if ($bar) { $rebar = "$bar MB" }
+    } catch {}
+}
 $xmpHint = if($ramMods.Count -gt 0 -and $ramMods[0].ConfiguredClockSpeed -gt $ramMods[0].Speed) { 'Likely' } elseif($ramMods[0].ConfiguredClockSpeed -ge 3200) { 'Likely' } else { 'Unknown' }
 $mon = $null
 try { $mon = Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorBasicDisplayParams -ErrorAction SilentlyContinue | Select-Object -First 1 } catch {}
@@ -2960,11 +3333,13 @@ $result = @{
     gpu_name = if($gpu) { $gpu.Name.Trim() } else { 'N/A' }
     gpu_vram_mb = if($gpu -and $gpu.AdapterRAM -gt 0) { [math]::Round($gpu.AdapterRAM / 1MB) } else { 0 }
     gpu_driver = if($gpu) { $gpu.DriverVersion } else { 'N/A' }
+    gpu_vendor = $gpuVendor
     ram_total_gb = [math]::Round($totalRam / 1GB, 1)
     ram_modules = $ramMods.Count
     ram_speed_mhz = if($ramMods[0].ConfiguredClockSpeed) { $ramMods[0].ConfiguredClockSpeed } else { 0 }
     os_name = $os.Caption.Trim()
     os_build = $os.BuildNumber
+    os_edition = (Get-ItemProperty 'HKLM:\SOFTWARE\Microsoft\Windows NT\CurrentVersion').EditionID
     disks = @($disks | ForEach-Object { @{ name = $_.FriendlyName; type = [string]$_.MediaType; size_gb = [math]::Round([double]$_.Size / 1GB) } })
     hags = if($hags -eq 2) { 'ON' } elseif($hags -ne $null) { 'OFF' } else { 'N/A' }
     rebar = $rebar
@@ -2972,9 +3347,9 @@ $result = @{
     refresh_rate = $refreshRate
 }
 $result | ConvertTo-Json -Depth 3
-"#;
+"#].concat();
     let output = Command::new("powershell")
-        .args(&["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script])
+        .args(&["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", &script])
         .output()
         .map_err(|e| format!("HW info failed: {}", e))?;
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -3022,10 +3397,11 @@ async fn kill_process(pid: u32) -> Result<String, String> {
 // Ping server — TCP connect latency (ICMP blocked on game servers)
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
-async fn ping_server(host: String, port: u16, count: u32) -> Result<serde_json::Value, String> {
+async fn ping_server(host: String, port: u16, count: u32) -> Result<serde_json::Value, ps::CommandError> {
+    let host = ps::quote(&ps::hostname("host", &host)?);
     let script = format!(r#"
 $times = @()
-$h = '{host}'
+$h = {host}
 $p = {port}
 $count = {count}
 for ($i = 0; $i -lt $count; $i++) {{
@@ -3049,9 +3425,9 @@ if ($times.Count -gt 0) {{
     $min = ($times | Measure-Object -Minimum).Minimum
     $max = ($times | Measure-Object -Maximum).Maximum
     $loss = [math]::Round(($count - $times.Count) * 100 / $count, 0)
-    @{{ host='{host}'; avg=$avg; min=$min; max=$max; loss=$loss; ok=$true }} | ConvertTo-Json
+    @{{ host=$h; avg=$avg; min=$min; max=$max; loss=$loss; ok=$true }} | ConvertTo-Json
 }} else {{
-    @{{ host='{host}'; avg=-1; min=-1; max=-1; loss=100; ok=$false }} | ConvertTo-Json
+    @{{ host=$h; avg=-1; min=-1; max=-1; loss=100; ok=$false }} | ConvertTo-Json
 }}
 "#, host = host, port = port, count = count);
     let output = Command::new("powershell")
@@ -3060,21 +3436,25 @@ if ($times.Count -gt 0) {{
         .map_err(|e| format!("Ping failed: {}", e))?;
     let stdout = String::from_utf8_lossy(&output.stdout);
     serde_json::from_str(stdout.trim())
-        .map_err(|e| format!("Ping JSON parse: {} — raw: {}", e, &stdout[..stdout.len().min(300)]))
+        .map_err(|e| format!("Ping JSON parse: {} — raw: {}", e, &stdout[..stdout.len().min(300)]).into())
 }
 
 // ────────────────────────────────────────────────────────────────────
 // Network Optimization Commands
 // ────────────────────────────────────────────────────────────────────
 #[tauri::command]
-async fn apply_dns(primary: String, secondary: String) -> Result<String, String> {
+async fn apply_dns(primary: String, secondary: String) -> Result<String, ps::CommandError> {
+    let primary = ps::ip("primary", &primary)?;
+    let secondary = ps::ip("secondary", &secondary)?;
+    let servers = ps::quote_array(&[&primary, &secondary]);
+    let status = ps::quote(&format!("DNS set to {}/{} on ", primary, secondary));
     let script = format!(r#"
 $adapter = Get-NetAdapter | Where-Object {{ $_.Status -eq 'Up' -and ($_.InterfaceDescription -notlike '*Virtual*') -and ($_.InterfaceDescription -notlike '*Loopback*') }} | Sort-Object -Property LinkSpeed -Descending | Select-Object -First 1
 if (-not $adapter) {{ throw 'No active network adapter found' }}
 $ifIdx = $adapter.InterfaceIndex
-Set-DnsClientServerAddress -InterfaceIndex $ifIdx -ServerAddresses ('{primary}','{secondary}')
-"DNS set to {primary}/{secondary} on $($adapter.Name)"
-"#, primary = primary, secondary = secondary);
+Set-DnsClientServerAddress -InterfaceIndex $ifIdx -ServerAddresses {servers}
+{status} + $adapter.Name
+"#, servers = servers, status = status);
     let out = Command::new("powershell")
         .args(&["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", &script])
         .output()
@@ -3082,7 +3462,7 @@ Set-DnsClientServerAddress -InterfaceIndex $ifIdx -ServerAddresses ('{primary}',
     if out.status.success() {
         Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
     } else {
-        Err(String::from_utf8_lossy(&out.stderr).trim().to_string())
+        Err(String::from_utf8_lossy(&out.stderr).trim().to_string().into())
     }
 }
 
@@ -0,0 +1,230 @@
+// ────────────────────────────────────────────────────────────────────
+// OptimizationConfig schema versions. Payloads without `schema_version`
+// are version 1; every step in migrations.json upgrades `from` → `from + 1`
+// by renaming, splitting, removing or defaulting fields. Paths are
+// "section.field" (or a top-level field name).
+// ────────────────────────────────────────────────────────────────────
+
+use serde_json::{Map, Value};
+use std::sync::OnceLock;
+
+#[derive(serde::Deserialize)]
+struct Migrations {
+    steps: Vec<Step>,
+}
+
+#[derive(serde::Deserialize)]
+struct Step {
+    from: u32,
+    ops: Vec<Op>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Op {
+    /// Move a value to a new name
+    Rename { from: String, to: String },
+    /// One toggle became several; each new field starts with the old value
+    Split { from: String, into: Vec<String> },
+    Remove { path: String },
+    /// Field added in this version; only set when missing
+    Default { path: String, value: Value },
+}
+
+static MIGRATIONS: OnceLock<Migrations> = OnceLock::new();
+
+fn migrations() -> &'static Migrations {
+    MIGRATIONS.get_or_init(|| {
+        let m: Migrations = serde_json::from_str(include_str!("migrations.json"))
+            .expect("migrations.json is malformed");
+        assert!(
+            m.steps.iter().enumerate().all(|(i, s)| s.from == i as u32 + 1),
+            "migrations.json steps must be contiguous from version 1"
+        );
+        m
+    })
+}
+
+/// Version written by this agent.
+pub fn current_version() -> u32 {
+    migrations().steps.len() as u32 + 1
+}
+
+/// Refuse payloads written by a newer agent.
+pub fn check_version(version: u32) -> Result<(), String> {
+    let current = current_version();
+    if version > current {
+        return Err(format!(
+            "This file was saved by a newer aim.camp agent (config schema v{}, this agent supports up to v{}). Update the agent to load it.",
+            version, current
+        ));
+    }
+    Ok(())
+}
+
+/// Upgrade a serialized OptimizationConfig to the current schema version.
+pub fn upgrade(mut value: Value) -> Result<Value, String> {
+    let root = value
+        .as_object_mut()
+        .ok_or_else(|| "Config must be a JSON object".to_string())?;
+    let version = match root.get("schema_version") {
+        None | Some(Value::Null) => 1,
+        Some(v) => v
+            .as_u64()
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| format!("Invalid schema_version: {}", v))? as u32,
+    };
+    check_version(version)?;
+
+    for step in migrations().steps.iter().filter(|s| s.from >= version) {
+        for op in &step.ops {
+            apply(root, op);
+        }
+    }
+    root.insert("schema_version".into(), Value::from(current_version()));
+    Ok(value)
+}
+
+fn apply(root: &mut Map<String, Value>, op: &Op) {
+    match op {
+        Op::Rename { from, to } => {
+            if let Some(v) = take(root, from) {
+                set(root, to, v);
+            }
+        }
+        Op::Split { from, into } => {
+            if let Some(v) = take(root, from) {
+                for path in into {
+                    set(root, path, v.clone());
+                }
+            }
+        }
+        Op::Remove { path } => {
+            take(root, path);
+        }
+        Op::Default { path, value } => {
+            if get(root, path).is_none() {
+                set(root, path, value.clone());
+            }
+        }
+    }
+}
+
+/// Parent object of `path` (created on demand) and the last segment.
+fn parent<'a>(root: &'a mut Map<String, Value>, path: &'a str) -> Option<(&'a mut Map<String, Value>, &'a str)> {
+    let (section, field) = match path.split_once('.') {
+        Some((s, f)) => (Some(s), f),
+        None => (None, path),
+    };
+    match section {
+        None => Some((root, field)),
+        Some(s) => {
+            let entry = root.entry(s).or_insert_with(|| Value::Object(Map::new()));
+            entry.as_object_mut().map(|m| (m, field))
+        }
+    }
+}
+
+fn get<'a>(root: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    match path.split_once('.') {
+        Some((s, f)) => root.get(s)?.get(f),
+        None => root.get(path),
+    }
+}
+
+fn take(root: &mut Map<String, Value>, path: &str) -> Option<Value> {
+    match path.split_once('.') {
+        Some((s, f)) => root.get_mut(s)?.as_object_mut()?.remove(f),
+        None => root.remove(path),
+    }
+}
+
+fn set(root: &mut Map<String, Value>, path: &str, value: Value) {
+    if let Some((map, field)) = parent(root, path) {
+        map.insert(field.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_are_contiguous() {
+        let steps = &migrations().steps;
+        assert!(!steps.is_empty());
+        for (i, step) in steps.iter().enumerate() {
+            assert_eq!(step.from, i as u32 + 1);
+        }
+        assert_eq!(current_version(), steps.last().unwrap().from + 1);
+    }
+
+    #[test]
+    fn frontend_sends_the_current_version() {
+        let ts = include_str!("../../src/main.ts");
+        let line = ts
+            .lines()
+            .find_map(|l| l.strip_prefix("const CONFIG_SCHEMA_VERSION = "))
+            .expect("CONFIG_SCHEMA_VERSION not found in main.ts");
+        assert_eq!(line.trim_end_matches(';').parse::<u32>().unwrap(), current_version());
+    }
+
+    #[test]
+    fn v1_payload_upgrades_and_loads() {
+        let v1: Value = serde_json::from_str(include_str!("../fixtures/config/v1.json")).unwrap();
+        let up = upgrade(v1.clone()).unwrap();
+        assert_eq!(up["schema_version"], current_version());
+        // Old values kept, later fields defaulted
+        assert_eq!(up["windows"]["ultimate_power_plan"], true);
+        assert_eq!(up["autoexec"]["custom_commands"], "echo hi");
+        assert_eq!(up["windows"]["disable_spectre"], false);
+        assert_eq!(up["intel"]["intel_rebar_check"], false);
+        assert_eq!(up["autoexec"]["crosshair_code"], "");
+        assert_eq!(up["autoexec"]["crosshair_cvars"], serde_json::json!([]));
+        assert_eq!(up["theme_primary"], "");
+
+        // Upgrading is idempotent, and the result deserializes
+        assert_eq!(upgrade(up.clone()).unwrap(), up);
+        let config = crate::OptimizationConfig::try_from(v1).unwrap();
+        assert_eq!(config.schema_version, current_version());
+        assert!(config.windows.ultimate_power_plan && !config.bios.disable_svm);
+        assert_eq!(config.autoexec.fps_max, "0");
+        assert!(config.autoexec.crosshair_cvars.is_empty());
+    }
+
+    #[test]
+    fn existing_values_win_over_defaults() {
+        let v: Value = serde_json::json!({
+            "schema_version": 1,
+            "theme_primary": "#ff0000",
+            "autoexec": { "crosshair_code": "CSGO-xxxxx" },
+        });
+        let up = upgrade(v).unwrap();
+        assert_eq!(up["theme_primary"], "#ff0000");
+        assert_eq!(up["autoexec"]["crosshair_code"], "CSGO-xxxxx");
+    }
+
+    #[test]
+    fn rejects_newer_and_invalid_versions() {
+        let next = current_version() + 1;
+        assert!(check_version(current_version()).is_ok());
+        let e = check_version(next).unwrap_err();
+        assert!(e.contains(&format!("v{}", next)), "{}", e);
+        assert!(upgrade(serde_json::json!({ "schema_version": next })).is_err());
+        for bad in [serde_json::json!(0), serde_json::json!("2"), serde_json::json!(-1)] {
+            assert!(upgrade(serde_json::json!({ "schema_version": bad })).is_err());
+        }
+        assert!(upgrade(serde_json::json!([])).is_err());
+    }
+
+    #[test]
+    fn ops_rename_split_and_remove() {
+        let mut root = serde_json::json!({ "a": { "x": 1, "y": true }, "top": 2 }).as_object().unwrap().clone();
+        apply(&mut root, &Op::Rename { from: "a.x".into(), to: "b.x".into() });
+        apply(&mut root, &Op::Split { from: "a.y".into(), into: vec!["a.y1".into(), "a.y2".into()] });
+        apply(&mut root, &Op::Remove { path: "top".into() });
+        // A missing source is a no-op
+        apply(&mut root, &Op::Rename { from: "a.gone".into(), to: "a.new".into() });
+        assert_eq!(Value::Object(root), serde_json::json!({ "a": { "y1": true, "y2": true }, "b": { "x": 1 } }));
+    }
+}
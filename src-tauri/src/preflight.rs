@@ -0,0 +1,147 @@
+// ────────────────────────────────────────────────────────────────────
+// Pre-flight safety net for elevated runs: a System Restore point named
+// after the profile, and a .reg export of every registry key the
+// selected tweaks touch into a dated backup folder. The outcome is kept
+// in $_pf, which the run wrapper writes into the script report.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::ps;
+use crate::tweaks::{self, Touch, Tweak};
+
+/// Restore points are capped at one per 24 hours unless this is 0.
+const FREQUENCY_KEY: &str = "HKLM:\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\SystemRestore";
+
+const HELPERS: &str = r#"
+# ---- Pre-flight: restore point + registry export ----
+$_pf = [ordered]@{ restore_point = 'skipped'; restore_point_name = $null; restore_point_error = $null; backup_dir = $null; exported = @(); export_errors = @() }
+
+function Export-RegKey($Path) {
+    if (-not $Path -or -not (Test-Path $Path)) { return }
+    $native = (Get-Item -Path $Path).Name
+    if ($_pf.exported -contains $native) { return }
+    $leaf = (Split-Path $native -Leaf) -replace '[^\w.-]', '_'
+    $file = Join-Path $_pf.backup_dir ('{0:D2}_{1}.reg' -f ($_pf.exported.Count + $_pf.export_errors.Count + 1), $leaf)
+    reg export "$native" "$file" /y 2>&1 | Out-Null
+    if ($LASTEXITCODE -eq 0) { $_pf.exported += $native } else { $_pf.export_errors += $native }
+}
+"#;
+
+/// Registry keys the tweaks write to, in catalog order. Keys held in a
+/// variable (`$nvPath`) stay as the variable; per-adapter values export
+/// the parent key with all its subkeys.
+pub fn registry_keys(tweaks: &[&Tweak]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for touch in tweaks.iter().flat_map(|t| &t.touches) {
+        let key = match touch {
+            Touch::Registry { path, .. } => path,
+            Touch::RegistryEach { parent, .. } => parent,
+            _ => continue,
+        };
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    keys
+}
+
+/// PowerShell block that creates the restore point and exports the keys
+/// `tweaks` touch to `backup_dir`. Failures are recorded, never fatal.
+pub fn script(tweaks: &[&Tweak], profile: Option<&str>, backup_dir: &str) -> String {
+    let mut s = String::from(HELPERS);
+    let name = format!("AimCamp: {}", profile.unwrap_or("config run"));
+
+    s.push_str(&format!("$_pf.restore_point_name = {}\n", ps::quote(&name)));
+    s.push_str(&format!("$_pfFreqKey = {}\n", ps::quote(FREQUENCY_KEY)));
+    s.push_str(
+        r#"Write-Host '    Creating System Restore point...' -ForegroundColor Cyan
+$_pfFreq = (Get-ItemProperty -Path $_pfFreqKey -Name 'SystemRestorePointCreationFrequency' -ErrorAction SilentlyContinue).SystemRestorePointCreationFrequency
+try {
+    $_pfLast = (Get-ComputerRestorePoint -ErrorAction SilentlyContinue | Measure-Object -Property SequenceNumber -Maximum).Maximum
+    Set-ItemProperty -Path $_pfFreqKey -Name 'SystemRestorePointCreationFrequency' -Value 0 -Type DWord -Force
+    Checkpoint-Computer -Description $_pf.restore_point_name -RestorePointType 'MODIFY_SETTINGS' -ErrorAction Stop -WarningAction SilentlyContinue
+    $_pfNew = (Get-ComputerRestorePoint -ErrorAction SilentlyContinue | Measure-Object -Property SequenceNumber -Maximum).Maximum
+    if ($_pfNew -and $_pfNew -ne $_pfLast) {
+        $_pf.restore_point = 'created'
+        Write-Host '    ✔ Restore point created' -ForegroundColor Green
+    } else {
+        $_pf.restore_point = 'failed'
+        $_pf.restore_point_error = 'No restore point was added. System Protection may be off for the system drive.'
+        Write-Host "    ⚠ $($_pf.restore_point_error)" -ForegroundColor Yellow
+    }
+} catch {
+    $_pf.restore_point = 'failed'
+    $_pf.restore_point_error = $_.Exception.Message
+    Write-Host "    ⚠ Restore point failed: $($_.Exception.Message)" -ForegroundColor Yellow
+} finally {
+    if ($null -eq $_pfFreq) { Remove-ItemProperty -Path $_pfFreqKey -Name 'SystemRestorePointCreationFrequency' -ErrorAction SilentlyContinue }
+    else { Set-ItemProperty -Path $_pfFreqKey -Name 'SystemRestorePointCreationFrequency' -Value $_pfFreq -Type DWord -Force }
+}
+"#,
+    );
+
+    let keys = registry_keys(tweaks);
+    if keys.is_empty() {
+        return s;
+    }
+    s.push_str(&format!("$_pf.backup_dir = {}\n", ps::quote(backup_dir)));
+    s.push_str("New-Item -ItemType Directory -Path $_pf.backup_dir -Force | Out-Null\n");
+    // Keys held in variables need their section's lookup lines
+    s.push_str(&tweaks::catalog().variable_preambles(tweaks));
+    for key in &keys {
+        let arg = if key.starts_with('$') { key.clone() } else { ps::quote(key) };
+        s.push_str(&format!("Export-RegKey {}\n", arg));
+    }
+    s.push_str("Write-Host \"    ✔ $($_pf.exported.Count) registry key(s) backed up to $($_pf.backup_dir)\" -ForegroundColor Green\n");
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(keys: &[&str]) -> Vec<&'static Tweak> {
+        keys.iter().map(|k| tweaks::catalog().find(k).unwrap()).collect()
+    }
+
+    #[test]
+    fn quotes_profile_names_and_backup_paths() {
+        let tweaks = find(&["w_dvr"]);
+        let backup_dir = "C:\\Users\\O'Neil\\AimCamp\\backups\\2026-10-17";
+        let s = script(&tweaks, Some("O'Brien \u{2019}main\u{2019}; $(calc)"), backup_dir);
+        let name = "$_pf.restore_point_name = 'AimCamp: O''Brien \u{2019}\u{2019}main\u{2019}\u{2019}; $(calc)'\n";
+        assert!(s.contains(name), "{}", s);
+        assert!(s.contains("$_pf.backup_dir = 'C:\\Users\\O''Neil\\AimCamp\\backups\\2026-10-17'\n"));
+        // Each assigned literal reads back as the original value
+        let literal = |prefix: &str| {
+            let line = s.lines().find_map(|l| l.strip_prefix(prefix)).unwrap();
+            ps::unquote(line).unwrap()
+        };
+        assert_eq!(literal("$_pf.restore_point_name = "), "AimCamp: O'Brien \u{2019}main\u{2019}; $(calc)");
+        assert_eq!(literal("$_pf.backup_dir = "), backup_dir);
+        assert!(s.contains("Export-RegKey 'HKCU:\\System\\GameConfigStore'\n"));
+    }
+
+    #[test]
+    fn without_registry_touches_only_the_restore_point() {
+        let s = script(&find(&["w_idx"]), None, "C:\\backups");
+        assert!(s.contains("$_pf.restore_point_name = 'AimCamp: config run'\n"));
+        assert!(s.contains("Checkpoint-Computer"));
+        assert!(!s.contains("$_pf.backup_dir ="));
+        assert!(!s.contains("Export-RegKey '"));
+    }
+
+    #[test]
+    fn registry_keys_are_unique_and_in_order() {
+        let tweaks = find(&["w_dvr", "w_bar", "w_dvr"]);
+        let keys = registry_keys(&tweaks);
+        assert_eq!(
+            keys,
+            [
+                "HKCU:\\System\\GameConfigStore",
+                "HKLM:\\SOFTWARE\\Policies\\Microsoft\\Windows\\GameDVR",
+                "HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\GameDVR",
+                "HKCU:\\SOFTWARE\\Microsoft\\GameBar",
+            ]
+        );
+    }
+}
@@ -0,0 +1,478 @@
+// ────────────────────────────────────────────────────────────────────
+// Named profile store – one JSON file per profile under
+// <data dir>/aimcamp-player-agent/profiles. Every save appends a
+// revision, so older states stay loadable and diffable. Configs are kept
+// as serialized OptimizationConfig and upgraded through migrate on read.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::{migrate, tweaks};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub created: String,
+    pub updated: String,
+    /// Profile this one was duplicated from
+    #[serde(default)]
+    pub source: Option<String>,
+    pub revisions: Vec<Revision>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Revision {
+    pub revision: u32,
+    pub saved: String,
+    pub config: Value,
+    /// Frontend schema state (layout, UI values), stored as-is
+    #[serde(default)]
+    pub ui: Option<Value>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ProfileInfo {
+    pub id: String,
+    pub name: String,
+    pub created: String,
+    pub updated: String,
+    pub revision: u32,
+    pub revision_count: usize,
+    /// Why the file couldn't be read; the other fields are then best effort
+    pub error: Option<String>,
+}
+
+/// A profile file that doesn't parse (corrupt, or written by a newer
+/// agent). Kept in the listing so its name is never silently reused.
+struct Unreadable {
+    id: String,
+    /// Name recovered from the JSON, when it parses that far
+    name: Option<String>,
+    error: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct RevisionInfo {
+    pub revision: u32,
+    pub saved: String,
+}
+
+/// `name` plus an optional revision (latest when omitted).
+#[derive(serde::Deserialize)]
+pub struct ProfileRef {
+    pub name: String,
+    #[serde(default)]
+    pub revision: Option<u32>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ProfileChange {
+    /// "section.field" path inside OptimizationConfig
+    pub path: String,
+    /// Tweak name from the catalog for toggles
+    pub name: Option<String>,
+    pub a: Value,
+    pub b: Value,
+}
+
+pub struct Store {
+    dir: PathBuf,
+}
+
+fn now() -> String {
+    chrono::Local::now().to_rfc3339()
+}
+
+impl Profile {
+    fn info(&self) -> ProfileInfo {
+        ProfileInfo {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+            revision: self.revisions.last().map_or(0, |r| r.revision),
+            revision_count: self.revisions.len(),
+            error: None,
+        }
+    }
+
+    pub fn revision(&self, revision: Option<u32>) -> Result<&Revision, String> {
+        match revision {
+            None => self.revisions.last(),
+            Some(n) => self.revisions.iter().find(|r| r.revision == n),
+        }
+        .ok_or_else(|| match revision {
+            Some(n) => format!("Profile '{}' has no revision {}", self.name, n),
+            None => format!("Profile '{}' has no revisions", self.name),
+        })
+    }
+
+    pub fn revision_list(&self) -> Vec<RevisionInfo> {
+        self.revisions
+            .iter()
+            .rev()
+            .map(|r| RevisionInfo { revision: r.revision, saved: r.saved.clone() })
+            .collect()
+    }
+}
+
+impl Unreadable {
+    fn name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("{}.json", self.id))
+    }
+
+    fn is(&self, name: &str) -> bool {
+        self.name().eq_ignore_ascii_case(name.trim())
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "Profile '{}' can't be read ({}.json: {}) — fix or delete the file",
+            self.name(),
+            self.id,
+            self.error
+        )
+    }
+
+    fn info(&self) -> ProfileInfo {
+        ProfileInfo {
+            id: self.id.clone(),
+            name: self.name(),
+            created: String::new(),
+            updated: String::new(),
+            revision: 0,
+            revision_count: 0,
+            error: Some(self.error.clone()),
+        }
+    }
+}
+
+impl Store {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn read(path: &Path) -> Result<Profile, String> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read profile: {}", e))?;
+        serde_json::from_str(raw.trim_start_matches('\u{feff}'))
+            .map_err(|e| format!("Profile parse error: {}", e))
+    }
+
+    fn write(&self, profile: &Profile) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(profile)
+            .map_err(|e| format!("Profile serialize error: {}", e))?;
+        // Write to a temp file first so a crash never leaves a truncated profile
+        let tmp = self.dir.join(format!("{}.json.tmp", profile.id));
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write profile: {}", e))?;
+        std::fs::rename(&tmp, self.path(&profile.id))
+            .map_err(|e| format!("Failed to write profile: {}", e))
+    }
+
+    /// Every profile file, readable or not.
+    fn all(&self) -> Vec<Result<Profile, Unreadable>> {
+        let rd = match std::fs::read_dir(&self.dir) {
+            Ok(rd) => rd,
+            Err(_) => return vec![],
+        };
+        rd.flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+            .map(|p| {
+                Self::read(&p).map_err(|error| Unreadable {
+                    id: p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default(),
+                    name: std::fs::read_to_string(&p)
+                        .ok()
+                        .and_then(|raw| serde_json::from_str::<Value>(raw.trim_start_matches('\u{feff}')).ok())
+                        .and_then(|v| v.get("name")?.as_str().map(String::from)),
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    /// Profile names are unique, compared case-insensitively.
+    pub fn find(&self, name: &str) -> Result<Profile, String> {
+        let mut unreadable = None;
+        for entry in self.all() {
+            match entry {
+                Ok(p) if p.name.eq_ignore_ascii_case(name.trim()) => return Ok(p),
+                Err(u) if u.is(name) => unreadable = Some(u),
+                _ => {}
+            }
+        }
+        Err(match unreadable {
+            Some(u) => u.message(),
+            None => format!("Profile '{}' not found", name),
+        })
+    }
+
+    fn ensure_free(&self, name: &str) -> Result<(), String> {
+        if name.trim().is_empty() {
+            return Err("Profile name cannot be empty".into());
+        }
+        for entry in self.all() {
+            match entry {
+                Ok(p) if p.name.eq_ignore_ascii_case(name.trim()) => {
+                    return Err(format!("A profile named '{}' already exists", name.trim()));
+                }
+                Err(u) if u.is(name) => return Err(u.message()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Readable profiles plus the files that failed to load, with `error` set.
+    pub fn list(&self) -> Vec<ProfileInfo> {
+        let mut out: Vec<ProfileInfo> = self
+            .all()
+            .iter()
+            .map(|entry| match entry {
+                Ok(p) => p.info(),
+                Err(u) => u.info(),
+            })
+            .collect();
+        out.sort_by_key(|p| p.name.to_lowercase());
+        out
+    }
+
+    /// Create the profile or append a revision to the existing one.
+    pub fn save(&self, name: &str, config: Value, ui: Option<Value>) -> Result<ProfileInfo, String> {
+        let ts = now();
+        let mut profile = match self.find(name) {
+            Ok(p) => p,
+            Err(_) => {
+                self.ensure_free(name)?;
+                Profile {
+                    id: uuid::Uuid::new_v4().simple().to_string(),
+                    name: name.trim().to_string(),
+                    created: ts.clone(),
+                    updated: ts.clone(),
+                    source: None,
+                    revisions: vec![],
+                }
+            }
+        };
+        let revision = profile.revisions.last().map_or(1, |r| r.revision + 1);
+        profile.revisions.push(Revision { revision, saved: ts.clone(), config, ui });
+        profile.updated = ts;
+        self.write(&profile)?;
+        Ok(profile.info())
+    }
+
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<ProfileInfo, String> {
+        let mut profile = self.find(name)?;
+        if !profile.name.eq_ignore_ascii_case(new_name.trim()) {
+            self.ensure_free(new_name)?;
+        }
+        profile.name = new_name.trim().to_string();
+        profile.updated = now();
+        self.write(&profile)?;
+        Ok(profile.info())
+    }
+
+    /// Also removes an unreadable file listed under `name`.
+    pub fn delete(&self, name: &str) -> Result<(), String> {
+        let id = self
+            .all()
+            .into_iter()
+            .find_map(|entry| match entry {
+                Ok(p) if p.name.eq_ignore_ascii_case(name.trim()) => Some(p.id),
+                Err(u) if u.is(name) => Some(u.id),
+                _ => None,
+            })
+            .ok_or_else(|| format!("Profile '{}' not found", name))?;
+        std::fs::remove_file(self.path(&id)).map_err(|e| format!("Failed to delete profile: {}", e))
+    }
+
+    /// New profile starting from the latest revision of `name`.
+    pub fn duplicate(&self, name: &str, new_name: &str) -> Result<ProfileInfo, String> {
+        let source = self.find(name)?;
+        self.ensure_free(new_name)?;
+        let latest = source.revision(None)?.clone();
+        let ts = now();
+        let copy = Profile {
+            id: uuid::Uuid::new_v4().simple().to_string(),
+            name: new_name.trim().to_string(),
+            created: ts.clone(),
+            updated: ts.clone(),
+            source: Some(source.name.clone()),
+            revisions: vec![Revision { revision: 1, saved: ts, ..latest }],
+        };
+        self.write(&copy)?;
+        Ok(copy.info())
+    }
+
+    fn config_at(&self, r: &ProfileRef) -> Result<Value, String> {
+        let profile = self.find(&r.name)?;
+        let config = profile.revision(r.revision)?.config.clone();
+        migrate::upgrade(config)
+    }
+
+    /// Toggles and values that differ between two profiles or revisions.
+    pub fn diff(&self, a: &ProfileRef, b: &ProfileRef) -> Result<Vec<ProfileChange>, String> {
+        let (va, vb) = (self.config_at(a)?, self.config_at(b)?);
+        let mut changes = Vec::new();
+        diff_values("", &va, &vb, &mut changes);
+        Ok(changes)
+    }
+}
+
+fn diff_values(path: &str, a: &Value, b: &Value, out: &mut Vec<ProfileChange>) {
+    if let (Value::Object(ma), Value::Object(mb)) = (a, b) {
+        let mut keys: Vec<&String> = ma.keys().chain(mb.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for k in keys {
+            let child = if path.is_empty() { k.clone() } else { format!("{}.{}", path, k) };
+            let null = Value::Null;
+            diff_values(&child, ma.get(k).unwrap_or(&null), mb.get(k).unwrap_or(&null), out);
+        }
+        return;
+    }
+    if a != b {
+        out.push(ProfileChange { path: path.to_string(), name: tweak_name(path), a: a.clone(), b: b.clone() });
+    }
+}
+
+fn tweak_name(path: &str) -> Option<String> {
+    let (section_field, id) = path.split_once('.')?;
+    let cat = tweaks::catalog();
+    let section = cat.sections.iter().find(|s| s.config == section_field)?;
+    cat.section_tweaks(&section.id)
+        .find(|t| t.id == id)
+        .map(|t| t.name().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct TempStore(Store);
+
+    impl TempStore {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("aimcamp-profiles-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            TempStore(Store::new(dir))
+        }
+    }
+
+    impl Drop for TempStore {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0.dir);
+        }
+    }
+
+    /// Current-version config with a few fields set.
+    fn config(dvr: bool, fps_max: &str) -> Value {
+        migrate::upgrade(json!({
+            "windows": { "disable_game_dvr": dvr, "disable_game_bar": true },
+            "autoexec": { "fps_max": fps_max },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn save_appends_revisions() {
+        let t = TempStore::new("save");
+        let store = &t.0;
+        let first = store.save(" Main ", config(false, "400"), Some(json!({ "layout": 1 }))).unwrap();
+        assert_eq!((first.name.as_str(), first.revision, first.revision_count), ("Main", 1, 1));
+        let second = store.save("MAIN", config(true, "0"), None).unwrap();
+        assert_eq!((second.id.as_str(), second.revision, second.revision_count), (first.id.as_str(), 2, 2));
+        assert!(store.save("  ", config(false, "0"), None).is_err());
+
+        let profile = store.find("main").unwrap();
+        assert_eq!(profile.revision(None).unwrap().config, config(true, "0"));
+        assert_eq!(profile.revision(Some(1)).unwrap().ui, Some(json!({ "layout": 1 })));
+        assert!(profile.revision(Some(3)).is_err());
+        let revs: Vec<u32> = profile.revision_list().iter().map(|r| r.revision).collect();
+        assert_eq!(revs, [2, 1]);
+        // Written through a temp file that doesn't linger
+        let files: Vec<_> = std::fs::read_dir(&store.dir).unwrap().flatten().map(|e| e.file_name()).collect();
+        assert_eq!(files, [std::ffi::OsString::from(format!("{}.json", first.id))]);
+    }
+
+    #[test]
+    fn rename_duplicate_and_delete() {
+        let t = TempStore::new("rename");
+        let store = &t.0;
+        store.save("Main", config(false, "400"), None).unwrap();
+        store.save("Main", config(true, "400"), None).unwrap();
+        store.save("Other", config(false, "0"), None).unwrap();
+
+        assert!(store.rename("Main", "other").err().unwrap().contains("already exists"));
+        assert_eq!(store.rename("Main", "MAIN").unwrap().name, "MAIN");
+        assert_eq!(store.rename("main", "Faceit").unwrap().revision, 2);
+        assert!(store.find("Main").is_err());
+
+        assert!(store.duplicate("Faceit", "Other").is_err());
+        let copy = store.duplicate("faceit", "Faceit LAN").unwrap();
+        assert_eq!((copy.revision, copy.revision_count), (1, 1));
+        let copy = store.find("Faceit LAN").unwrap();
+        assert_eq!(copy.source.as_deref(), Some("Faceit"));
+        assert_eq!(copy.revisions[0].config, config(true, "400"));
+
+        let names: Vec<String> = store.list().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["Faceit", "Faceit LAN", "Other"]);
+        store.delete("other").unwrap();
+        assert!(store.delete("Other").is_err());
+        assert_eq!(store.list().len(), 2);
+    }
+
+    #[test]
+    fn diff_between_revisions_and_profiles() {
+        let t = TempStore::new("diff");
+        let store = &t.0;
+        store.save("Main", config(false, "400"), None).unwrap();
+        store.save("Main", config(true, "0"), None).unwrap();
+        store.save("Old", json!({ "windows": { "disable_game_dvr": true, "disable_game_bar": true } }), None).unwrap();
+
+        let at = |name: &str, revision| ProfileRef { name: name.into(), revision };
+        let changes = store.diff(&at("Main", Some(1)), &at("Main", None)).unwrap();
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["autoexec.fps_max", "windows.disable_game_dvr"]);
+        assert_eq!((&changes[0].a, &changes[0].b), (&json!("400"), &json!("0")));
+        assert_eq!(changes[0].name, None);
+        assert_eq!(changes[1].name.as_deref(), Some("Disable Game DVR (recording & captures)"));
+
+        // An unversioned payload is upgraded before comparing
+        let changes = store.diff(&at("Old", None), &at("Main", None)).unwrap();
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["autoexec.fps_max"]);
+        assert!(store.diff(&at("Main", Some(9)), &at("Main", None)).is_err());
+    }
+
+    #[test]
+    fn unreadable_files_are_listed_and_block_their_name() {
+        let t = TempStore::new("unreadable");
+        let store = &t.0;
+        store.save("Fine", config(false, "400"), None).unwrap();
+        // Valid JSON in a shape this agent doesn't know, and plain garbage
+        std::fs::write(store.dir.join("newer.json"), r#"{ "name": "Main", "revisions": "elsewhere" }"#).unwrap();
+        std::fs::write(store.dir.join("broken.json"), "{ not json").unwrap();
+
+        let list = store.list();
+        let entries: Vec<(&str, bool)> = list.iter().map(|p| (p.name.as_str(), p.error.is_some())).collect();
+        assert_eq!(entries, [("broken.json", true), ("Fine", false), ("Main", true)]);
+
+        assert!(store.find("Main").err().unwrap().contains("can't be read"));
+        assert!(store.save("main", config(true, "0"), None).err().unwrap().contains("newer.json"));
+        assert!(store.rename("Fine", "Main").is_err());
+        assert!(store.duplicate("Fine", "MAIN").is_err());
+
+        store.delete("Main").unwrap();
+        store.delete("broken.json").unwrap();
+        assert_eq!(store.save("Main", config(true, "0"), None).unwrap().revision, 1);
+        assert!(store.list().iter().all(|p| p.error.is_none()));
+    }
+}
@@ -0,0 +1,351 @@
+// ────────────────────────────────────────────────────────────────────
+// PowerShell literals and input validation. Every user-supplied value
+// that ends up in a generated script goes through `quote` (or one of the
+// validators below first), so a crafted field in a shared schema can
+// only ever become a string literal, never code.
+// ────────────────────────────────────────────────────────────────────
+
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+
+/// PowerShell treats the typographic quotes U+2018..U+201B like `'`.
+fn is_quote(c: char) -> bool {
+    matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}')
+}
+
+/// Single-quoted PowerShell literal; every kind of single quote is doubled.
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('\'');
+    for c in s.chars() {
+        if is_quote(c) {
+            out.push(c);
+        }
+        out.push(c);
+    }
+    out.push('\'');
+    out
+}
+
+/// Inverse of `quote`: the value of a complete single-quoted literal.
+pub fn unquote(literal: &str) -> Option<String> {
+    let mut chars = literal.trim().chars();
+    if !chars.next().is_some_and(is_quote) {
+        return None;
+    }
+    let mut out = String::new();
+    while let Some(c) = chars.next() {
+        if is_quote(c) {
+            match chars.next() {
+                Some(d) if is_quote(d) => out.push(d),
+                None => return Some(out),
+                Some(_) => return None,
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    None
+}
+
+/// `@('a','b')` array literal.
+pub fn quote_array<S: AsRef<str>>(items: &[S]) -> String {
+    let parts: Vec<String> = items.iter().map(|s| quote(s.as_ref())).collect();
+    format!("@({})", parts.join(","))
+}
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    Ip,
+    Hostname,
+    Guid,
+    Path,
+    Number,
+    Text,
+    ShareCode,
+}
+
+/// A field rejected before it reached a script.
+#[derive(serde::Serialize, Debug)]
+pub struct ValidationError {
+    pub field: String,
+    pub rule: Rule,
+    pub value: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(field: &str, rule: Rule, value: &str, message: &str) -> Self {
+        // Keep the echoed value short; it is only there to help the user
+        let value: String = value.chars().take(80).collect();
+        Self { field: field.into(), rule, value, message: message.into() }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid {}: {}", self.field, self.message)
+    }
+}
+
+/// Error type for commands that validate input. Serialized with a `kind`
+/// tag so the frontend can tell a rejected field from a failed run.
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandError {
+    Validation(ValidationError),
+    Failed { message: String },
+    /// High-risk tweaks were selected without being acknowledged
+    Unacknowledged { message: String, tweaks: Vec<crate::tweaks::RiskNotice> },
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Validation(v) => v.fmt(f),
+            CommandError::Failed { message } | CommandError::Unacknowledged { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl From<ValidationError> for CommandError {
+    fn from(e: ValidationError) -> Self {
+        CommandError::Validation(e)
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Failed { message }
+    }
+}
+
+impl From<CommandError> for String {
+    fn from(e: CommandError) -> Self {
+        e.to_string()
+    }
+}
+
+// ── Validators ──
+
+pub fn ip(field: &str, value: &str) -> Result<String, ValidationError> {
+    value
+        .trim()
+        .parse::<IpAddr>()
+        .map(|a| a.to_string())
+        .map_err(|_| ValidationError::new(field, Rule::Ip, value, "not an IPv4 or IPv6 address"))
+}
+
+/// DNS name (RFC 1123 labels) or IP address.
+pub fn hostname(field: &str, value: &str) -> Result<String, ValidationError> {
+    let v = value.trim().trim_end_matches('.');
+    if let Ok(addr) = v.parse::<IpAddr>() {
+        return Ok(addr.to_string());
+    }
+    let label_ok = |l: &str| {
+        !l.is_empty()
+            && l.len() <= 63
+            && l.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            && !l.starts_with('-')
+            && !l.ends_with('-')
+    };
+    if v.is_empty() || v.len() > 253 || !v.split('.').all(label_ok) {
+        return Err(ValidationError::new(field, Rule::Hostname, value, "not a valid host name"));
+    }
+    Ok(v.to_ascii_lowercase())
+}
+
+/// 8-4-4-4-12 hex GUID, braces optional.
+pub fn guid(field: &str, value: &str) -> Result<String, ValidationError> {
+    let v = value.trim().trim_start_matches('{').trim_end_matches('}');
+    let groups: Vec<&str> = v.split('-').collect();
+    let lens = [8, 4, 4, 4, 12];
+    let ok = groups.len() == lens.len()
+        && groups
+            .iter()
+            .zip(lens)
+            .all(|(g, n)| g.len() == n && g.bytes().all(|b| b.is_ascii_hexdigit()));
+    if !ok {
+        return Err(ValidationError::new(field, Rule::Guid, value, "not a GUID"));
+    }
+    Ok(v.to_ascii_lowercase())
+}
+
+/// Absolute local path to an existing file with the given extension.
+/// Double quotes and control characters are refused because the path is
+/// passed on through `-ArgumentList`; UNC shares and `..` are refused so
+/// the elevated script reads only the file that was picked.
+pub fn file_path(field: &str, value: &str, extension: &str) -> Result<String, ValidationError> {
+    let err = |msg: &str| ValidationError::new(field, Rule::Path, value, msg);
+    if value.is_empty() {
+        return Err(err("no path provided"));
+    }
+    if value.chars().any(|c| c == '"' || c.is_control()) {
+        return Err(err("contains quotes or control characters"));
+    }
+    if value.starts_with(r"\\") || value.starts_with("//") {
+        return Err(err("network paths are not allowed"));
+    }
+    if value.split(['\\', '/']).any(|part| part == "..") {
+        return Err(err("must not contain .."));
+    }
+    let path = Path::new(value);
+    if !path.is_absolute() {
+        return Err(err("must be an absolute path"));
+    }
+    let ext_ok = path
+        .extension()
+        .is_some_and(|e| e.to_string_lossy().eq_ignore_ascii_case(extension));
+    if !ext_ok {
+        return Err(err(&format!("must be a .{} file", extension)));
+    }
+    if !path.is_file() {
+        return Err(err("file does not exist"));
+    }
+    Ok(value.to_string())
+}
+
+/// Numeric cvar / launch option value (`128000`, `0.015625`, `-1`).
+pub fn number(field: &str, value: &str) -> Result<String, ValidationError> {
+    let v = value.trim();
+    if v.parse::<f64>().is_err() || !v.bytes().all(|b| b.is_ascii_digit() || b == b'.' || b == b'-') {
+        return Err(ValidationError::new(field, Rule::Number, value, "must be a number"));
+    }
+    Ok(v.to_string())
+}
+
+/// CS2 share code (`CSGO-xxxxx-…`) that decodes.
+pub fn crosshair_code(field: &str, value: &str) -> Result<String, ValidationError> {
+    crate::crosshair::decode(value)
+        .map(|_| value.trim().to_string())
+        .map_err(|e| ValidationError::new(field, Rule::ShareCode, value, &e))
+}
+
+/// Free text that becomes a single script line (cfg command, launch
+/// arguments). Quoting makes it inert; line breaks and other control
+/// characters are still refused so one field cannot become several lines.
+pub fn text_line(field: &str, value: &str) -> Result<String, ValidationError> {
+    if value.chars().any(|c| c.is_control()) {
+        return Err(ValidationError::new(field, Rule::Text, value, "contains line breaks or control characters"));
+    }
+    Ok(value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_doubles_every_single_quote() {
+        assert_eq!(quote("it's"), "'it''s'");
+        assert_eq!(quote(""), "''");
+        for q in ['\u{2018}', '\u{2019}', '\u{201A}', '\u{201B}'] {
+            assert_eq!(quote(&format!("a{}b", q)), format!("'a{}{}b'", q, q));
+        }
+        assert_eq!(quote("x'; Start-Process calc; '"), "'x''; Start-Process calc; '''");
+        assert_eq!(quote("x\u{2019}; calc"), "'x\u{2019}\u{2019}; calc'");
+        assert_eq!(quote_array(&["a", "b'c"]), "@('a','b''c')");
+    }
+
+    #[test]
+    fn quote_keeps_expansions_inert() {
+        // Nothing expands inside a single-quoted literal, so these stay as they are
+        for s in ["$(Remove-Item C:\\ -Recurse)", "`$env:TEMP`n", "$env:USERPROFILE", "a\nb\r\nc"] {
+            assert_eq!(quote(s), format!("'{}'", s));
+        }
+    }
+
+    #[test]
+    fn quote_unquote_round_trip() {
+        for s in ["", "plain", "it's", "''", "\u{2019}\u{2018}'", "$(x) `y` \"z\"", "a\nb", "C:\\Users\\O'Brien\\cfg"] {
+            assert_eq!(unquote(&quote(s)).as_deref(), Some(s), "{}", s);
+        }
+        assert_eq!(unquote("  'padded'  ").as_deref(), Some("padded"));
+        // Unterminated, unquoted, or with a lone quote inside
+        for bad in ["'open", "bare", "'a'b'", "\"double\""] {
+            assert_eq!(unquote(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn ip_and_hostname() {
+        assert_eq!(ip("dns", " 1.1.1.1 ").unwrap(), "1.1.1.1");
+        assert_eq!(ip("dns", "2606:4700:4700::1111").unwrap(), "2606:4700:4700::1111");
+        for bad in ["", "1.1.1", "256.1.1.1", "1.1.1.1; calc", "localhost"] {
+            assert_eq!(ip("dns", bad).unwrap_err().rule, Rule::Ip, "{}", bad);
+        }
+        assert_eq!(hostname("host", "Example.COM.").unwrap(), "example.com");
+        assert_eq!(hostname("host", "10.0.0.1").unwrap(), "10.0.0.1");
+        let long_label = "a".repeat(64);
+        for bad in ["", "-bad.com", "bad-.com", "a..b", "a b", "a;b", "$(calc)", "x'y", long_label.as_str()] {
+            assert_eq!(hostname("host", bad).unwrap_err().rule, Rule::Hostname, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn guid_needs_all_five_groups() {
+        let g = "381b4222-f694-41f0-9685-ff5bb260df2e";
+        assert_eq!(guid("scheme", &format!("{{{}}}", g.to_uppercase())).unwrap(), g);
+        for bad in [
+            "",
+            "381b4222-f694-41f0-9685",
+            "381b4222f69441f09685ff5bb260df2e",
+            "381b4222-f694-41f0-9685-ff5bb260df2g",
+            "381b4222-f694-41f0-9685-ff5bb260df2e'",
+        ] {
+            assert_eq!(guid("scheme", bad).unwrap_err().rule, Rule::Guid, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn file_path_rules() {
+        let dir = std::env::temp_dir().join(format!("aimcamp-ps-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("profile.json");
+        std::fs::write(&file, "{}").unwrap();
+        std::fs::write(dir.join("profile.txt"), "").unwrap();
+        let good = file.to_string_lossy().to_string();
+        assert_eq!(file_path("import", &good, "JSON").unwrap(), good);
+
+        let name = dir.file_name().unwrap().to_string_lossy().to_string();
+        let dotdot = format!("{}/../{}/profile.json", dir.to_string_lossy(), name);
+        let missing = dir.join("missing.json").to_string_lossy().to_string();
+        let wrong_ext = dir.join("profile.txt").to_string_lossy().to_string();
+        for (bad, msg) in [
+            ("", "no path"),
+            ("profile.json", "absolute"),
+            (wrong_ext.as_str(), ".json"),
+            (missing.as_str(), "does not exist"),
+            (dotdot.as_str(), ".."),
+            (r"C:\Users\..\Windows\profile.json", ".."),
+            (r"\\server\share\profile.json", "network"),
+            ("//server/share/profile.json", "network"),
+            ("C:\\a\"b.json", "quotes"),
+            ("C:\\a\nb.json", "control"),
+        ] {
+            let e = file_path("import", bad, "json").unwrap_err();
+            assert_eq!(e.rule, Rule::Path);
+            assert!(e.message.contains(msg), "{}: {}", bad, e.message);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn number_and_text_line() {
+        for good in ["128000", " 0.015625 ", "-1"] {
+            assert_eq!(number("rate", good).unwrap(), good.trim());
+        }
+        for bad in ["", "1e5", "0x10", "NaN", "inf", "1;calc", "1 2", "$(1)"] {
+            assert_eq!(number("rate", bad).unwrap_err().rule, Rule::Number, "{}", bad);
+        }
+        assert_eq!(text_line("cmd", "  say 'gl hf'; $(x) ").unwrap(), "say 'gl hf'; $(x)");
+        for bad in ["a\nb", "a\rb", "a\u{0}b", "tab\there"] {
+            assert_eq!(text_line("cmd", bad).unwrap_err().rule, Rule::Text, "{:?}", bad);
+        }
+        // The echoed value is cut short
+        let long = format!("{}\n", "x".repeat(200));
+        assert_eq!(text_line("cmd", &long).unwrap_err().value.chars().count(), 80);
+    }
+}
@@ -0,0 +1,259 @@
+// ────────────────────────────────────────────────────────────────────
+// Script execution report – the wrapper around elevated runs writes
+// timing/trap errors and the pre-flight outcome to REPORT_FILE, every
+// ps1_cmd step appends one JSON line to STEPS_FILE. Both live in the
+// temp dir.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::tweaks;
+use std::path::Path;
+
+pub const REPORT_FILE: &str = "aimcamp_script_report.json";
+pub const STEPS_FILE: &str = "aimcamp_script_steps.jsonl";
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Ok,
+    /// Finished, but wrote to the error stream or left a non-zero exit code
+    Warning,
+    Failed,
+    /// Not applicable on this Windows build / edition
+    Skipped,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct StepRecord {
+    /// Tweak key (e.g. "w_dvr")
+    pub step: String,
+    #[serde(default)]
+    pub section: Option<String>,
+    pub description: String,
+    pub status: StepStatus,
+    #[serde(default)]
+    pub exit_code: Option<i64>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+/// Outcome of the pre-flight step (restore point + .reg export).
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Preflight {
+    /// "created", "failed" or "skipped"
+    pub restore_point: String,
+    #[serde(default)]
+    pub restore_point_name: Option<String>,
+    #[serde(default)]
+    pub restore_point_error: Option<String>,
+    /// Folder holding the .reg exports; None when no key was touched
+    #[serde(default)]
+    pub backup_dir: Option<String>,
+    /// Registry keys exported, in native form (HKEY_LOCAL_MACHINE\...)
+    #[serde(default)]
+    pub exported: Vec<String>,
+    #[serde(default)]
+    pub export_errors: Vec<String>,
+}
+
+#[derive(serde::Deserialize, Default)]
+struct RunSummary {
+    #[serde(default)]
+    duration_secs: f64,
+    #[serde(default)]
+    errors: Vec<String>,
+    #[serde(default)]
+    start_time: Option<String>,
+    #[serde(default)]
+    end_time: Option<String>,
+    #[serde(default)]
+    snapshot_id: Option<String>,
+    /// Keys of the high-risk tweaks the run was allowed to apply
+    #[serde(default)]
+    acknowledged: Vec<String>,
+    /// Absent when the run opted out of the pre-flight step
+    #[serde(default)]
+    preflight: Option<Preflight>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ScriptReport {
+    /// "no_report" while the script is still running, else "success" / "partial"
+    pub status: String,
+    pub duration_secs: f64,
+    pub sections_run: usize,
+    pub commands_run: usize,
+    pub failed_steps: usize,
+    pub warning_steps: usize,
+    pub skipped_steps: usize,
+    /// Errors trapped outside of any step
+    pub errors: Vec<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub snapshot_id: Option<String>,
+    /// High-risk tweaks acknowledged for this run
+    pub acknowledged: Vec<tweaks::RiskNotice>,
+    pub preflight: Option<Preflight>,
+    pub steps: Vec<StepRecord>,
+    /// Step log lines that did not parse ("line 3: ..."); their steps are unaccounted for
+    pub unreadable_steps: Vec<String>,
+}
+
+fn read_text(path: &Path) -> Result<String, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read report: {}", e))?;
+    Ok(content.trim_start_matches('\u{FEFF}').to_string())
+}
+
+pub fn load(report_path: &Path, steps_path: &Path) -> Result<ScriptReport, String> {
+    if !report_path.exists() {
+        return Ok(ScriptReport {
+            status: "no_report".into(),
+            duration_secs: 0.0,
+            sections_run: 0,
+            commands_run: 0,
+            failed_steps: 0,
+            warning_steps: 0,
+            skipped_steps: 0,
+            errors: vec![],
+            start_time: None,
+            end_time: None,
+            snapshot_id: None,
+            acknowledged: vec![],
+            preflight: None,
+            steps: vec![],
+            unreadable_steps: vec![],
+        });
+    }
+    let summary: RunSummary = serde_json::from_str(read_text(report_path)?.trim())
+        .map_err(|e| format!("Report parse error: {}", e))?;
+
+    // Add-Content writes a BOM-less line per step
+    let mut steps: Vec<StepRecord> = Vec::new();
+    let mut unreadable_steps = Vec::new();
+    if steps_path.exists() {
+        for (i, line) in read_text(steps_path)?.lines().enumerate() {
+            let line = line.trim_start_matches('\u{FEFF}').trim();
+            if line.is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(step) => steps.push(step),
+                Err(e) => unreadable_steps.push(format!("line {}: {}", i + 1, e)),
+            }
+        }
+    }
+
+    let mut sections: Vec<&str> = steps.iter().filter_map(|s| s.section.as_deref()).collect();
+    sections.sort_unstable();
+    sections.dedup();
+    let failed_steps = steps.iter().filter(|s| s.status == StepStatus::Failed).count();
+    let warning_steps = steps.iter().filter(|s| s.status == StepStatus::Warning).count();
+    let skipped_steps = steps.iter().filter(|s| s.status == StepStatus::Skipped).count();
+    let clean = failed_steps == 0 && summary.errors.is_empty() && unreadable_steps.is_empty();
+    let status = if clean { "success" } else { "partial" };
+
+    Ok(ScriptReport {
+        status: status.into(),
+        duration_secs: summary.duration_secs,
+        sections_run: sections.len(),
+        commands_run: steps.len() - skipped_steps,
+        failed_steps,
+        warning_steps,
+        skipped_steps,
+        errors: summary.errors,
+        start_time: summary.start_time,
+        end_time: summary.end_time,
+        snapshot_id: summary.snapshot_id,
+        acknowledged: summary
+            .acknowledged
+            .iter()
+            .filter_map(|key| tweaks::catalog().find(key))
+            .map(tweaks::RiskNotice::from)
+            .collect(),
+        preflight: summary.preflight,
+        steps,
+        unreadable_steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_pair(name: &str, report: &str, steps: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("aimcamp-report-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (r, s) = (dir.join(REPORT_FILE), dir.join(STEPS_FILE));
+        std::fs::write(&r, report).unwrap();
+        std::fs::write(&s, steps).unwrap();
+        (r, s)
+    }
+
+    #[test]
+    fn missing_report_means_still_running() {
+        let dir = std::env::temp_dir().join(format!("aimcamp-report-none-{}", std::process::id()));
+        let r = load(&dir.join(REPORT_FILE), &dir.join(STEPS_FILE)).unwrap();
+        assert_eq!(r.status, "no_report");
+        assert!(r.steps.is_empty());
+    }
+
+    #[test]
+    fn parses_failed_skipped_and_malformed_steps() {
+        let report = "\u{feff}{\"duration_secs\": 12.5, \"errors\": [], \"snapshot_id\": \"20261017-101500\", \
+                      \"acknowledged\": [\"w_hpet\", \"not_a_tweak\"], \
+                      \"preflight\": {\"restore_point\": \"failed\", \"restore_point_error\": \"Frequency limit\"}}";
+        let steps = [
+            r#"{"step":"w_dvr","section":"windows","description":"Disable Game DVR","status":"ok","duration_ms":40}"#,
+            r#"{"step":"w_hpet","section":"windows","description":"Disable HPET","status":"failed","exit_code":1,"error":"The parameter is incorrect. <b>"}"#,
+            "",
+            r#"{"step":"w_widgets","section":"windows","description":"Disable Widgets","status":"skipped"}"#,
+            r#"{"step":"n_nagle","section":"network","description":"Disable Nagle","status":"warning","exit_code":2}"#,
+            r#"{"step":"n_tcp","section":"network","description":"cut off mid-wri"#,
+            r#"{"step":"n_dns","description":"Flush DNS","status":"exploded"}"#,
+        ]
+        .join("\n");
+        let (rp, sp) = write_pair("mixed", report, &steps);
+        let r = load(&rp, &sp).unwrap();
+
+        assert_eq!(r.status, "partial");
+        assert_eq!(r.duration_secs, 12.5);
+        assert_eq!(r.snapshot_id.as_deref(), Some("20261017-101500"));
+        assert_eq!(r.steps.len(), 4);
+        assert_eq!((r.failed_steps, r.warning_steps, r.skipped_steps), (1, 1, 1));
+        // Skipped steps ran nothing
+        assert_eq!((r.commands_run, r.sections_run), (3, 2));
+        let failed = r.steps.iter().find(|s| s.status == StepStatus::Failed).unwrap();
+        assert_eq!((failed.step.as_str(), failed.exit_code), ("w_hpet", Some(1)));
+        assert_eq!(failed.error.as_deref(), Some("The parameter is incorrect. <b>"));
+        // Line numbers count the blank line too
+        assert_eq!(r.unreadable_steps.len(), 2);
+        assert!(r.unreadable_steps[0].starts_with("line 6: "), "{:?}", r.unreadable_steps);
+        assert!(r.unreadable_steps[1].starts_with("line 7: "), "{:?}", r.unreadable_steps);
+        // Unknown keys are dropped from the acknowledgements
+        let acked: Vec<&str> = r.acknowledged.iter().map(|n| n.key.as_str()).collect();
+        assert_eq!(acked, ["w_hpet"]);
+        let pf = r.preflight.unwrap();
+        assert_eq!((pf.restore_point.as_str(), pf.restore_point_error.as_deref()), ("failed", Some("Frequency limit")));
+        let _ = std::fs::remove_dir_all(rp.parent().unwrap());
+    }
+
+    #[test]
+    fn clean_run_is_a_success() {
+        let steps = r#"{"step":"w_dvr","section":"windows","description":"Disable Game DVR","status":"ok"}"#;
+        let (rp, sp) = write_pair("clean", r#"{"duration_secs": 1.0}"#, steps);
+        let r = load(&rp, &sp).unwrap();
+        assert_eq!(r.status, "success");
+        assert!(r.preflight.is_none());
+
+        let (rp, sp) = write_pair("trapped", r#"{"errors": ["Access denied"]}"#, steps);
+        assert_eq!(load(&rp, &sp).unwrap().status, "partial");
+        let (rp, sp) = write_pair("corrupt", "{ nope", steps);
+        assert!(load(&rp, &sp).is_err());
+        for name in ["clean", "trapped", "corrupt"] {
+            let dir = std::env::temp_dir().join(format!("aimcamp-report-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
@@ -0,0 +1,287 @@
+// ────────────────────────────────────────────────────────────────────
+// Round-trip between generated scripts and OptimizationConfig. New
+// scripts carry the serialized config (base64, so no cfg text can close
+// a comment) in a header; scripts from older versions are recognised by
+// their ps1_cmd / info comments and the autoexec / launch option lines.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::{ps, tweaks};
+use base64::Engine;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+const HEADER_TAG: &str = "# aimcamp-config:";
+const DATA_TAG: &str = "# aimcamp-config-data:";
+
+/// UI keys of the value-driven sections and the config fields they mirror.
+const VALUE_TOGGLES: &[(&str, &str, &str)] = &[
+    ("ae_on", "autoexec", "enabled"),
+    ("ae_raw", "autoexec", "m_rawinput"),
+    ("lo_exec", "launch_options", "exec_autoexec"),
+    ("lo_nvid", "launch_options", "novid"),
+    ("lo_joy", "launch_options", "nojoy"),
+    ("lo_high", "launch_options", "high_priority"),
+    ("lo_allow", "launch_options", "allow_third_party"),
+];
+
+const AUTOEXEC_CVARS: &[&str] = &["fps_max", "rate", "cl_interp", "cl_interp_ratio", "cl_updaterate", "cl_cmdrate"];
+
+#[derive(serde::Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    /// Exact config from the script header
+    Header,
+    /// Rebuilt from known command blocks (older scripts)
+    Recognised,
+}
+
+pub struct Imported {
+    pub source: ImportSource,
+    pub generator: Option<String>,
+    pub section: Option<String>,
+    /// Serialized OptimizationConfig, possibly from an older schema version
+    pub config: Value,
+}
+
+/// Comment lines placed at the top of every generated script.
+pub fn header(config: &Value, section: Option<&str>) -> String {
+    let meta = json!({
+        "generator": env!("CARGO_PKG_VERSION"),
+        "section": section,
+        "config": config,
+    });
+    let data = base64::engine::general_purpose::STANDARD.encode(meta.to_string());
+    format!(
+        "{} generator={} section={}\n{} {}\n",
+        HEADER_TAG,
+        env!("CARGO_PKG_VERSION"),
+        section.unwrap_or("all"),
+        DATA_TAG,
+        data
+    )
+}
+
+pub fn import(content: &str) -> Result<Imported, String> {
+    let content = content.trim_start_matches('\u{feff}');
+    match content.lines().find_map(|l| l.trim().strip_prefix(DATA_TAG)) {
+        Some(data) => from_header(data.trim()),
+        None => recognise(content),
+    }
+}
+
+fn from_header(data: &str) -> Result<Imported, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| format!("Script config header is corrupt: {}", e))?;
+    let mut meta: Value = serde_json::from_slice(&bytes)
+        .map_err(|e| format!("Script config header is corrupt: {}", e))?;
+    let str_field = |meta: &Value, k: &str| meta.get(k).and_then(|v| v.as_str()).map(String::from);
+    Ok(Imported {
+        source: ImportSource::Header,
+        generator: str_field(&meta, "generator"),
+        section: str_field(&meta, "section"),
+        config: meta.get_mut("config").map(Value::take).unwrap_or_default(),
+    })
+}
+
+/// Config with every toggle off and the UI defaults for value fields.
+fn empty_config() -> Map<String, Value> {
+    let cat = tweaks::catalog();
+    let mut root = Map::new();
+    for sec in &cat.sections {
+        let obj = root.entry(sec.config.clone()).or_insert_with(|| json!({}));
+        for tweak in cat.section_tweaks(&sec.id) {
+            obj[&tweak.id] = Value::Bool(false);
+        }
+    }
+    root.insert(
+        "autoexec".into(),
+        json!({
+            "enabled": false, "fps_max": "400", "rate": "786432", "cl_interp": "0",
+            "cl_interp_ratio": "1", "cl_updaterate": "128", "cl_cmdrate": "128",
+            "m_rawinput": false, "crosshair_code": "", "crosshair_cvars": [], "custom_commands": "",
+        }),
+    );
+    root.insert(
+        "launch_options".into(),
+        json!({
+            "novid": false, "nojoy": false, "high_priority": false, "allow_third_party": false,
+            "threads": "", "exec_autoexec": false, "custom_args": "",
+        }),
+    );
+    root.insert("theme_primary".into(), json!(""));
+    root.insert("theme_secondary".into(), json!(""));
+    root
+}
+
+fn recognise(content: &str) -> Result<Imported, String> {
+    let cat = tweaks::catalog();
+    let mut root = empty_config();
+    let mut found = 0;
+
+    // ps1_cmd blocks and info lines both start with "    # <text>"
+    for line in content.lines() {
+        let Some(text) = line.trim().strip_prefix("# ") else { continue };
+        let text = text.strip_prefix("Already applied, skipped: ").unwrap_or(text);
+        let tweak = cat
+            .tweaks
+            .iter()
+            .find(|t| t.label.as_deref() == Some(text) || t.info.as_deref() == Some(text) || t.name() == text);
+        if let Some(t) = tweak {
+            let section = cat.section(&t.section).map_or(t.section.as_str(), |s| s.config.as_str());
+            root[section][&t.id] = Value::Bool(true);
+            found += 1;
+        }
+    }
+
+    // Autoexec: $autoexecLines += '<line>'
+    let mut custom = Vec::new();
+    for line in content.lines() {
+        let Some(lit) = line.trim().strip_prefix("$autoexecLines +=") else { continue };
+        let Some(cfg_line) = ps::unquote(lit) else { continue };
+        if cfg_line.starts_with("//") {
+            continue;
+        }
+        root["autoexec"]["enabled"] = Value::Bool(true);
+        let (cvar, value) = cfg_line.split_once(' ').unwrap_or((cfg_line.as_str(), ""));
+        if AUTOEXEC_CVARS.contains(&cvar) {
+            root["autoexec"][cvar] = Value::String(value.trim().to_string());
+        } else if cfg_line == "m_rawinput 1" {
+            root["autoexec"]["m_rawinput"] = Value::Bool(true);
+        } else {
+            custom.push(cfg_line);
+        }
+        found += 1;
+    }
+    root["autoexec"]["custom_commands"] = Value::String(custom.join("\n"));
+
+    // Launch options: Set-Clipboard -Value '<options>'
+    if let Some(opts) = content
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("Set-Clipboard -Value"))
+        .and_then(ps::unquote)
+    {
+        let lo = &mut root["launch_options"];
+        let mut rest = Vec::new();
+        let mut tokens = opts.split_whitespace().peekable();
+        while let Some(tok) = tokens.next() {
+            match tok {
+                "+exec" if tokens.peek() == Some(&"autoexec.cfg") => {
+                    tokens.next();
+                    lo["exec_autoexec"] = Value::Bool(true);
+                }
+                "-novid" => lo["novid"] = Value::Bool(true),
+                "-nojoy" => lo["nojoy"] = Value::Bool(true),
+                "-high" => lo["high_priority"] = Value::Bool(true),
+                "-allow_third_party_software" => lo["allow_third_party"] = Value::Bool(true),
+                "-threads" if tokens.peek().is_some_and(|n| n.parse::<u32>().is_ok()) => {
+                    lo["threads"] = Value::String(tokens.next().unwrap_or_default().to_string());
+                }
+                other => rest.push(other),
+            }
+        }
+        lo["custom_args"] = Value::String(rest.join(" "));
+        found += 1;
+    }
+
+    if found == 0 {
+        return Err("No aim.camp settings found in this script".into());
+    }
+    Ok(Imported { source: ImportSource::Recognised, generator: None, section: None, config: Value::Object(root) })
+}
+
+/// UI toggle states (catalog keys plus the autoexec / launch checkboxes).
+pub fn toggles(config: &Value) -> BTreeMap<String, bool> {
+    let cat = tweaks::catalog();
+    let mut out = BTreeMap::new();
+    for tweak in &cat.tweaks {
+        out.insert(tweak.key.clone(), tweak.is_enabled(config));
+    }
+    for (key, section, field) in VALUE_TOGGLES {
+        let on = config[section][field].as_bool().unwrap_or(false);
+        out.insert(key.to_string(), on);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_round_trip() {
+        let mut config = Value::Object(empty_config());
+        config["windows"]["disable_game_dvr"] = Value::Bool(true);
+        config["autoexec"]["custom_commands"] = Value::String("say \"# aimcamp-config-data: x\"\n*/ '@".into());
+        let script = format!("\u{feff}{}\n# body\nWrite-Host 'hi'\n", header(&config, Some("windows")));
+
+        let imported = import(&script).unwrap();
+        assert!(matches!(imported.source, ImportSource::Header));
+        assert_eq!(imported.generator.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+        assert_eq!(imported.section.as_deref(), Some("windows"));
+        assert_eq!(imported.config, config);
+
+        let all = import(&header(&config, None)).unwrap();
+        assert_eq!(all.section, None);
+        assert!(header(&config, None).starts_with("# aimcamp-config: generator="));
+    }
+
+    #[test]
+    fn corrupt_header_is_an_error() {
+        let e = import("# aimcamp-config-data: not*base64!\n# Disable Game DVR (recording & captures)...\n").err().unwrap();
+        assert!(e.contains("corrupt"), "{}", e);
+        let not_json = base64::engine::general_purpose::STANDARD.encode("{\"config\": ");
+        assert!(import(&format!("{} {}\n", DATA_TAG, not_json)).is_err());
+    }
+
+    #[test]
+    fn recognises_a_legacy_script() {
+        let script = r#"
+# aim.camp CS2 optimizer
+Write-Host '[1/3] Windows' -ForegroundColor Cyan
+    # Disable Game DVR (recording & captures)...
+    Set-ItemProperty -Path 'HKCU:\System\GameConfigStore' -Name 'GameDVR_Enabled' -Value 0 -Type DWord -Force
+    # Already applied, skipped: Disable Nagle algorithm (reduce network latency)...
+    # Disable SVM Mode / VT-x in BIOS
+    # Disable Hyper-V in Windows
+    # Not a known tweak
+    $autoexecLines = @()
+    $autoexecLines += '// generated by aim.camp'
+    $autoexecLines += 'fps_max 0'
+    $autoexecLines += 'rate 786432'
+    $autoexecLines += 'm_rawinput 1'
+    $autoexecLines += 'say ''gl hf'''
+    $autoexecLines += 'bind "mwheelup" "+jump"'
+    Set-Clipboard -Value '-novid -high +exec autoexec.cfg -threads 8 +fps_max 0'
+"#;
+        let imported = import(script).unwrap();
+        assert!(matches!(imported.source, ImportSource::Recognised));
+        assert_eq!((imported.generator, imported.section), (None, None));
+        let c = &imported.config;
+
+        assert_eq!(c["windows"]["disable_game_dvr"], true);
+        assert_eq!(c["network"]["disable_nagle"], true);
+        assert_eq!(c["bios"]["disable_svm"], true);
+        assert_eq!(c["windows"]["disable_game_bar"], false);
+        let on = toggles(c).into_iter().filter(|(_, on)| *on).map(|(k, _)| k).collect::<Vec<_>>();
+        assert_eq!(on, ["ae_on", "ae_raw", "b_svm", "lo_exec", "lo_high", "lo_nvid", "n_nagle", "w_dvr"]);
+
+        let ae = &c["autoexec"];
+        assert_eq!((ae["fps_max"].as_str(), ae["rate"].as_str()), (Some("0"), Some("786432")));
+        assert_eq!(ae["cl_interp"], "0");
+        assert_eq!(ae["custom_commands"], "say 'gl hf'\nbind \"mwheelup\" \"+jump\"");
+
+        let lo = &c["launch_options"];
+        assert_eq!(lo["threads"], "8");
+        assert_eq!(lo["nojoy"], false);
+        assert_eq!(lo["custom_args"], "+fps_max 0");
+
+        // The rebuilt config is a complete v1 payload
+        assert!(crate::OptimizationConfig::try_from(imported.config).is_ok());
+    }
+
+    #[test]
+    fn script_without_settings_is_refused() {
+        assert!(import("Write-Host 'hello'\n# just a comment\n").is_err());
+    }
+}
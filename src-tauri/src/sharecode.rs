@@ -0,0 +1,76 @@
+// ────────────────────────────────────────────────────────────────────
+// CS2 share codes (CSGO-xxxxx-xxxxx-xxxxx-xxxxx-xxxxx). Crosshair and
+// match codes both carry 18 bytes written in base 57, least
+// significant digit first; what the bytes mean is up to the caller.
+// ────────────────────────────────────────────────────────────────────
+
+const ALPHABET: &[u8; 57] = b"ABCDEFGHJKLMNOPQRSTUVWXYZabcdefhijkmnopqrstuvwxyz23456789";
+pub const LEN: usize = 18;
+const DIGITS: usize = 25;
+
+/// The 18 bytes of a code, most significant first. Accepts the code with
+/// or without the CSGO- prefix and dashes.
+pub fn to_bytes(code: &str) -> Result<[u8; LEN], String> {
+    let trimmed = code.trim();
+    let body = trimmed.strip_prefix("CSGO-").unwrap_or(trimmed).replace('-', "");
+    if body.len() != DIGITS {
+        return Err(format!("Invalid share code: expected 25 characters, got {}", body.len()));
+    }
+
+    let mut bytes = [0u8; LEN];
+    for ch in body.bytes().rev() {
+        let digit = ALPHABET
+            .iter()
+            .position(|&c| c == ch)
+            .ok_or_else(|| format!("Invalid share code: unexpected character '{}'", ch as char))?;
+        let mut carry = digit as u32;
+        for b in bytes.iter_mut().rev() {
+            let v = *b as u32 * 57 + carry;
+            *b = v as u8;
+            carry = v >> 8;
+        }
+    }
+    Ok(bytes)
+}
+
+pub fn from_bytes(mut bytes: [u8; LEN]) -> String {
+    // Repeated division by 57; remainders come out least significant first
+    let mut digits = String::with_capacity(DIGITS);
+    for _ in 0..DIGITS {
+        let mut rem = 0u32;
+        for b in bytes.iter_mut() {
+            let v = (rem << 8) | *b as u32;
+            *b = (v / 57) as u8;
+            rem = v % 57;
+        }
+        digits.push(ALPHABET[rem as usize] as char);
+    }
+    format!(
+        "CSGO-{}-{}-{}-{}-{}",
+        &digits[0..5],
+        &digits[5..10],
+        &digits[10..15],
+        &digits[15..20],
+        &digits[20..25]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_round_trip() {
+        let code = "CSGO-GADqf-jjyJ8-cSP2r-smZRo-TO2xK";
+        let bytes = to_bytes(code).unwrap();
+        assert_eq!(from_bytes(bytes), code);
+        assert_eq!(to_bytes("GADqfjjyJ8cSP2rsmZRoTO2xK").unwrap(), bytes);
+        assert_eq!(from_bytes([0; LEN]), "CSGO-AAAAA-AAAAA-AAAAA-AAAAA-AAAAA");
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(to_bytes("CSGO-GADqf-jjyJ8").unwrap_err().contains("expected 25 characters, got 10"));
+        assert!(to_bytes("CSGO-GADqf-jjyJ8-cSP2r-smZRo-TO2xl").unwrap_err().contains("unexpected character 'l'"));
+    }
+}
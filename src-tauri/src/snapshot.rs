@@ -0,0 +1,566 @@
+// ────────────────────────────────────────────────────────────────────
+// Pre-apply snapshots – the elevated script records the current value
+// of everything the enabled tweaks touch (registry, services, power
+// settings, bcdedit) before changing it. restore_snapshot puts those
+// exact values back instead of assuming Windows defaults.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::ps;
+use crate::tweaks::{self, Touch, Tweak};
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub created: String,
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub section: Option<String>,
+    #[serde(default)]
+    pub tweaks: Vec<String>,
+    #[serde(default)]
+    pub entries: Vec<SnapshotEntry>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SnapshotEntry {
+    Registry {
+        tweak: String,
+        path: String,
+        name: String,
+        exists: bool,
+        #[serde(default)]
+        value: serde_json::Value,
+        #[serde(default, rename = "type")]
+        value_type: Option<String>,
+    },
+    Service {
+        tweak: String,
+        name: String,
+        start_type: String,
+        #[serde(default)]
+        delayed: bool,
+        #[serde(default)]
+        running: bool,
+    },
+    Powercfg {
+        tweak: String,
+        scheme: Option<String>,
+        subgroup: String,
+        setting: String,
+        ac: Option<i64>,
+        dc: Option<i64>,
+    },
+    Bcdedit {
+        tweak: String,
+        name: String,
+        value: Option<String>,
+    },
+    PowerScheme {
+        tweak: String,
+        guid: Option<String>,
+    },
+    Hibernate {
+        tweak: String,
+        enabled: Option<bool>,
+    },
+    MemoryCompression {
+        tweak: String,
+        enabled: Option<bool>,
+    },
+    Fsutil {
+        tweak: String,
+        name: String,
+        value: Option<i64>,
+    },
+    Other {
+        tweak: String,
+        what: String,
+    },
+}
+
+impl SnapshotEntry {
+    fn tweak(&self) -> &str {
+        match self {
+            SnapshotEntry::Registry { tweak, .. }
+            | SnapshotEntry::Service { tweak, .. }
+            | SnapshotEntry::Powercfg { tweak, .. }
+            | SnapshotEntry::Bcdedit { tweak, .. }
+            | SnapshotEntry::PowerScheme { tweak, .. }
+            | SnapshotEntry::Hibernate { tweak, .. }
+            | SnapshotEntry::MemoryCompression { tweak, .. }
+            | SnapshotEntry::Fsutil { tweak, .. }
+            | SnapshotEntry::Other { tweak, .. } => tweak,
+        }
+    }
+}
+
+/// Registry value kinds Get-ItemProperty reports and Set-ItemProperty takes.
+const REGISTRY_KINDS: [&str; 8] = ["String", "ExpandString", "Binary", "DWord", "MultiString", "QWord", "Unknown", "None"];
+const START_TYPES: [&str; 5] = ["Automatic", "Manual", "Disabled", "Boot", "System"];
+
+/// Service, bcdedit, fsutil and tweak names: letters, digits, `_`, `-`, `.`
+fn is_name(s: &str) -> bool {
+    !s.is_empty() && s.len() <= 128 && s.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.'))
+}
+
+/// powercfg subgroup / setting: a GUID or an alias such as SUB_PROCESSOR.
+fn is_power_id(s: &str) -> bool {
+    ps::guid("powercfg", s).is_ok() || (!s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'))
+}
+
+impl SnapshotEntry {
+    /// The file is written by an elevated script but read back from a
+    /// user-writable folder; everything that is not quoted on restore must
+    /// look like what the capture helpers write.
+    fn validate(&self) -> Result<(), String> {
+        let bad = |what: &str, value: &str| Err(format!("Snapshot entry of {} has an invalid {}: {}", self.tweak(), what, value));
+        if !is_name(self.tweak()) {
+            return Err(format!("Snapshot entry has an invalid tweak key: {}", self.tweak()));
+        }
+        match self {
+            SnapshotEntry::Registry { value, value_type, exists: true, .. } => {
+                let ty = value_type.as_deref().unwrap_or("String");
+                if !REGISTRY_KINDS.contains(&ty) {
+                    return bad("registry type", ty);
+                }
+                let ok = match value {
+                    serde_json::Value::String(_) | serde_json::Value::Number(_) | serde_json::Value::Null => true,
+                    serde_json::Value::Array(items) => items.iter().all(|i| i.is_string()),
+                    _ => false,
+                };
+                if !ok {
+                    return bad("registry value", &value.to_string());
+                }
+            }
+            SnapshotEntry::Registry { .. } => {}
+            SnapshotEntry::Service { name, start_type, .. } => {
+                if !is_name(name) {
+                    return bad("service name", name);
+                }
+                if !START_TYPES.contains(&start_type.as_str()) {
+                    return bad("start type", start_type);
+                }
+            }
+            SnapshotEntry::Powercfg { scheme, subgroup, setting, .. } => {
+                if let Some(scheme) = scheme {
+                    if ps::guid("scheme", scheme).is_err() {
+                        return bad("power scheme", scheme);
+                    }
+                }
+                for id in [subgroup, setting] {
+                    if !is_power_id(id) {
+                        return bad("powercfg setting", id);
+                    }
+                }
+            }
+            SnapshotEntry::Bcdedit { name, value, .. } => {
+                if !is_name(name) {
+                    return bad("bcdedit name", name);
+                }
+                if let Some(v) = value.as_deref().filter(|v| !is_name(v)) {
+                    return bad("bcdedit value", v);
+                }
+            }
+            SnapshotEntry::PowerScheme { guid: Some(guid), .. } if ps::guid("scheme", guid).is_err() => {
+                return bad("power scheme", guid);
+            }
+            SnapshotEntry::Fsutil { name, .. } if !is_name(name) => return bad("fsutil name", name),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Snapshot {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(bad) = self.tweaks.iter().find(|k| !is_name(k)) {
+            return Err(format!("Snapshot has an invalid tweak key: {}", bad));
+        }
+        if let Some(profile) = self.profile.as_deref().filter(|p| p.chars().any(|c| c.is_control())) {
+            return Err(format!("Snapshot has an invalid profile name: {:?}", profile));
+        }
+        self.entries.iter().try_for_each(SnapshotEntry::validate)
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub created: String,
+    pub profile: Option<String>,
+    pub section: Option<String>,
+    pub tweak_count: usize,
+    pub entry_count: usize,
+}
+
+// ── Capture ─────────────────────────────────────────────────────────
+
+const CAPTURE_HELPERS: &str = r#"
+# ---- Pre-apply snapshot ----
+$_snapEntries = New-Object System.Collections.ArrayList
+$_snapBcd = bcdedit /enum '{current}' 2>$null | Out-String
+$_snapScheme = if ((powercfg /getactivescheme 2>$null | Out-String) -match '([0-9a-fA-F-]{36})') { $Matches[1] } else { $null }
+
+function Snap-Reg($Tweak, $Path, $Name) {
+    if (-not $Path) { return }
+    $e = [ordered]@{ kind = 'registry'; tweak = $Tweak; path = "$Path"; name = $Name; exists = $false; value = $null; type = $null }
+    try {
+        $key = Get-Item -Path $Path -ErrorAction Stop
+        $v = $key.GetValue($Name, $null, 'DoNotExpandEnvironmentNames')
+        if ($null -ne $v) {
+            $e.exists = $true
+            $e.type = $key.GetValueKind($Name).ToString()
+            if ($v -is [byte[]]) { $e.value = [Convert]::ToBase64String($v) } else { $e.value = $v }
+        }
+    } catch {}
+    [void]$_snapEntries.Add($e)
+}
+
+function Snap-RegEach($Tweak, $Parent, $Name) {
+    Get-ChildItem -Path $Parent -ErrorAction SilentlyContinue | ForEach-Object { Snap-Reg $Tweak $_.PSPath $Name }
+}
+
+function Snap-Svc($Tweak, $Name) {
+    Get-Service -Name $Name -ErrorAction SilentlyContinue | ForEach-Object {
+        $delayed = (Get-ItemProperty "HKLM:\SYSTEM\CurrentControlSet\Services\$($_.Name)" -Name 'DelayedAutostart' -ErrorAction SilentlyContinue).DelayedAutostart -eq 1
+        [void]$_snapEntries.Add([ordered]@{ kind = 'service'; tweak = $Tweak; name = $_.Name; start_type = $_.StartType.ToString(); delayed = [bool]$delayed; running = ($_.Status -eq 'Running') })
+    }
+}
+
+function Snap-Pcfg($Tweak, $Sub, $Setting) {
+    $q = powercfg /query SCHEME_CURRENT $Sub $Setting 2>$null | Out-String
+    $ac = if ($q -match 'Current AC Power Setting Index:\s+0x([0-9a-fA-F]+)') { [convert]::ToInt64($Matches[1], 16) } else { $null }
+    $dc = if ($q -match 'Current DC Power Setting Index:\s+0x([0-9a-fA-F]+)') { [convert]::ToInt64($Matches[1], 16) } else { $null }
+    [void]$_snapEntries.Add([ordered]@{ kind = 'powercfg'; tweak = $Tweak; scheme = $_snapScheme; subgroup = $Sub; setting = $Setting; ac = $ac; dc = $dc })
+}
+
+function Snap-Bcd($Tweak, $Name) {
+    $v = if ($_snapBcd -match "(?m)^$Name\s+(\S+)") { $Matches[1] } else { $null }
+    [void]$_snapEntries.Add([ordered]@{ kind = 'bcdedit'; tweak = $Tweak; name = $Name; value = $v })
+}
+
+function Snap-Fsutil($Tweak, $Name) {
+    $q = fsutil behavior query $Name 2>$null | Out-String
+    $v = if ($q -match '=\s*(\d+)') { [int64]$Matches[1] } else { $null }
+    [void]$_snapEntries.Add([ordered]@{ kind = 'fsutil'; tweak = $Tweak; name = $Name; value = $v })
+}
+"#;
+
+fn capture_line(tweak: &Tweak, touch: &Touch) -> String {
+    let t = ps::quote(&tweak.key);
+    let path_arg = |p: &str| if p.starts_with('$') { p.to_string() } else { ps::quote(p) };
+    match touch {
+        Touch::Registry { path, name } => format!("Snap-Reg {} {} {}", t, path_arg(path), ps::quote(name)),
+        Touch::RegistryEach { parent, name } => format!("Snap-RegEach {} {} {}", t, ps::quote(parent), ps::quote(name)),
+        Touch::Service { name } => format!("Snap-Svc {} {}", t, ps::quote(name)),
+        Touch::Powercfg { subgroup, setting } => format!("Snap-Pcfg {} {} {}", t, subgroup, setting),
+        Touch::Bcdedit { name } => format!("Snap-Bcd {} {}", t, ps::quote(name)),
+        Touch::Fsutil { name } => format!("Snap-Fsutil {} {}", t, ps::quote(name)),
+        Touch::PowerScheme => format!(
+            "[void]$_snapEntries.Add([ordered]@{{ kind = 'power_scheme'; tweak = {}; guid = $_snapScheme }})",
+            t
+        ),
+        Touch::Hibernate => format!(
+            "[void]$_snapEntries.Add([ordered]@{{ kind = 'hibernate'; tweak = {}; enabled = ((Get-ItemProperty 'HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Power' -Name 'HibernateEnabled' -ErrorAction SilentlyContinue).HibernateEnabled -eq 1) }})",
+            t
+        ),
+        Touch::MemoryCompression => format!(
+            "[void]$_snapEntries.Add([ordered]@{{ kind = 'memory_compression'; tweak = {}; enabled = (Get-MMAgent -ErrorAction SilentlyContinue).MemoryCompression }})",
+            t
+        ),
+        Touch::Other { what } => format!(
+            "[void]$_snapEntries.Add([ordered]@{{ kind = 'other'; tweak = {}; what = {} }})",
+            t,
+            ps::quote(what)
+        ),
+    }
+}
+
+/// PowerShell block that records the current state of every value the
+/// enabled tweaks are about to change and writes it to `snapshot_path`.
+pub fn capture_script(
+    config: &serde_json::Value,
+    section: Option<&str>,
+    skip: &[String],
+    id: &str,
+    profile: Option<&str>,
+    snapshot_path: &str,
+) -> String {
+    let cat = tweaks::catalog();
+    let enabled: Vec<&Tweak> = cat.selected(config, section, skip).collect();
+
+    let mut s = String::from(CAPTURE_HELPERS);
+
+    // Sections whose touches refer to variables (e.g. $nvPath) need their preamble
+    s.push_str(&cat.variable_preambles(&enabled));
+
+    for tweak in &enabled {
+        for touch in &tweak.touches {
+            s.push_str(&capture_line(tweak, touch));
+            s.push('\n');
+        }
+    }
+
+    let keys: Vec<String> = enabled.iter().map(|t| ps::quote(&t.key)).collect();
+    s.push_str(&format!(
+        "$_snap = [ordered]@{{\n    id       = {}\n    created  = (Get-Date).ToString('o')\n    profile  = {}\n    section  = {}\n    tweaks   = @({})\n    entries  = @($_snapEntries)\n}}\n",
+        ps::quote(id),
+        profile.map_or("$null".to_string(), ps::quote),
+        section.map_or("$null".to_string(), ps::quote),
+        keys.join(",")
+    ));
+    s.push_str(&format!(
+        "$_snap | ConvertTo-Json -Depth 5 | Set-Content -Path {} -Encoding UTF8\n",
+        ps::quote(snapshot_path)
+    ));
+    s
+}
+
+// ── Restore ─────────────────────────────────────────────────────────
+
+fn registry_value_ps(value: &serde_json::Value, value_type: &str) -> String {
+    match (value_type, value) {
+        ("Binary", serde_json::Value::String(b64)) => format!("([Convert]::FromBase64String({}))", ps::quote(b64)),
+        ("MultiString", serde_json::Value::Array(items)) => {
+            let parts: Vec<String> = items
+                .iter()
+                .map(|i| ps::quote(i.as_str().unwrap_or_default()))
+                .collect();
+            format!("@({})", parts.join(","))
+        }
+        (_, serde_json::Value::String(v)) => ps::quote(v),
+        (_, serde_json::Value::Number(n)) => n.to_string(),
+        _ => "$null".to_string(),
+    }
+}
+
+fn restore_lines(entry: &SnapshotEntry) -> Vec<String> {
+    match entry {
+        SnapshotEntry::Registry { path, name, exists, value, value_type, .. } => {
+            if *exists {
+                let ty = value_type.as_deref().unwrap_or("String");
+                vec![
+                    format!("Ensure-RegPath {}", ps::quote(path)),
+                    format!(
+                        "Set-ItemProperty -Path {} -Name {} -Value {} -Type {} -Force",
+                        ps::quote(path),
+                        ps::quote(name),
+                        registry_value_ps(value, ty),
+                        ps::quote(ty)
+                    ),
+                ]
+            } else {
+                vec![format!(
+                    "Remove-ItemProperty -Path {} -Name {} -ErrorAction SilentlyContinue",
+                    ps::quote(path),
+                    ps::quote(name)
+                )]
+            }
+        }
+        SnapshotEntry::Service { name, start_type, delayed, running, .. } => {
+            let mut lines = Vec::new();
+            if *delayed {
+                lines.push(format!("sc.exe config {} start= delayed-auto | Out-Null", ps::quote(name)));
+            } else {
+                lines.push(format!(
+                    "Set-Service -Name {} -StartupType {} -ErrorAction SilentlyContinue",
+                    ps::quote(name),
+                    ps::quote(start_type)
+                ));
+            }
+            if *running {
+                lines.push(format!("Start-Service -Name {} -ErrorAction SilentlyContinue", ps::quote(name)));
+            }
+            lines
+        }
+        SnapshotEntry::Powercfg { scheme, subgroup, setting, ac, dc, .. } => {
+            let ids = format!(
+                "{} {} {}",
+                ps::quote(scheme.as_deref().unwrap_or("SCHEME_CURRENT")),
+                ps::quote(subgroup),
+                ps::quote(setting)
+            );
+            let mut lines = Vec::new();
+            if let Some(ac) = ac {
+                lines.push(format!("powercfg /setacvalueindex {} {}", ids, ac));
+            }
+            if let Some(dc) = dc {
+                lines.push(format!("powercfg /setdcvalueindex {} {}", ids, dc));
+            }
+            if !lines.is_empty() {
+                lines.push("powercfg /setactive SCHEME_CURRENT".to_string());
+            }
+            lines
+        }
+        SnapshotEntry::Bcdedit { name, value, .. } => match value {
+            Some(v) => vec![format!("bcdedit /set {} {} 2>$null", ps::quote(name), ps::quote(v))],
+            None => vec![format!("bcdedit /deletevalue {} 2>$null", ps::quote(name))],
+        },
+        SnapshotEntry::PowerScheme { guid, .. } => guid
+            .iter()
+            .map(|g| format!("powercfg -setactive {}", ps::quote(g)))
+            .collect(),
+        SnapshotEntry::Hibernate { enabled, .. } => match enabled {
+            Some(true) => vec!["powercfg -h on".to_string()],
+            Some(false) => vec!["powercfg -h off".to_string()],
+            None => vec![],
+        },
+        SnapshotEntry::MemoryCompression { enabled, .. } => match enabled {
+            Some(true) => vec!["Enable-MMAgent -MemoryCompression -ErrorAction SilentlyContinue".to_string()],
+            Some(false) => vec!["Disable-MMAgent -MemoryCompression -ErrorAction SilentlyContinue".to_string()],
+            None => vec![],
+        },
+        SnapshotEntry::Fsutil { name, value, .. } => value
+            .iter()
+            .map(|v| format!("fsutil behavior set {} {} 2>$null", ps::quote(name), v))
+            .collect(),
+        SnapshotEntry::Other { .. } => vec![],
+    }
+}
+
+/// Commands restoring a snapshot, grouped per tweak in capture order:
+/// (tweak key, display name, commands). Effects that could not be read back
+/// run the tweak's revert lines first, exact values are applied after.
+pub fn restore_plan(snap: &Snapshot) -> Vec<(String, String, Vec<String>)> {
+    let cat = tweaks::catalog();
+    let mut plan = Vec::new();
+    for key in &snap.tweaks {
+        let tweak = cat.find(key);
+        let entries: Vec<&SnapshotEntry> = snap.entries.iter().filter(|e| e.tweak() == key).collect();
+        let mut lines = Vec::new();
+        if entries.iter().any(|e| matches!(e, SnapshotEntry::Other { .. })) {
+            if let Some(t) = tweak {
+                lines.extend(t.revert.iter().cloned());
+            }
+        }
+        for e in entries {
+            lines.extend(restore_lines(e));
+        }
+        if !lines.is_empty() {
+            let name = tweak.map(|t| t.name().to_string()).unwrap_or_else(|| key.clone());
+            plan.push((key.clone(), name, lines));
+        }
+    }
+    plan
+}
+
+// ── Storage ─────────────────────────────────────────────────────────
+
+pub fn load(dir: &std::path::Path, id: &str) -> Result<Snapshot, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid snapshot id: {}", id));
+    }
+    let path = dir.join(format!("{}.json", id));
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Snapshot {} not found: {}", id, e))?;
+    parse(&raw)
+}
+
+/// Parse and validate a snapshot file.
+pub fn parse(raw: &str) -> Result<Snapshot, String> {
+    let snap: Snapshot = serde_json::from_str(raw.trim_start_matches('\u{feff}'))
+        .map_err(|e| format!("Snapshot parse error: {}", e))?;
+    snap.validate()?;
+    Ok(snap)
+}
+
+pub fn list(dir: &std::path::Path) -> Result<Vec<SnapshotInfo>, String> {
+    let mut out = Vec::new();
+    let rd = match std::fs::read_dir(dir) {
+        Ok(rd) => rd,
+        Err(_) => return Ok(out),
+    };
+    for entry in rd.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let id = match path.file_stem().and_then(|s| s.to_str()) {
+            Some(id) => id.to_string(),
+            None => continue,
+        };
+        if let Ok(snap) = load(dir, &id) {
+            out.push(SnapshotInfo {
+                id: snap.id,
+                created: snap.created,
+                profile: snap.profile,
+                section: snap.section,
+                tweak_count: snap.tweaks.len(),
+                entry_count: snap.entries.len(),
+            });
+        }
+    }
+    out.sort_by(|a, b| b.created.cmp(&a.created));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(entry: serde_json::Value) -> String {
+        serde_json::json!({
+            "id": "20261017-120000",
+            "created": "2026-10-17T12:00:00",
+            "tweaks": ["w_dvr"],
+            "entries": [entry],
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn restores_captured_values() {
+        let snap = parse(&snapshot(serde_json::json!({
+            "kind": "registry", "tweak": "w_dvr", "path": "HKCU:\\System\\GameConfigStore",
+            "name": "GameDVR_Enabled", "exists": true, "value": 1, "type": "DWord"
+        })))
+        .unwrap();
+        let plan = restore_plan(&snap);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(
+            plan[0].2[1],
+            "Set-ItemProperty -Path 'HKCU:\\System\\GameConfigStore' -Name 'GameDVR_Enabled' -Value 1 -Type 'DWord' -Force"
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_entries() {
+        let evil = "x; Start-Process calc";
+        let entries = [
+            serde_json::json!({ "kind": "registry", "tweak": "w_dvr", "path": "HKCU:\\X", "name": "Y", "exists": true, "value": 1, "type": evil }),
+            serde_json::json!({ "kind": "registry", "tweak": "w_dvr", "path": "HKCU:\\X", "name": "Y", "exists": true, "value": { "a": 1 }, "type": "DWord" }),
+            serde_json::json!({ "kind": "service", "tweak": "w_dvr", "name": evil, "start_type": "Manual" }),
+            serde_json::json!({ "kind": "service", "tweak": "w_dvr", "name": "SysMain", "start_type": evil }),
+            serde_json::json!({ "kind": "powercfg", "tweak": "w_dvr", "scheme": evil, "subgroup": "SUB_PROCESSOR", "setting": "PROCTHROTTLEMIN", "ac": 5, "dc": 5 }),
+            serde_json::json!({ "kind": "powercfg", "tweak": "w_dvr", "scheme": null, "subgroup": "SUB_PROCESSOR", "setting": evil, "ac": 5, "dc": 5 }),
+            serde_json::json!({ "kind": "bcdedit", "tweak": "w_dvr", "name": "useplatformclock", "value": evil }),
+            serde_json::json!({ "kind": "bcdedit", "tweak": "w_dvr", "name": evil, "value": null }),
+            serde_json::json!({ "kind": "power_scheme", "tweak": "w_dvr", "guid": evil }),
+            serde_json::json!({ "kind": "fsutil", "tweak": "w_dvr", "name": evil, "value": 1 }),
+            serde_json::json!({ "kind": "other", "tweak": "$(Start-Process calc)", "what": "x" }),
+        ];
+        for entry in entries {
+            assert!(parse(&snapshot(entry.clone())).is_err(), "accepted {}", entry);
+        }
+
+        let mut snap: serde_json::Value = serde_json::from_str(&snapshot(serde_json::json!({ "kind": "other", "tweak": "w_dvr", "what": "x" }))).unwrap();
+        snap["tweaks"] = serde_json::json!(["w_dvr\"; Start-Process calc; \""]);
+        assert!(parse(&snap.to_string()).is_err());
+        snap["tweaks"] = serde_json::json!(["w_dvr"]);
+        snap["profile"] = serde_json::json!("Pro\nStart-Process calc");
+        assert!(parse(&snap.to_string()).is_err());
+    }
+
+    #[test]
+    fn quotes_every_restored_field() {
+        let snap = parse(&snapshot(serde_json::json!({
+            "kind": "powercfg", "tweak": "w_dvr", "scheme": "381b4222-f694-41f0-9685-ff5bb260df2e",
+            "subgroup": "SUB_PROCESSOR", "setting": "PROCTHROTTLEMIN", "ac": 5, "dc": null
+        })))
+        .unwrap();
+        let lines = restore_lines(&snap.entries[0]);
+        assert_eq!(lines[0], "powercfg /setacvalueindex '381b4222-f694-41f0-9685-ff5bb260df2e' 'SUB_PROCESSOR' 'PROCTHROTTLEMIN' 5");
+    }
+}
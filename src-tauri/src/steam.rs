@@ -0,0 +1,273 @@
+// ────────────────────────────────────────────────────────────────────
+// Steam install discovery, accounts and per-account launch options.
+// Library folders come from steamapps/libraryfolders.vdf, accounts from
+// config/loginusers.vdf; each account keeps its launch options in
+// userdata/<account>/config/localconfig.vdf and its CS2 cfgs in
+// userdata/<account>/730/local/cfg.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::vdf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const CS2_APP_ID: &str = "730";
+pub const CS2_INSTALL_SUBDIR: &str = "steamapps\\common\\Counter-Strike Global Offensive";
+/// SteamID64 of account number 0; userdata folders are named by the
+/// account number (SteamID3).
+const STEAM_ID64_BASE: u64 = 76561197960265728;
+const LAUNCH_OPTIONS: [&str; 7] =
+    ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", CS2_APP_ID, "LaunchOptions"];
+
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct Library {
+    pub path: PathBuf,
+    /// App ids installed there; empty for the old format, which doesn't list them
+    pub apps: Vec<String>,
+}
+
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct Account {
+    pub steam_id64: String,
+    /// SteamID3 account number, the name of the userdata folder
+    pub account_id: u32,
+    pub account_name: String,
+    pub persona_name: String,
+    /// Unix time of the last sign-in
+    pub last_login: i64,
+    pub most_recent: bool,
+    /// userdata/<account>/730/local/cfg, once CS2 has run on the account
+    pub cfg_dir: Option<String>,
+}
+
+/// Steam's install folder, from the registry.
+pub fn root() -> Option<PathBuf> {
+    if !cfg!(windows) {
+        return None;
+    }
+    for (key, name) in [
+        ("HKCU:\\Software\\Valve\\Steam", "SteamPath"),
+        ("HKLM:\\SOFTWARE\\WOW6432Node\\Valve\\Steam", "InstallPath"),
+    ] {
+        let query = format!(
+            "(Get-ItemProperty -Path '{}' -Name '{}' -ErrorAction SilentlyContinue).{}",
+            key, name, name
+        );
+        if let Ok(out) = Command::new("powershell").args(["-NoProfile", "-Command", &query]).output() {
+            // SteamPath is written with forward slashes
+            let path = String::from_utf8_lossy(&out.stdout).trim().replace('/', "\\");
+            if !path.is_empty() && Path::new(&path).is_dir() {
+                return Some(PathBuf::from(path));
+            }
+        }
+    }
+    None
+}
+
+/// Library folders listed in libraryfolders.vdf, current and pre-2021
+/// layouts (`"1" "D:\\SteamLibrary"`).
+pub fn libraries(doc: &vdf::Document) -> Vec<Library> {
+    let Some(root) = doc.root.get("libraryfolders").and_then(vdf::Value::as_object) else {
+        return Vec::new();
+    };
+    root.items
+        .iter()
+        .filter(|i| i.key.text.chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|i| match &i.value {
+            vdf::Value::Str(t) => Some(Library { path: PathBuf::from(&t.text), apps: Vec::new() }),
+            vdf::Value::Obj(o) => Some(Library {
+                path: PathBuf::from(o.get("path")?.as_str()?),
+                apps: o
+                    .get("apps")
+                    .and_then(vdf::Value::as_object)
+                    .map(|apps| apps.items.iter().map(|a| a.key.text.clone()).collect())
+                    .unwrap_or_default(),
+            }),
+        })
+        .collect()
+}
+
+/// The library CS2 is installed in: the one listing app 730, else the
+/// first whose install folder exists (old layout).
+pub fn cs2_library(libs: &[Library], exists: impl Fn(&Path) -> bool) -> Option<&Library> {
+    libs.iter()
+        .find(|l| l.apps.iter().any(|a| a == CS2_APP_ID))
+        .or_else(|| libs.iter().find(|l| exists(&l.path.join(CS2_INSTALL_SUBDIR))))
+}
+
+/// CS2's install folder, for the Steam install at `root`.
+pub fn cs2_dir(root: &Path) -> Option<PathBuf> {
+    let mut libs = std::fs::read_to_string(root.join("steamapps").join("libraryfolders.vdf"))
+        .ok()
+        .and_then(|t| vdf::parse(&t).ok())
+        .map(|doc| libraries(&doc))
+        .unwrap_or_default();
+    // The old layout leaves Steam's own folder out of the list
+    if !libs.iter().any(|l| l.path == root) {
+        libs.push(Library { path: root.to_path_buf(), apps: Vec::new() });
+    }
+    cs2_library(&libs, |p| p.is_dir())
+        .map(|l| l.path.join(CS2_INSTALL_SUBDIR))
+        .filter(|p| p.is_dir())
+}
+
+/// Whether a process with this image name (e.g. `steam.exe`) is running.
+pub fn is_running(image: &str) -> bool {
+    if !cfg!(windows) {
+        return false;
+    }
+    Command::new("tasklist")
+        .args(["/FI", &format!("IMAGENAME eq {}", image), "/NH"])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_ascii_lowercase().contains(&image.to_ascii_lowercase()))
+        .unwrap_or(false)
+}
+
+pub fn account_id(steam_id64: &str) -> Result<u32, String> {
+    steam_id64
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|id| id.checked_sub(STEAM_ID64_BASE))
+        .and_then(|id| u32::try_from(id).ok())
+        .ok_or_else(|| format!("Invalid SteamID64: {}", steam_id64))
+}
+
+/// Accounts listed in loginusers.vdf, most recent sign-in first.
+pub fn accounts(doc: &vdf::Document) -> Vec<Account> {
+    let Some(users) = doc.root.get("users").and_then(vdf::Value::as_object) else {
+        return Vec::new();
+    };
+    let mut out: Vec<Account> = users
+        .items
+        .iter()
+        .filter_map(|u| {
+            let fields = u.value.as_object()?;
+            let field = |k: &str| fields.get(k).and_then(vdf::Value::as_str).unwrap_or("").to_string();
+            Some(Account {
+                steam_id64: u.key.text.clone(),
+                account_id: account_id(&u.key.text).ok()?,
+                account_name: field("AccountName"),
+                persona_name: field("PersonaName"),
+                last_login: field("Timestamp").parse().unwrap_or(0),
+                most_recent: field("MostRecent") == "1",
+                cfg_dir: None,
+            })
+        })
+        .collect();
+    out.sort_by_key(|a| std::cmp::Reverse(a.last_login));
+    out
+}
+
+/// Accounts that have signed in on this machine, with their CS2 cfg folders.
+pub fn list_accounts(root: &Path) -> Result<Vec<Account>, String> {
+    let path = root.join("config").join("loginusers.vdf");
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let doc = vdf::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(accounts(&doc)
+        .into_iter()
+        .map(|mut a| {
+            let dir = cfg_dir(root, a.account_id);
+            a.cfg_dir = dir.is_dir().then(|| dir.to_string_lossy().to_string());
+            a
+        })
+        .collect())
+}
+
+pub fn cfg_dir(root: &Path, account_id: u32) -> PathBuf {
+    root.join("userdata").join(account_id.to_string()).join(CS2_APP_ID).join("local").join("cfg")
+}
+
+pub fn localconfig(root: &Path, account_id: u32) -> PathBuf {
+    root.join("userdata").join(account_id.to_string()).join("config").join("localconfig.vdf")
+}
+
+/// localconfig.vdf of every account that has signed in on this machine.
+pub fn localconfigs(root: &Path) -> Vec<PathBuf> {
+    let mut out: Vec<PathBuf> = std::fs::read_dir(root.join("userdata"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path().join("config").join("localconfig.vdf"))
+                .filter(|p| p.is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    out.sort();
+    out
+}
+
+pub fn launch_options(doc: &vdf::Document) -> Option<&str> {
+    doc.get_str(&LAUNCH_OPTIONS)
+}
+
+/// Rewrite CS2's launch options in one localconfig.vdf, leaving the rest
+/// of the file as it was. The previous file is copied next to it first;
+/// returns the backup's path, or None when nothing had to change.
+pub fn write_launch_options(path: &Path, options: &str) -> Result<Option<PathBuf>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let mut doc = vdf::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if launch_options(&doc) == Some(options) {
+        return Ok(None);
+    }
+    doc.set(&LAUNCH_OPTIONS, options)?;
+    vdf::save(path, &doc).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(text: &str) -> vdf::Document {
+        vdf::parse(text).unwrap()
+    }
+
+    #[test]
+    fn finds_cs2_library() {
+        let libs = libraries(&fixture(include_str!("../fixtures/steam/libraryfolders.vdf")));
+        assert_eq!(libs.len(), 2);
+        assert_eq!(libs[0].path, PathBuf::from("C:\\Program Files (x86)\\Steam"));
+        let cs2 = cs2_library(&libs, |_| false).unwrap();
+        assert_eq!(cs2.path, PathBuf::from("D:\\SteamLibrary"));
+    }
+
+    #[test]
+    fn legacy_layout_checks_the_disk() {
+        let libs = libraries(&fixture(include_str!("../fixtures/steam/libraryfolders_legacy.vdf")));
+        assert_eq!(libs.iter().map(|l| l.path.clone()).collect::<Vec<_>>(),
+            vec![PathBuf::from("E:\\SteamLibrary"), PathBuf::from("F:\\Games\\Steam")]);
+        let cs2 = cs2_library(&libs, |p| p.starts_with("F:\\Games\\Steam")).unwrap();
+        assert_eq!(cs2.path, PathBuf::from("F:\\Games\\Steam"));
+        assert!(cs2_library(&libs, |_| false).is_none());
+    }
+
+    #[test]
+    fn lists_accounts_from_loginusers() {
+        let accounts = accounts(&fixture(include_str!("../fixtures/steam/loginusers.vdf")));
+        assert_eq!(accounts.len(), 2);
+        let main = &accounts[0];
+        assert_eq!(main.steam_id64, "76561198012345678");
+        assert_eq!(main.account_id, 52079950);
+        assert_eq!(main.persona_name, "player");
+        assert_eq!(main.last_login, 1718000000);
+        assert!(main.most_recent);
+        assert_eq!(accounts[1].account_name, "smurf_acc");
+        assert!(account_id("123").is_err());
+        assert!(account_id("../../76561198012345678").is_err());
+    }
+
+    #[test]
+    fn writes_launch_options_with_backup() {
+        let dir = std::env::temp_dir().join(format!("aimcamp-steam-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("localconfig.vdf");
+        let original = include_str!("../fixtures/steam/localconfig_no_cs2.vdf");
+        std::fs::write(&path, original).unwrap();
+
+        let backup = write_launch_options(&path, "-novid -high").unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+        let written = fixture(&std::fs::read_to_string(&path).unwrap());
+        assert_eq!(launch_options(&written), Some("-novid -high"));
+        assert!(write_launch_options(&path, "-novid -high").unwrap().is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
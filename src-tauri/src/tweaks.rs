@@ -0,0 +1,653 @@
+// ────────────────────────────────────────────────────────────────────
+// Tweak catalog – one entry per optimization. The same data drives the
+// generated .ps1 (apply), the system state checker (verify), the revert
+// script and the advisor knowledge base. The catalog itself lives in
+// tweaks.json next to this file and is embedded at compile time.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::ps;
+use std::sync::OnceLock;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Catalog {
+    pub sections: Vec<Section>,
+    pub tweaks: Vec<Tweak>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Section {
+    /// Section filter id used by generate_script ("bios", "launch", ...)
+    pub id: String,
+    /// Field name of the matching sub-struct in OptimizationConfig
+    pub config: String,
+    /// GPU vendor ("nvidia", "amd", "intel") for driver sections; the
+    /// script leaves them out unless one of their toggles is set
+    #[serde(default)]
+    pub vendor: Option<String>,
+    pub num: u8,
+    pub title: String,
+    pub icon: String,
+    /// Raw lines emitted right after the section header
+    #[serde(default)]
+    pub preamble: Vec<String>,
+    /// Lines the state checker runs before this section's verifications
+    #[serde(default)]
+    pub check_preamble: Vec<String>,
+    /// State keys that belong to the section but are not toggles
+    #[serde(default)]
+    pub checks: Vec<SectionCheck>,
+    /// Printed by the revert script for value-driven sections
+    #[serde(default)]
+    pub revert_note: Option<String>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct SectionCheck {
+    pub key: String,
+    #[serde(default)]
+    pub verify: Verify,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Tweak {
+    /// Config field name inside the section (e.g. "disable_game_dvr")
+    pub id: String,
+    pub section: String,
+    /// Short UI / state key (e.g. "w_dvr")
+    pub key: String,
+    /// Windows builds / editions the tweak applies to (all when absent)
+    #[serde(default)]
+    pub os: Option<OsRequirement>,
+    /// Progress label; without one the apply lines are emitted as-is
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Informational line printed before the commands (BIOS hints etc.)
+    #[serde(default)]
+    pub info: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub apply: Vec<String>,
+    #[serde(default)]
+    pub verify: Verify,
+    #[serde(default)]
+    pub revert: Vec<String>,
+    /// System values the apply commands change, captured before applying
+    #[serde(default)]
+    pub touches: Vec<Touch>,
+    pub risk: Risk,
+    pub reversibility: Reversibility,
+    #[serde(default)]
+    pub restart: bool,
+}
+
+/// High-risk tweaks are only applied when the caller acknowledges them
+/// by key (see `unacknowledged`).
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Risk {
+    /// Cosmetic or per-user settings
+    Low,
+    /// System-wide settings that can cost features (services, drivers, TCP)
+    Medium,
+    /// Boot configuration, security mitigations, file system and device
+    /// power changes
+    High,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Reversibility {
+    /// Undone by the revert script / snapshot restore (or changes nothing)
+    Full,
+    /// Only part of the change is undone automatically
+    Partial,
+    /// Has to be undone by hand (BIOS, settings without revert lines)
+    Manual,
+    /// Can't be undone: deleted caches, flushed DNS, killed processes
+    OneWay,
+}
+
+/// Editions are matched as prefixes of the EditionID registry value, so
+/// "Professional" also covers ProfessionalN and ProfessionalWorkstation.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct OsRequirement {
+    #[serde(default)]
+    pub min_build: Option<u32>,
+    #[serde(default)]
+    pub max_build: Option<u32>,
+    #[serde(default)]
+    pub editions: Vec<String>,
+}
+
+/// A system value a tweak modifies. `Other` marks effects that cannot be
+/// read back; restoring those falls back to the tweak's revert lines.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Touch {
+    Registry { path: String, name: String },
+    /// Same value name under every subkey of `parent` (per-adapter keys)
+    RegistryEach { parent: String, name: String },
+    Service { name: String },
+    Powercfg { subgroup: String, setting: String },
+    Bcdedit { name: String },
+    PowerScheme,
+    Hibernate,
+    MemoryCompression,
+    Fsutil { name: String },
+    Other { what: String },
+}
+
+/// How the state checker decides whether a tweak is currently applied.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Verify {
+    #[default]
+    None,
+    Registry { path: String, name: String, expected: serde_json::Value },
+    Service { name: String },
+    Powercfg { subgroup: String, setting: String, expected: i64 },
+    Script { lines: Vec<String> },
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Get-GpuBarMb / Get-DeviceBarMb, shared by generated scripts, the state
+/// checker and the hardware scan.
+pub const GPU_BAR_PS1: &str = include_str!("gpu_bar.ps1");
+
+pub fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| {
+        serde_json::from_str(include_str!("tweaks.json")).expect("tweaks.json is malformed")
+    })
+}
+
+impl Catalog {
+    pub fn section(&self, id: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.id == id)
+    }
+
+    pub fn find(&self, key: &str) -> Option<&Tweak> {
+        self.tweaks.iter().find(|t| t.key == key)
+    }
+
+    pub fn section_tweaks<'a>(&'a self, section: &'a str) -> impl Iterator<Item = &'a Tweak> + 'a {
+        self.tweaks.iter().filter(move |t| t.section == section)
+    }
+
+    /// Preamble lines of the sections whose registry touches among
+    /// `tweaks` name a key through a variable (e.g. `$nvPath`).
+    pub fn variable_preambles(&self, tweaks: &[&Tweak]) -> String {
+        let mut s = String::new();
+        for sec in &self.sections {
+            let needs_preamble = tweaks.iter().any(|t| {
+                t.section == sec.id
+                    && t.touches.iter().any(|x| matches!(x, Touch::Registry { path, .. } if path.starts_with('$')))
+            });
+            if needs_preamble {
+                for line in &sec.preamble {
+                    s.push_str(line);
+                    s.push('\n');
+                }
+            }
+        }
+        s
+    }
+
+    /// Tweaks a script for `config` applies: enabled, in `section` (all
+    /// when None) and not skipped.
+    pub fn selected<'a>(
+        &'a self,
+        config: &'a serde_json::Value,
+        section: Option<&'a str>,
+        skip: &'a [String],
+    ) -> impl Iterator<Item = &'a Tweak> + 'a {
+        self.tweaks.iter().filter(move |t| {
+            section.map_or(true, |s| s == t.section) && !skip.contains(&t.key) && t.is_enabled(config)
+        })
+    }
+}
+
+/// A high-risk tweak waiting for acknowledgement.
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct RiskNotice {
+    pub key: String,
+    pub name: String,
+    pub risk: Risk,
+    pub reversibility: Reversibility,
+    pub restart: bool,
+}
+
+impl From<&Tweak> for RiskNotice {
+    fn from(t: &Tweak) -> Self {
+        Self {
+            key: t.key.clone(),
+            name: t.name().to_string(),
+            risk: t.risk,
+            reversibility: t.reversibility,
+            restart: t.restart,
+        }
+    }
+}
+
+/// High-risk tweaks among `tweaks` whose keys `acknowledged` doesn't list.
+pub fn unacknowledged<'a>(tweaks: impl Iterator<Item = &'a Tweak>, acknowledged: &[String]) -> Vec<RiskNotice> {
+    tweaks
+        .filter(|t| t.risk == Risk::High && !acknowledged.contains(&t.key))
+        .map(RiskNotice::from)
+        .collect()
+}
+
+impl Tweak {
+    /// Field of the section's sub-struct in OptimizationConfig.
+    fn section_field(&self) -> &str {
+        catalog()
+            .section(&self.section)
+            .map(|s| s.config.as_str())
+            .unwrap_or(self.section.as_str())
+    }
+
+    pub fn is_enabled(&self, config: &serde_json::Value) -> bool {
+        config
+            .get(self.section_field())
+            .and_then(|s| s.get(&self.id))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Set the toggle in an OptimizationConfig value; a missing section
+    /// is left alone.
+    pub fn set_enabled(&self, config: &mut serde_json::Value, on: bool) {
+        if let Some(section) = config.get_mut(self.section_field()).and_then(|s| s.as_object_mut()) {
+            section.insert(self.id.clone(), serde_json::Value::Bool(on));
+        }
+    }
+
+    /// Human readable name without the trailing progress ellipsis.
+    pub fn name(&self) -> &str {
+        self.label
+            .as_deref()
+            .or(self.info.as_deref())
+            .unwrap_or(&self.id)
+            .trim_end_matches("...")
+    }
+
+    /// Only prints instructions for a manual BIOS change.
+    pub fn is_manual(&self) -> bool {
+        self.label.is_none() && self.info.is_some()
+    }
+
+    /// Anything outside HKCU (HKLM, services, bcdedit, powercfg) needs an
+    /// elevated shell; tweaks without declared touches are assumed to.
+    pub fn needs_admin(&self) -> bool {
+        if self.is_manual() {
+            return false;
+        }
+        if self.touches.is_empty() {
+            return !self.apply.is_empty();
+        }
+        self.touches
+            .iter()
+            .any(|t| !matches!(t, Touch::Registry { path, .. } if path.starts_with("HKCU:")))
+    }
+}
+
+impl OsRequirement {
+    /// Unknown build or edition counts as applicable.
+    pub fn applies(&self, build: Option<u32>, edition: Option<&str>) -> bool {
+        if let Some(b) = build {
+            if self.min_build.is_some_and(|min| b < min) || self.max_build.is_some_and(|max| b > max) {
+                return false;
+            }
+        }
+        match edition {
+            Some(e) if !self.editions.is_empty() => self.editions.iter().any(|p| e.starts_with(p.as_str())),
+            _ => true,
+        }
+    }
+
+    /// PowerShell condition over $_aimBuild / $_aimEdition (set by the
+    /// script header and the state checker).
+    pub fn to_ps1(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(min) = self.min_build {
+            parts.push(format!("($_aimBuild -ge {})", min));
+        }
+        if let Some(max) = self.max_build {
+            parts.push(format!("($_aimBuild -le {})", max));
+        }
+        if !self.editions.is_empty() {
+            parts.push(format!(
+                "(@({} | Where-Object {{ $_aimEdition -like \"$_*\" }}).Count -gt 0)",
+                self.editions.iter().map(|e| ps::quote(e)).collect::<Vec<_>>().join(",")
+            ));
+        }
+        if parts.is_empty() { "$true".to_string() } else { parts.join(" -and ") }
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────
+// Dry-run plan – requested toggles vs. the live system state
+// ────────────────────────────────────────────────────────────────────
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanStatus {
+    AlreadyApplied,
+    WillChange,
+    CannotVerify,
+    RequiresBiosReboot,
+    /// Outside the tweak's OS build / edition range; the script skips it
+    NotApplicable,
+}
+
+#[derive(serde::Serialize)]
+pub struct PlanItem {
+    pub key: String,
+    pub id: String,
+    pub section: String,
+    pub name: String,
+    pub status: PlanStatus,
+    pub needs_admin: bool,
+    pub needs_restart: bool,
+    pub risk: Risk,
+    pub reversibility: Reversibility,
+}
+
+#[derive(serde::Serialize, Default)]
+pub struct ChangePlan {
+    pub items: Vec<PlanItem>,
+    pub already_applied: usize,
+    pub will_change: usize,
+    pub cannot_verify: usize,
+    pub requires_bios_reboot: usize,
+    pub not_applicable: usize,
+    /// Changes (excluding already applied ones) that need elevation
+    pub needs_admin: usize,
+    /// Changes that only take effect after a restart
+    pub needs_restart: usize,
+    /// Keys to pass back as `skip` so the script leaves them alone
+    pub skip: Vec<String>,
+}
+
+/// Merge a serialized OptimizationConfig with check_system_state output.
+/// `os_build` / `os_edition` (from get_hardware_info) mark tweaks outside
+/// their supported range as not applicable.
+pub fn plan(
+    config: &serde_json::Value,
+    state: &serde_json::Value,
+    section: Option<&str>,
+    os_build: Option<u32>,
+    os_edition: Option<&str>,
+) -> ChangePlan {
+    let mut plan = ChangePlan::default();
+    for tweak in &catalog().tweaks {
+        if section.is_some_and(|s| s != tweak.section) || !tweak.is_enabled(config) {
+            continue;
+        }
+        let applicable = tweak.os.as_ref().map_or(true, |r| r.applies(os_build, os_edition));
+        let status = match state.get(&tweak.key).and_then(|v| v.as_bool()) {
+            _ if !applicable => PlanStatus::NotApplicable,
+            Some(true) => PlanStatus::AlreadyApplied,
+            _ if tweak.is_manual() || tweak.restart => PlanStatus::RequiresBiosReboot,
+            Some(false) => PlanStatus::WillChange,
+            None => PlanStatus::CannotVerify,
+        };
+        match status {
+            PlanStatus::AlreadyApplied => {
+                plan.already_applied += 1;
+                plan.skip.push(tweak.key.clone());
+            }
+            PlanStatus::WillChange => plan.will_change += 1,
+            PlanStatus::CannotVerify => plan.cannot_verify += 1,
+            PlanStatus::RequiresBiosReboot => plan.requires_bios_reboot += 1,
+            PlanStatus::NotApplicable => plan.not_applicable += 1,
+        }
+        let pending = !matches!(status, PlanStatus::AlreadyApplied | PlanStatus::NotApplicable);
+        let needs_admin = pending && tweak.needs_admin();
+        let needs_restart = pending && tweak.restart;
+        plan.needs_admin += needs_admin as usize;
+        plan.needs_restart += needs_restart as usize;
+        plan.items.push(PlanItem {
+            key: tweak.key.clone(),
+            id: tweak.id.clone(),
+            section: tweak.section.clone(),
+            name: tweak.name().to_string(),
+            status,
+            needs_admin,
+            needs_restart,
+            risk: tweak.risk,
+            reversibility: tweak.reversibility,
+        });
+    }
+    plan
+}
+
+impl Verify {
+    /// PowerShell expression yielding $true / $false / $null.
+    pub fn to_ps1(&self) -> String {
+        match self {
+            Verify::None => "$null".to_string(),
+            Verify::Registry { path, name, expected } => {
+                let path = if path.starts_with('$') { path.clone() } else { ps::quote(path) };
+                let expected = match expected {
+                    serde_json::Value::String(v) => ps::quote(v),
+                    other => other.to_string(),
+                };
+                format!("CR {} {} {}", path, ps::quote(name), expected)
+            }
+            Verify::Service { name } => format!("CS {}", ps::quote(name)),
+            Verify::Powercfg { subgroup, setting, expected } => {
+                format!("CP {} {} {}", subgroup, setting, expected)
+            }
+            Verify::Script { lines } => {
+                let mut s = String::from("& {\n");
+                for l in lines {
+                    s.push_str("    ");
+                    s.push_str(l);
+                    s.push('\n');
+                }
+                s.push('}');
+                s
+            }
+        }
+    }
+}
+
+/// Body of the system state checker: helpers come from check_state.ps1,
+/// every `$s['key']` assignment is generated from the catalog.
+pub fn state_check_script() -> String {
+    let cat = catalog();
+    let mut s = String::from(include_str!("check_state.ps1"));
+    s.push_str(GPU_BAR_PS1);
+    for section in &cat.sections {
+        s.push_str(&format!("\n# ── {} ──\n", section.id.to_uppercase()));
+        for line in &section.check_preamble {
+            s.push_str(line);
+            s.push('\n');
+        }
+        for tweak in cat.section_tweaks(&section.id) {
+            let expr = tweak.verify.to_ps1();
+            match &tweak.os {
+                Some(req) => s.push_str(&format!(
+                    "$s['{}'] = if ({}) {{ {} }} else {{ $null }}\n",
+                    tweak.key,
+                    req.to_ps1(),
+                    expr
+                )),
+                None => s.push_str(&format!("$s['{}'] = {}\n", tweak.key, expr)),
+            }
+        }
+        for check in &section.checks {
+            s.push_str(&format!("$s['{}'] = {}\n", check.key, check.verify.to_ps1()));
+        }
+    }
+    s.push_str("\n# Output\n$s | ConvertTo-Json -Compress\n");
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_and_keys_are_unique() {
+        let cat = catalog();
+        let mut ids: Vec<&str> = cat.tweaks.iter().map(|t| t.id.as_str()).collect();
+        let mut keys: Vec<&str> = cat.tweaks.iter().map(|t| t.key.as_str()).collect();
+        ids.sort_unstable();
+        keys.sort_unstable();
+        let dup = |v: &[&str]| v.windows(2).find(|w| w[0] == w[1]).map(|w| w[0].to_string());
+        assert_eq!(dup(&ids), None, "duplicate tweak id");
+        assert_eq!(dup(&keys), None, "duplicate tweak key");
+    }
+
+    #[test]
+    fn os_requirement_build_range_and_editions() {
+        let os = |min: Option<u32>, max: Option<u32>, editions: &[&str]| OsRequirement {
+            min_build: min,
+            max_build: max,
+            editions: editions.iter().map(|e| e.to_string()).collect(),
+        };
+        let any = OsRequirement::default();
+        assert!(any.applies(Some(10240), Some("Core")) && any.applies(None, None));
+        assert_eq!(any.to_ps1(), "$true");
+
+        let win11 = os(Some(22000), None, &[]);
+        assert!(!win11.applies(Some(19045), None));
+        assert!(win11.applies(Some(22000), None) && win11.applies(Some(26100), Some("Core")));
+        assert!(win11.applies(None, None), "unknown build counts as applicable");
+
+        let win10 = os(Some(19041), Some(19045), &[]);
+        assert!(!win10.applies(Some(19040), None) && !win10.applies(Some(22000), None));
+        assert!(win10.applies(Some(19041), None) && win10.applies(Some(19045), None));
+        assert_eq!(win10.to_ps1(), "($_aimBuild -ge 19041) -and ($_aimBuild -le 19045)");
+
+        let pro = os(None, None, &["Professional", "Enterprise"]);
+        assert!(pro.applies(None, Some("ProfessionalWorkstation")) && pro.applies(None, Some("EnterpriseS")));
+        assert!(!pro.applies(None, Some("Core")) && !pro.applies(None, Some("CoreN")));
+        assert!(pro.applies(Some(19045), None), "unknown edition counts as applicable");
+
+        // Both conditions must hold
+        let pro11 = os(Some(22000), None, &["Professional"]);
+        assert!(pro11.applies(Some(22631), Some("Professional")));
+        assert!(!pro11.applies(Some(19045), Some("Professional")));
+        assert!(!pro11.applies(Some(22631), Some("Core")));
+        assert!(pro11.to_ps1().starts_with("($_aimBuild -ge 22000) -and (@('Professional' | Where-Object"));
+    }
+
+    #[test]
+    fn no_build_floor_at_or_below_the_first_windows_10() {
+        for tweak in &catalog().tweaks {
+            let min = tweak.os.as_ref().and_then(|os| os.min_build);
+            assert!(min.map_or(true, |b| b > 10240), "{}: min_build {:?} excludes nothing", tweak.key, min);
+        }
+    }
+
+    /// Plan item of `key` with only that tweak switched on.
+    fn plan_one(key: &str, state: serde_json::Value, build: Option<u32>, edition: Option<&str>) -> ChangePlan {
+        let mut config = serde_json::Value::Object(
+            catalog().sections.iter().map(|s| (s.config.clone(), serde_json::json!({}))).collect(),
+        );
+        catalog().find(key).unwrap().set_enabled(&mut config, true);
+        let plan = plan(&config, &state, None, build, edition);
+        assert_eq!(plan.items.len(), 1);
+        assert_eq!(plan.items[0].key, key);
+        plan
+    }
+
+    #[test]
+    fn plan_skips_what_is_already_applied() {
+        let plan = plan_one("w_dvr", serde_json::json!({ "w_dvr": true }), Some(22631), None);
+        assert!(plan.items[0].status == PlanStatus::AlreadyApplied);
+        assert_eq!((plan.already_applied, plan.needs_admin), (1, 0));
+        assert_eq!(plan.skip, ["w_dvr"]);
+    }
+
+    #[test]
+    fn plan_counts_what_will_change() {
+        let plan = plan_one("w_idx", serde_json::json!({ "w_idx": false }), Some(22631), None);
+        assert!(plan.items[0].status == PlanStatus::WillChange);
+        assert_eq!((plan.will_change, plan.needs_admin, plan.needs_restart), (1, 1, 0));
+        assert!(plan.skip.is_empty());
+    }
+
+    #[test]
+    fn plan_without_a_state_cannot_verify() {
+        for state in [serde_json::json!({}), serde_json::json!({ "w_idx": null })] {
+            let plan = plan_one("w_idx", state, None, None);
+            assert!(plan.items[0].status == PlanStatus::CannotVerify);
+            assert_eq!(plan.cannot_verify, 1);
+        }
+    }
+
+    #[test]
+    fn plan_flags_bios_and_reboot_tweaks() {
+        for key in ["b_xmp", "b_svm", "w_hpet"] {
+            let plan = plan_one(key, serde_json::json!({ key: false }), Some(22631), None);
+            assert!(plan.items[0].status == PlanStatus::RequiresBiosReboot, "{}", key);
+            assert_eq!(plan.requires_bios_reboot, 1);
+        }
+        let plan = plan_one("w_hpet", serde_json::json!({}), None, None);
+        assert!(plan.items[0].status == PlanStatus::RequiresBiosReboot);
+        assert_eq!(plan.needs_restart, 1);
+        // Manual BIOS steps only print instructions
+        let bios = plan_one("b_xmp", serde_json::json!({}), None, None);
+        assert!(catalog().find("b_xmp").unwrap().is_manual());
+        assert!(bios.items[0].status == PlanStatus::RequiresBiosReboot);
+        assert_eq!((bios.needs_admin, bios.needs_restart), (0, 0));
+    }
+
+    #[test]
+    fn plan_leaves_out_tweaks_for_other_windows_versions() {
+        let state = serde_json::json!({ "w_widgets": false });
+        let plan = plan_one("w_widgets", state.clone(), Some(19045), None);
+        assert!(plan.items[0].status == PlanStatus::NotApplicable);
+        assert_eq!((plan.not_applicable, plan.needs_admin), (1, 0));
+        assert!(plan.skip.is_empty());
+        assert!(plan_one("w_widgets", state, Some(22631), None).items[0].status == PlanStatus::WillChange);
+        let home = plan_one("x_large", serde_json::json!({ "x_large": true }), Some(22631), Some("Core"));
+        assert!(home.items[0].status == PlanStatus::NotApplicable);
+    }
+
+    /// Argument of `-<flag> '<value>'` in a PowerShell line.
+    fn arg<'a>(line: &'a str, flag: &str) -> Option<&'a str> {
+        let rest = &line[line.find(&format!("-{} '", flag))? + flag.len() + 3..];
+        Some(&rest[..rest.find('\'')?])
+    }
+
+    #[test]
+    fn apply_targets_are_declared_in_touches() {
+        let mut checked = 0;
+        for tweak in &catalog().tweaks {
+            for line in &tweak.apply {
+                let line = line.trim();
+                if ["Set-ItemProperty", "New-ItemProperty", "Remove-ItemProperty"].iter().any(|c| line.starts_with(c)) {
+                    // Paths built in a loop are covered by RegistryEach
+                    let (Some(path), Some(name)) = (arg(line, "Path"), arg(line, "Name")) else { continue };
+                    let declared = tweak.touches.iter().any(|t| {
+                        matches!(t, Touch::Registry { path: p, name: n }
+                            if p.eq_ignore_ascii_case(path) && n.eq_ignore_ascii_case(name))
+                    });
+                    assert!(declared, "{}: {}\\{} missing from touches", tweak.key, path, name);
+                    checked += 1;
+                } else if let Some(rest) = line.strip_prefix("bcdedit ") {
+                    let mut words = rest.split_whitespace().filter(|w| !w.starts_with('{'));
+                    if !matches!(words.next(), Some("/set" | "/deletevalue")) {
+                        continue;
+                    }
+                    let name = words.next().unwrap_or_default();
+                    let declared = tweak
+                        .touches
+                        .iter()
+                        .any(|t| matches!(t, Touch::Bcdedit { name: n } if n.eq_ignore_ascii_case(name)));
+                    assert!(declared, "{}: bcdedit {} missing from touches", tweak.key, name);
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 50, "only {} apply lines checked", checked);
+    }
+}
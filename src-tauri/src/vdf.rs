@@ -0,0 +1,414 @@
+// ────────────────────────────────────────────────────────────────────
+// Valve KeyValues text (.vdf). The parser keeps every token as written
+// plus the whitespace and comments around it, so a document that is
+// only read serializes back byte for byte and an edit touches nothing
+// but the value it changes.
+// ────────────────────────────────────────────────────────────────────
+
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Token {
+    /// Unescaped text
+    pub text: String,
+    /// As written, quotes and escapes included
+    raw: String,
+}
+
+impl Token {
+    fn quoted(text: &str) -> Token {
+        Token { text: text.to_string(), raw: quote(text) }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Item {
+    /// Whitespace and comments before the key
+    lead: String,
+    pub key: Token,
+    /// Between the key and the value
+    gap: String,
+    pub value: Value,
+    /// A trailing `[$WIN32]`-style condition, with the space before it
+    cond: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Str(Token),
+    Obj(Object),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Object {
+    pub items: Vec<Item>,
+    /// Whitespace and comments before the closing brace (end of file at
+    /// the top level)
+    tail: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Document {
+    pub root: Object,
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// ── Parser ──
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Lexer {
+    fn new(src: &str) -> Self {
+        // A BOM is not part of the first token
+        let chars = src.strip_prefix('\u{feff}').unwrap_or(src).chars().collect();
+        Lexer { chars, pos: 0, line: 1 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn err(&self, msg: &str) -> String {
+        format!("VDF parse error on line {}: {}", self.line, msg)
+    }
+
+    /// Whitespace and `//` comments.
+    fn trivia(&mut self) -> String {
+        let mut out = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                out.push(c);
+                self.bump();
+            } else if c == '/' && self.chars.get(self.pos + 1) == Some(&'/') {
+                while let Some(c) = self.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    out.push(c);
+                    self.bump();
+                }
+            } else {
+                break;
+            }
+        }
+        out
+    }
+
+    fn string(&mut self) -> Result<Token, String> {
+        let mut raw = String::new();
+        let mut text = String::new();
+        if self.peek() == Some('"') {
+            raw.push('"');
+            self.bump();
+            loop {
+                match self.bump() {
+                    None => return Err(self.err("unterminated string")),
+                    Some('"') => {
+                        raw.push('"');
+                        break;
+                    }
+                    Some('\\') => {
+                        raw.push('\\');
+                        match self.bump() {
+                            None => return Err(self.err("unterminated string")),
+                            Some(e) => {
+                                raw.push(e);
+                                match e {
+                                    'n' => text.push('\n'),
+                                    't' => text.push('\t'),
+                                    '\\' | '"' => text.push(e),
+                                    _ => {
+                                        text.push('\\');
+                                        text.push(e);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Some(c) => {
+                        raw.push(c);
+                        text.push(c);
+                    }
+                }
+            }
+        } else {
+            while let Some(c) = self.peek() {
+                if c.is_whitespace() || matches!(c, '{' | '}' | '"') {
+                    break;
+                }
+                raw.push(c);
+                text.push(c);
+                self.bump();
+            }
+        }
+        Ok(Token { text, raw })
+    }
+
+    fn condition(&mut self) -> String {
+        let start = (self.pos, self.line);
+        let space = self.trivia();
+        if self.peek() != Some('[') || space.contains('\n') {
+            self.pos = start.0;
+            self.line = start.1;
+            return String::new();
+        }
+        let mut out = space;
+        while let Some(c) = self.bump() {
+            out.push(c);
+            if c == ']' {
+                break;
+            }
+        }
+        out
+    }
+
+    fn object(&mut self, nested: bool) -> Result<Object, String> {
+        let mut items = Vec::new();
+        loop {
+            let lead = self.trivia();
+            match self.peek() {
+                None if nested => return Err(self.err("missing '}'")),
+                None => return Ok(Object { items, tail: lead }),
+                Some('}') if nested => {
+                    self.bump();
+                    return Ok(Object { items, tail: lead });
+                }
+                Some('}') => return Err(self.err("unexpected '}'")),
+                Some('{') => return Err(self.err("'{' without a key")),
+                Some(_) => {}
+            }
+            let key = self.string()?;
+            let gap = self.trivia();
+            let value = match self.peek() {
+                None => return Err(self.err(&format!("key \"{}\" has no value", key.text))),
+                Some('{') => {
+                    self.bump();
+                    Value::Obj(self.object(true)?)
+                }
+                Some('}') => return Err(self.err(&format!("key \"{}\" has no value", key.text))),
+                Some(_) => Value::Str(self.string()?),
+            };
+            let cond = self.condition();
+            items.push(Item { lead, key, gap, value, cond });
+        }
+    }
+}
+
+pub fn parse(content: &str) -> Result<Document, String> {
+    let bom = content.starts_with('\u{feff}');
+    let mut doc = Document { root: Lexer::new(content).object(false)? };
+    if bom {
+        // Keep it on the way back out
+        match doc.root.items.first_mut() {
+            Some(first) => first.lead.insert(0, '\u{feff}'),
+            None => doc.root.tail.insert(0, '\u{feff}'),
+        }
+    }
+    Ok(doc)
+}
+
+// ── Serializer ──
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.items {
+            write!(f, "{}{}{}", item.lead, item.key.raw, item.gap)?;
+            match &item.value {
+                Value::Str(t) => f.write_str(&t.raw)?,
+                Value::Obj(o) => write!(f, "{{{}}}", o)?,
+            }
+            f.write_str(&item.cond)?;
+        }
+        f.write_str(&self.tail)
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The root has no braces, only its items and the trailing trivia
+        self.root.fmt(f)
+    }
+}
+
+// ── Lookup and edits ──
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(t) => Some(&t.text),
+            Value::Obj(_) => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Object> {
+        match self {
+            Value::Obj(o) => Some(o),
+            Value::Str(_) => None,
+        }
+    }
+}
+
+impl Object {
+    /// First value under `key`; keys compare case-insensitively, as in Steam.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.items.iter().find(|i| i.key.text.eq_ignore_ascii_case(key)).map(|i| &i.value)
+    }
+
+    pub fn get_path(&self, path: &[&str]) -> Option<&Value> {
+        let (first, rest) = path.split_first()?;
+        let v = self.get(first)?;
+        if rest.is_empty() {
+            Some(v)
+        } else {
+            v.as_object()?.get_path(rest)
+        }
+    }
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.items.iter_mut().find(|i| i.key.text.eq_ignore_ascii_case(key)).map(|i| &mut i.value)
+    }
+
+    /// Set the string at `path`, creating the objects on the way. New
+    /// entries are indented with tabs, the way Steam writes its files.
+    /// `depth` is how deep `self` sits in the document.
+    fn set(&mut self, path: &[&str], value: &str, depth: usize) -> Result<(), String> {
+        let (first, rest) = path.split_first().ok_or("Empty VDF path")?;
+        if self.get(first).is_none() {
+            let indent = "\t".repeat(depth);
+            let lead = if depth == 0 && self.items.is_empty() { String::new() } else { format!("\n{}", indent) };
+            let (gap, value) = if rest.is_empty() {
+                ("\t\t".to_string(), Value::Str(Token::quoted(value)))
+            } else {
+                (
+                    format!("\n{}", indent),
+                    Value::Obj(Object { items: Vec::new(), tail: format!("\n{}", indent) }),
+                )
+            };
+            self.items.push(Item { lead, key: Token::quoted(first), gap, value, cond: String::new() });
+            if depth == 0 && self.tail.is_empty() {
+                self.tail.push('\n');
+            }
+        }
+        match (self.get_mut(first), rest.is_empty()) {
+            (Some(Value::Str(t)), true) => {
+                if t.text != value {
+                    *t = Token::quoted(value);
+                }
+                Ok(())
+            }
+            (Some(Value::Obj(o)), false) => o.set(rest, value, depth + 1),
+            _ => Err(format!("VDF key \"{}\" does not hold a {}", first, if rest.is_empty() { "value" } else { "section" })),
+        }
+    }
+}
+
+impl Document {
+    pub fn get(&self, path: &[&str]) -> Option<&Value> {
+        self.root.get_path(path)
+    }
+
+    pub fn get_str(&self, path: &[&str]) -> Option<&str> {
+        self.get(path)?.as_str()
+    }
+
+    pub fn set(&mut self, path: &[&str], value: &str) -> Result<(), String> {
+        self.root.set(path, value, 0)
+    }
+}
+
+/// Write `doc` over `path`, copying the current file to
+/// `<name>.<timestamp>.bak` first; returns the backup's path. The new
+/// content goes to a temporary file that replaces the original, so a
+/// failed write never leaves a truncated file behind.
+pub fn save(path: &std::path::Path, doc: &Document) -> Result<std::path::PathBuf, String> {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup = path.with_file_name(format!("{}.{}.bak", name, stamp));
+    std::fs::copy(path, &backup).map_err(|e| format!("Backup failed: {}", e))?;
+    let partial = path.with_file_name(format!("{}.part", name));
+    std::fs::write(&partial, doc.to_string()).map_err(|e| format!("Write error: {}", e))?;
+    std::fs::rename(&partial, path).map_err(|e| format!("Write error: {}", e))?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: [&str; 5] = [
+        include_str!("../fixtures/steam/libraryfolders.vdf"),
+        include_str!("../fixtures/steam/libraryfolders_legacy.vdf"),
+        include_str!("../fixtures/steam/localconfig.vdf"),
+        include_str!("../fixtures/steam/localconfig_no_cs2.vdf"),
+        include_str!("../fixtures/steam/loginusers.vdf"),
+    ];
+
+    #[test]
+    fn fixtures_round_trip() {
+        for text in FIXTURES {
+            assert_eq!(parse(text).unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn reads_nested_values() {
+        let doc = parse(FIXTURES[0]).unwrap();
+        assert_eq!(doc.get_str(&["libraryfolders", "1", "path"]), Some("D:\\SteamLibrary"));
+        assert_eq!(doc.get_str(&["LibraryFolders", "0", "Label"]), Some(""));
+        assert!(doc.get(&["libraryfolders", "1", "apps"]).unwrap().as_object().is_some());
+    }
+
+    #[test]
+    fn set_changes_only_the_value() {
+        let text = FIXTURES[2];
+        let mut doc = parse(text).unwrap();
+        let path = ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", "730", "LaunchOptions"];
+        doc.set(&path, "-novid +exec \"autoexec.cfg\"").unwrap();
+        let out = doc.to_string();
+        assert_eq!(
+            out,
+            text.replace(
+                "\"LaunchOptions\"\t\t\"-novid\"",
+                "\"LaunchOptions\"\t\t\"-novid +exec \\\"autoexec.cfg\\\"\""
+            )
+        );
+        assert_eq!(parse(&out).unwrap().get_str(&path), Some("-novid +exec \"autoexec.cfg\""));
+    }
+
+    #[test]
+    fn set_creates_missing_sections() {
+        let mut doc = parse(FIXTURES[3]).unwrap();
+        let path = ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", "730", "LaunchOptions"];
+        doc.set(&path, "-novid").unwrap();
+        let out = doc.to_string();
+        assert!(out.contains("\t\t\t\t\t\"730\"\n\t\t\t\t\t{\n\t\t\t\t\t\t\"LaunchOptions\"\t\t\"-novid\"\n\t\t\t\t\t}\n"));
+        let reparsed = parse(&out).unwrap();
+        assert_eq!(reparsed.get_str(&path), Some("-novid"));
+        // Everything that was there is still there
+        assert_eq!(reparsed.get_str(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", "440", "LastPlayed"]), Some("1690000000"));
+        assert_eq!(reparsed.get_str(&["UserLocalConfigStore", "friends", "PersonaName"]), Some("player"));
+    }
+
+    #[test]
+    fn rejects_broken_input() {
+        assert!(parse("\"a\"\n{\n\t\"b\" \"c\"\n").is_err());
+        assert!(parse("\"a\" \"unterminated").is_err());
+        assert!(parse("}").is_err());
+    }
+}
@@ -0,0 +1,397 @@
+// ────────────────────────────────────────────────────────────────────
+// CS2 video settings. cs2_video.txt (per account) holds the menu's
+// video options as "setting.*" keys; cs2_machine_convars.vcfg (game
+// cfg folder) holds a few more, such as Boost Player Contrast. Both are
+// KeyValues files, edited in place so unknown keys survive.
+// ────────────────────────────────────────────────────────────────────
+
+use crate::vdf;
+use std::sync::OnceLock;
+
+pub const VIDEO_FILE: &str = "cs2_video.txt";
+pub const MACHINE_FILE: &str = "cs2_machine_convars.vcfg";
+const VIDEO: &str = "video.cfg";
+const PLAYER_CONTRAST: [&str; 3] = ["config", "convars", "r_player_visibility_mode"];
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum AspectRatio {
+    #[serde(rename = "4:3")]
+    Normal,
+    #[serde(rename = "16:9")]
+    Wide,
+    #[serde(rename = "16:10")]
+    Wide16x10,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    Fullscreen,
+    FullscreenWindowed,
+    Windowed,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Msaa {
+    #[serde(rename = "off")]
+    Off,
+    #[serde(rename = "cmaa2")]
+    Cmaa,
+    #[serde(rename = "2x")]
+    X2,
+    #[serde(rename = "4x")]
+    X4,
+    #[serde(rename = "8x")]
+    X8,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Hdr {
+    Quality,
+    Performance,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Reflex {
+    Off,
+    Enabled,
+    Boost,
+}
+
+/// Every field is optional: None means "not in the file" when reading
+/// and "leave as is" when writing, so a preset only sets what it names.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub display_mode: Option<DisplayMode>,
+    pub vsync: Option<bool>,
+    pub boost_player_contrast: Option<bool>,
+    pub msaa: Option<Msaa>,
+    /// 0 low – 3 very high
+    pub shadow_quality: Option<u8>,
+    /// 0 sun only, 1 all
+    pub dynamic_shadows: Option<u8>,
+    /// 0 low – 2 high
+    pub texture_detail: Option<u8>,
+    /// 0 bilinear, 1 trilinear, 2–5 anisotropic 2x–16x
+    pub texture_filtering: Option<u8>,
+    /// 0 low, 1 high
+    pub shader_detail: Option<u8>,
+    /// 0 low – 3 very high
+    pub particle_detail: Option<u8>,
+    /// 0 disabled – 3 high
+    pub ambient_occlusion: Option<u8>,
+    pub hdr: Option<Hdr>,
+    /// 0 disabled, 1 ultra quality – 4 performance
+    pub fsr: Option<u8>,
+    pub reflex: Option<Reflex>,
+}
+
+/// Quality levels: field key in cs2_video.txt and highest value.
+const LEVELS: [(&str, u8); 8] = [
+    ("setting.videocfg_shadow_quality", 3),
+    ("setting.videocfg_dynamic_shadows", 1),
+    ("setting.videocfg_texture_detail", 2),
+    ("setting.r_texturefilteringquality", 5),
+    ("setting.shaderquality", 1),
+    ("setting.videocfg_particle_detail", 3),
+    ("setting.videocfg_ao_detail", 3),
+    ("setting.videocfg_fsr_detail", 4),
+];
+
+impl VideoSettings {
+    fn levels(&self) -> [Option<u8>; 8] {
+        [
+            self.shadow_quality,
+            self.dynamic_shadows,
+            self.texture_detail,
+            self.texture_filtering,
+            self.shader_detail,
+            self.particle_detail,
+            self.ambient_occlusion,
+            self.fsr,
+        ]
+    }
+
+    fn levels_mut(&mut self) -> [&mut Option<u8>; 8] {
+        [
+            &mut self.shadow_quality,
+            &mut self.dynamic_shadows,
+            &mut self.texture_detail,
+            &mut self.texture_filtering,
+            &mut self.shader_detail,
+            &mut self.particle_detail,
+            &mut self.ambient_occlusion,
+            &mut self.fsr,
+        ]
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, v) in [("width", self.width), ("height", self.height)] {
+            if let Some(v) = v {
+                if !(320..=16384).contains(&v) {
+                    return Err(format!("Video setting {}: {} is out of range", name, v));
+                }
+            }
+        }
+        for ((key, max), v) in LEVELS.iter().zip(self.levels()) {
+            if let Some(v) = v {
+                if v > *max {
+                    return Err(format!("Video setting {}: {} is above {}", key, v, max));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn flag(on: bool) -> &'static str {
+    if on { "1" } else { "0" }
+}
+
+pub fn read(video: &vdf::Document, machine: Option<&vdf::Document>) -> VideoSettings {
+    let num = |key: &str| video.get_str(&[VIDEO, key]).and_then(|v| v.trim().parse::<i64>().ok());
+    let mut s = VideoSettings {
+        width: num("setting.defaultres").and_then(|v| u32::try_from(v).ok()),
+        height: num("setting.defaultresheight").and_then(|v| u32::try_from(v).ok()),
+        aspect_ratio: match num("setting.aspectratiomode") {
+            Some(0) => Some(AspectRatio::Normal),
+            Some(1) => Some(AspectRatio::Wide),
+            Some(2) => Some(AspectRatio::Wide16x10),
+            _ => None,
+        },
+        display_mode: match (num("setting.fullscreen"), num("setting.nowindowborder")) {
+            (Some(1), _) => Some(DisplayMode::Fullscreen),
+            (Some(0), Some(1)) => Some(DisplayMode::FullscreenWindowed),
+            (Some(0), _) => Some(DisplayMode::Windowed),
+            _ => None,
+        },
+        vsync: num("setting.mat_vsync").map(|v| v != 0),
+        boost_player_contrast: machine
+            .and_then(|m| m.get_str(&PLAYER_CONTRAST))
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .map(|v| v != 0),
+        msaa: match (num("setting.msaa_samples"), num("setting.r_csgo_cmaa_enable")) {
+            (Some(2), _) => Some(Msaa::X2),
+            (Some(4), _) => Some(Msaa::X4),
+            (Some(8), _) => Some(Msaa::X8),
+            (Some(0), Some(1)) => Some(Msaa::Cmaa),
+            (Some(0), _) => Some(Msaa::Off),
+            _ => None,
+        },
+        hdr: match num("setting.videocfg_hdr_detail") {
+            Some(-1) => Some(Hdr::Quality),
+            Some(3) => Some(Hdr::Performance),
+            _ => None,
+        },
+        reflex: match num("setting.r_low_latency") {
+            Some(0) => Some(Reflex::Off),
+            Some(1) => Some(Reflex::Enabled),
+            Some(2) => Some(Reflex::Boost),
+            _ => None,
+        },
+        ..Default::default()
+    };
+    for (dst, (key, max)) in s.levels_mut().into_iter().zip(LEVELS) {
+        *dst = num(key).filter(|v| (0..=max as i64).contains(v)).map(|v| v as u8);
+    }
+    s
+}
+
+/// Write the fields `s` sets. Boost Player Contrast lives in the machine
+/// convars, so setting it needs that file.
+pub fn apply(s: &VideoSettings, video: &mut vdf::Document, machine: Option<&mut vdf::Document>) -> Result<(), String> {
+    s.validate()?;
+    let mut values: Vec<(&str, String)> = Vec::new();
+    if let Some(w) = s.width {
+        values.push(("setting.defaultres", w.to_string()));
+    }
+    if let Some(h) = s.height {
+        values.push(("setting.defaultresheight", h.to_string()));
+    }
+    if let Some(a) = s.aspect_ratio {
+        let mode = match a {
+            AspectRatio::Normal => "0",
+            AspectRatio::Wide => "1",
+            AspectRatio::Wide16x10 => "2",
+        };
+        values.push(("setting.aspectratiomode", mode.into()));
+    }
+    if let Some(m) = s.display_mode {
+        values.push(("setting.fullscreen", flag(m == DisplayMode::Fullscreen).into()));
+        values.push(("setting.nowindowborder", flag(m != DisplayMode::Windowed).into()));
+    }
+    if let Some(v) = s.vsync {
+        values.push(("setting.mat_vsync", flag(v).into()));
+    }
+    if let Some(m) = s.msaa {
+        let samples = match m {
+            Msaa::Off | Msaa::Cmaa => "0",
+            Msaa::X2 => "2",
+            Msaa::X4 => "4",
+            Msaa::X8 => "8",
+        };
+        values.push(("setting.msaa_samples", samples.into()));
+        values.push(("setting.r_csgo_cmaa_enable", flag(m == Msaa::Cmaa).into()));
+    }
+    if let Some(h) = s.hdr {
+        values.push(("setting.videocfg_hdr_detail", if h == Hdr::Quality { "-1" } else { "3" }.into()));
+    }
+    if let Some(r) = s.reflex {
+        let mode = match r {
+            Reflex::Off => "0",
+            Reflex::Enabled => "1",
+            Reflex::Boost => "2",
+        };
+        values.push(("setting.r_low_latency", mode.into()));
+    }
+    for ((key, _), v) in LEVELS.iter().zip(s.levels()) {
+        if let Some(v) = v {
+            values.push((key, v.to_string()));
+        }
+    }
+
+    for (key, value) in values {
+        video.set(&[VIDEO, key], &value)?;
+    }
+    if let Some(on) = s.boost_player_contrast {
+        let machine = machine.ok_or_else(|| format!("{} not found — start CS2 once so it creates it", MACHINE_FILE))?;
+        machine.set(&PLAYER_CONTRAST, flag(on))?;
+    }
+    Ok(())
+}
+
+// ── Presets ──
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Preset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub settings: VideoSettings,
+}
+
+#[derive(serde::Deserialize)]
+struct Presets {
+    presets: Vec<Preset>,
+}
+
+static PRESETS: OnceLock<Vec<Preset>> = OnceLock::new();
+
+pub fn presets() -> &'static [Preset] {
+    PRESETS.get_or_init(|| {
+        let p: Presets =
+            serde_json::from_str(include_str!("video_presets.json")).expect("video_presets.json is malformed");
+        p.presets
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIDEO_TXT: &str = include_str!("../fixtures/video/cs2_video.txt");
+    const MACHINE_VCFG: &str = include_str!("../fixtures/video/cs2_machine_convars.vcfg");
+
+    #[test]
+    fn reads_the_fixture() {
+        let video = vdf::parse(VIDEO_TXT).unwrap();
+        let machine = vdf::parse(MACHINE_VCFG).unwrap();
+        let s = read(&video, Some(&machine));
+        assert_eq!((s.width, s.height), (Some(1920), Some(1080)));
+        assert_eq!(s.aspect_ratio, Some(AspectRatio::Wide));
+        assert_eq!(s.display_mode, Some(DisplayMode::Fullscreen));
+        assert_eq!(s.vsync, Some(false));
+        assert_eq!(s.boost_player_contrast, Some(true));
+        assert_eq!(s.msaa, Some(Msaa::X4));
+        assert_eq!(s.hdr, Some(Hdr::Performance));
+        assert_eq!(s.reflex, Some(Reflex::Enabled));
+        assert_eq!(s.texture_filtering, Some(5));
+        assert_eq!(s.dynamic_shadows, Some(1));
+        assert_eq!(s.shadow_quality, Some(0));
+    }
+
+    #[test]
+    fn empty_settings_leave_the_files_unchanged() {
+        let mut video = vdf::parse(VIDEO_TXT).unwrap();
+        let mut machine = vdf::parse(MACHINE_VCFG).unwrap();
+        apply(&VideoSettings::default(), &mut video, Some(&mut machine)).unwrap();
+        assert_eq!(video.to_string(), VIDEO_TXT);
+        assert_eq!(machine.to_string(), MACHINE_VCFG);
+    }
+
+    #[test]
+    fn round_trip_keeps_unknown_keys() {
+        let mut video = vdf::parse(VIDEO_TXT).unwrap();
+        let mut machine = vdf::parse(MACHINE_VCFG).unwrap();
+        let wanted = VideoSettings {
+            width: Some(1280),
+            height: Some(960),
+            aspect_ratio: Some(AspectRatio::Normal),
+            display_mode: Some(DisplayMode::FullscreenWindowed),
+            boost_player_contrast: Some(false),
+            msaa: Some(Msaa::Cmaa),
+            shadow_quality: Some(2),
+            hdr: Some(Hdr::Quality),
+            reflex: Some(Reflex::Boost),
+            ..Default::default()
+        };
+        apply(&wanted, &mut video, Some(&mut machine)).unwrap();
+
+        let video = vdf::parse(&video.to_string()).unwrap();
+        let machine = vdf::parse(&machine.to_string()).unwrap();
+        let s = read(&video, Some(&machine));
+        assert_eq!((s.width, s.height), (Some(1280), Some(960)));
+        assert_eq!(s.aspect_ratio, wanted.aspect_ratio);
+        assert_eq!(s.display_mode, wanted.display_mode);
+        assert_eq!(s.boost_player_contrast, Some(false));
+        assert_eq!(s.msaa, wanted.msaa);
+        assert_eq!(s.shadow_quality, Some(2));
+        assert_eq!(s.hdr, wanted.hdr);
+        assert_eq!(s.reflex, wanted.reflex);
+        // Fields left as None keep the file's values
+        assert_eq!(s.vsync, Some(false));
+        assert_eq!(s.texture_filtering, Some(5));
+
+        // Keys the app does not know about survive untouched
+        for (key, value) in [
+            ("Version", "15"),
+            ("VendorID", "4318"),
+            ("Autoconfig", "2"),
+            ("setting.refreshrate_numerator", "239760"),
+            ("setting.fullscreen_min_on_focus_loss", "1"),
+            ("setting.monitor_index", "0"),
+        ] {
+            assert_eq!(video.get_str(&[VIDEO, key]), Some(value), "{}", key);
+        }
+        assert_eq!(machine.get_str(&["config", "convars", "r_fullscreen_gamma"]), Some("2.200000"));
+        assert_eq!(machine.get_str(&["config", "convars", "snd_mute_losefocus"]), Some("true"));
+    }
+
+    #[test]
+    fn new_keys_are_added_to_the_section() {
+        let mut video = vdf::parse("\"video.cfg\"\n{\n\t\"Version\"\t\t\"15\"\n}\n").unwrap();
+        apply(&VideoSettings { fsr: Some(1), ..Default::default() }, &mut video, None).unwrap();
+        let video = vdf::parse(&video.to_string()).unwrap();
+        assert_eq!(video.get_str(&[VIDEO, "setting.videocfg_fsr_detail"]), Some("1"));
+        assert_eq!(video.get_str(&[VIDEO, "Version"]), Some("15"));
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        let mut video = vdf::parse(VIDEO_TXT).unwrap();
+        let too_high = VideoSettings { shadow_quality: Some(4), ..Default::default() };
+        assert!(apply(&too_high, &mut video, None).is_err());
+        let too_small = VideoSettings { width: Some(100), ..Default::default() };
+        assert!(apply(&too_small, &mut video, None).is_err());
+        assert_eq!(video.to_string(), VIDEO_TXT);
+
+        let contrast = VideoSettings { boost_player_contrast: Some(true), ..Default::default() };
+        assert!(apply(&contrast, &mut video, None).unwrap_err().contains(MACHINE_FILE));
+    }
+}
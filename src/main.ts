@@ -128,6 +128,9 @@ interface AutoexecConfig {
   viewmodel_fov: string;
   m_rawinput: boolean;
   net_graph: boolean;
+  crosshair_code: string;
+  /** cl_crosshair* cvars an imported cfg set; empty writes all of them */
+  crosshair_cvars: string[];
   custom_commands: string;
 }
 
@@ -158,11 +161,34 @@ interface ExtrasConfig {
   enable_large_pages: boolean;
 }
 
+interface AmdConfig {
+  anti_lag: boolean;
+  disable_enhanced_sync: boolean;
+  disable_chill: boolean;
+  surface_format_optimization: boolean;
+  shader_cache_reset: boolean;
+  rebar_check: boolean;
+}
+
+interface IntelConfig {
+  low_latency: boolean;
+  disable_speed_sync: boolean;
+  disable_endurance_gaming: boolean;
+  intel_shader_cache_reset: boolean;
+  intel_rebar_check: boolean;
+}
+
+/** Config schema version sent with every payload (src-tauri/src/migrations.json has one step per older version; migrate.rs tests keep the two in step) */
+const CONFIG_SCHEMA_VERSION = 4;
+
 interface OptimizationConfig {
+  schema_version: number;
   bios: BiosConfig;
   windows: WindowsConfig;
   network: NetworkConfig;
   nvidia: NvidiaConfig;
+  amd: AmdConfig;
+  intel: IntelConfig;
   services: ServicesConfig;
   autoexec: AutoexecConfig;
   launch_options: LaunchOptionsConfig;
@@ -175,6 +201,8 @@ interface OptimizationConfig {
    State
    ================================================================ */
 let _currentScriptPath = "";
+/** cl_crosshair* cvars the imported autoexec set; cleared when the code is edited */
+let _crosshairCvars: string[] = [];
 
 /* ================================================================
    Schema System — layout customization for CFG tabs
@@ -281,7 +309,7 @@ function updateSchemaTabLayout(schemaId: string, tabView: string, commandIds: st
   }
 }
 function _schemaToJson(schema: Schema): string {
-  return JSON.stringify(schema, null, 2);
+  return JSON.stringify({ ...schema, schema_version: CONFIG_SCHEMA_VERSION }, null, 2);
 }
 function schemaFromJson(json: string): Schema | null {
   try {
@@ -572,6 +600,21 @@ const IMPACT: Record<string, number> = {
   nv_ambient: 1, // Ambient Occlusion off — saves GPU cycles
   nv_fxaa: 0.5, // FXAA off — removes forced antialiasing
 
+  /* AMD Radeon */
+  amd_antilag: 1.5, // Anti-Lag — input latency, <1% FPS
+  amd_esync: 1, // Enhanced Sync off — no extra buffered frame
+  amd_chill: 2, // Chill off — no dynamic FPS cap
+  amd_surface: 0.5, // Surface format optimization — lighter render targets
+  amd_shader: 0, // Cache reset — no lasting FPS gain
+  amd_rebar: 0, // Check only
+
+  /* Intel Arc */
+  intel_lowlat: 1.5, // Low Latency — input latency, <1% FPS
+  intel_sync: 1, // Speed/Smart Sync off — no extra buffered frame
+  intel_endurance: 2, // Endurance Gaming off — no power-saving FPS cap
+  intel_shader: 0, // Cache reset — no lasting FPS gain
+  intel_rebar: 0, // Check only
+
   /* Services */
   s_sys: 1.5, // SysMain off — 1-2% (RAM/disk)
   s_diag: 0.5, // DiagTrack off — <1%
@@ -618,6 +661,8 @@ const IMPACT: Record<string, number> = {
    Hardware-Aware Recommendation Engine
    ================================================================ */
 
+type GpuVendor = "nvidia" | "amd" | "intel" | "unknown";
+
 interface HwProfile {
   cpuName: string;
   cpuCores: number;
@@ -626,6 +671,8 @@ interface HwProfile {
   gpuName: string;
   gpuVramMb: number;
   gpuDriver: string;
+  /** Vendor of the dedicated GPU from get_hardware_info ("unknown" if undetected) */
+  gpuVendor: GpuVendor;
   ramTotalGb: number;
   ramSpeedMhz: number;
   refreshRate: number;
@@ -662,6 +709,8 @@ async function getHwProfile(): Promise<HwProfile> {
     // Tier detection: low < 4GB VRAM or < 4 cores, high > 8GB VRAM and > 6 cores
     const isLowEnd = vram < 4096 || cores < 4 || ramGb < 8;
     const isHighEnd = vram >= 8192 && cores >= 6 && ramGb >= 16;
+    const vendor = str(hw.gpu_vendor, "unknown");
+    const gpuVendor: GpuVendor = vendor === "nvidia" || vendor === "amd" || vendor === "intel" ? vendor : "unknown";
     _hwCache = {
       cpuName: str(hw.cpu_name, "Unknown"),
       cpuCores: cores,
@@ -670,13 +719,14 @@ async function getHwProfile(): Promise<HwProfile> {
       gpuName: str(hw.gpu_name, "Unknown"),
       gpuVramMb: vram,
       gpuDriver: str(hw.gpu_driver, ""),
+      gpuVendor,
       ramTotalGb: ramGb,
       ramSpeedMhz: Number(hw.ram_speed_mhz) || 0,
       refreshRate: Number(hw.refresh_rate) || 60,
       osName: str(hw.os_name, ""),
       osBuild: str(hw.os_build, ""),
-      isNvidia: gpuName.includes("NVIDIA") || gpuName.includes("GEFORCE") || gpuName.includes("RTX") || gpuName.includes("GTX"),
-      isAmd: gpuName.includes("AMD") || gpuName.includes("RADEON"),
+      isNvidia: gpuVendor === "nvidia" || gpuName.includes("NVIDIA") || gpuName.includes("GEFORCE") || gpuName.includes("RTX") || gpuName.includes("GTX"),
+      isAmd: gpuVendor === "amd" || gpuName.includes("AMD") || gpuName.includes("RADEON"),
       isIntel: gpuName.includes("INTEL") || cpuName.includes("INTEL"),
       hasHags: str(hw.hags, "").toUpperCase().includes("ON") || str(hw.hags, "").toUpperCase().includes("ENABLED"),
       hasRebar: (() => {
@@ -695,7 +745,7 @@ async function getHwProfile(): Promise<HwProfile> {
     // Fallback profile for when HW detection fails
     return {
       cpuName: "Unknown", cpuCores: 4, cpuThreads: 8, cpuClockMhz: 3000,
-      gpuName: "Unknown", gpuVramMb: 4096, gpuDriver: "", ramTotalGb: 16,
+      gpuName: "Unknown", gpuVramMb: 4096, gpuDriver: "", gpuVendor: "unknown", ramTotalGb: 16,
       ramSpeedMhz: 2400, refreshRate: 60, osName: "Windows", osBuild: "",
       isNvidia: false, isAmd: false, isIntel: false, hasHags: false,
       hasRebar: false, isWin11: false, isLowEnd: false, isMidRange: true, isHighEnd: false,
@@ -765,6 +815,21 @@ function getSysRecommendations(hw: HwProfile): Record<string, boolean> {
     nv_ambient: hw.isNvidia, // Ambient occlusion off
     nv_fxaa: hw.isNvidia,    // FXAA off
 
+    /* ── AMD Radeon ── (only apply if AMD GPU) */
+    amd_antilag: hw.gpuVendor === "amd",  // Anti-Lag
+    amd_esync: hw.gpuVendor === "amd",    // Enhanced Sync off
+    amd_chill: hw.gpuVendor === "amd",    // Chill off
+    amd_surface: hw.gpuVendor === "amd",  // Surface format optimization
+    amd_shader: false,                    // Shader cache reset — not auto
+    amd_rebar: hw.gpuVendor === "amd",    // SAM / ReBAR check
+
+    /* ── Intel Arc ── (only apply if Intel Arc GPU) */
+    intel_lowlat: hw.gpuVendor === "intel",    // Low Latency
+    intel_sync: hw.gpuVendor === "intel",      // Speed / Smart Sync off
+    intel_endurance: hw.gpuVendor === "intel", // Endurance Gaming off
+    intel_shader: false,                       // Shader cache reset — not auto
+    intel_rebar: hw.gpuVendor === "intel",     // ReBAR check (Arc needs it)
+
     /* ── Services ── */
     s_sys: true,       // SysMain off — RAM/disk freed
     s_diag: true,      // DiagTrack off — telemetry
@@ -930,6 +995,8 @@ async function applySysRecommendations(section: string) {
     windows: ["w_"],
     network: ["n_"],
     nvidia: ["nv_"],
+    amd: ["amd_"],
+    intel: ["intel_"],
     services: ["s_"],
     extras: ["x_"],
   };
@@ -1516,6 +1583,27 @@ function ck(id: string): boolean {
   return (document.getElementById(id) as HTMLInputElement).checked;
 }
 
+/** GPU driver section currently offered; all of them until the vendor is known */
+let _gpuVendor: GpuVendor = "unknown";
+
+/** Toggle of a GPU vendor card — always off when that card is not offered */
+function gpuCk(vendor: GpuVendor, id: string): boolean {
+  return (_gpuVendor === "unknown" || _gpuVendor === vendor) && ck(id);
+}
+
+/** Show only the driver card matching the detected GPU vendor */
+function offerGpuSection(vendor: GpuVendor): void {
+  _gpuVendor = vendor;
+  for (const v of ["nvidia", "amd", "intel"] as const) {
+    const cardEl = document.querySelector<HTMLElement>(`.card[data-section="${v}"]`);
+    if (!cardEl) continue;
+    const offered = vendor === "unknown" || vendor === v;
+    cardEl.style.display = offered ? "" : "none";
+    if (!offered) cardEl.querySelectorAll<HTMLInputElement>("input[type=checkbox]").forEach((cb) => { cb.checked = false; });
+  }
+  updateImpact();
+}
+
 function vl(id: string, fb: string): string {
   return (document.getElementById(id) as HTMLInputElement).value || fb;
 }
@@ -1530,6 +1618,38 @@ function setVal(id: string, v: string) {
   if (el) el.value = v;
 }
 
+/** Backend command error: plain string or a typed `{ kind, ... }` object */
+interface CommandError {
+  kind: "validation" | "failed" | "unacknowledged";
+  field?: string;
+  rule?: string;
+  value?: string;
+  message: string;
+  tweaks?: RiskNotice[];
+}
+
+/** High-risk tweak the backend won't apply without an acknowledgement */
+interface RiskNotice {
+  key: string;
+  name: string;
+  risk: "low" | "medium" | "high";
+  reversibility: "full" | "partial" | "manual" | "one_way";
+  restart: boolean;
+}
+
+function errorText(e: unknown): string {
+  if (e && typeof e === "object" && "kind" in e) {
+    const err = e as CommandError;
+    return err.kind === "validation" ? `Invalid ${err.field}: ${err.message}` : err.message;
+  }
+  return String(e);
+}
+
+/** Escape text from files or the system before it goes into innerHTML. */
+function esc(t: string): string {
+  return t.replace(/[&<>"]/g, (c) => `&#${c.charCodeAt(0)};`);
+}
+
 function toast(message: string, isError = false) {
   document.querySelectorAll(".status-toast").forEach((e) => { e.remove(); });
   const t = document.createElement("div");
@@ -1544,6 +1664,7 @@ function toast(message: string, isError = false) {
    ================================================================ */
 function collectConfig(): OptimizationConfig {
   return {
+    schema_version: CONFIG_SCHEMA_VERSION,
     bios: {
       disable_svm: true,
       disable_c_states: true,
@@ -1595,18 +1716,33 @@ function collectConfig(): OptimizationConfig {
       enable_ctcp: ck("n_ctcp"),
     },
     nvidia: {
-      prefer_max_perf: ck("nv_perf"),
-      disable_vsync: ck("nv_vsync"),
-      low_latency_ultra: ck("nv_lat"),
-      threaded_optimization: ck("nv_thread"),
-      disable_anisotropic: ck("nv_aniso"),
-      shader_cache_clear: ck("nv_shader"),
-      force_reflex: ck("nv_reflex"),
-      disable_sharpening: ck("nv_sharp"),
-      texture_filter_perf: ck("nv_texfilt"),
-      pre_rendered_frames_1: ck("nv_prerender"),
-      disable_ambient_occlusion: ck("nv_ambient"),
-      disable_fxaa: ck("nv_fxaa"),
+      prefer_max_perf: gpuCk("nvidia", "nv_perf"),
+      disable_vsync: gpuCk("nvidia", "nv_vsync"),
+      low_latency_ultra: gpuCk("nvidia", "nv_lat"),
+      threaded_optimization: gpuCk("nvidia", "nv_thread"),
+      disable_anisotropic: gpuCk("nvidia", "nv_aniso"),
+      shader_cache_clear: gpuCk("nvidia", "nv_shader"),
+      force_reflex: gpuCk("nvidia", "nv_reflex"),
+      disable_sharpening: gpuCk("nvidia", "nv_sharp"),
+      texture_filter_perf: gpuCk("nvidia", "nv_texfilt"),
+      pre_rendered_frames_1: gpuCk("nvidia", "nv_prerender"),
+      disable_ambient_occlusion: gpuCk("nvidia", "nv_ambient"),
+      disable_fxaa: gpuCk("nvidia", "nv_fxaa"),
+    },
+    amd: {
+      anti_lag: gpuCk("amd", "amd_antilag"),
+      disable_enhanced_sync: gpuCk("amd", "amd_esync"),
+      disable_chill: gpuCk("amd", "amd_chill"),
+      surface_format_optimization: gpuCk("amd", "amd_surface"),
+      shader_cache_reset: gpuCk("amd", "amd_shader"),
+      rebar_check: gpuCk("amd", "amd_rebar"),
+    },
+    intel: {
+      low_latency: gpuCk("intel", "intel_lowlat"),
+      disable_speed_sync: gpuCk("intel", "intel_sync"),
+      disable_endurance_gaming: gpuCk("intel", "intel_endurance"),
+      intel_shader_cache_reset: gpuCk("intel", "intel_shader"),
+      intel_rebar_check: gpuCk("intel", "intel_rebar"),
     },
     services: {
       disable_sysmain: ck("s_sys"),
@@ -1635,6 +1771,8 @@ function collectConfig(): OptimizationConfig {
       viewmodel_fov: vl("ae_vmfov", "68"),
       m_rawinput: ck("ae_raw"),
       net_graph: ck("ae_netgraph"),
+      crosshair_code: vl("ae_xhcode", "").trim(),
+      crosshair_cvars: _crosshairCvars,
       custom_commands: (document.getElementById("ae_custom") as HTMLTextAreaElement)?.value || "",
     },
     launch_options: {
@@ -1790,23 +1928,106 @@ function parsePs1AndSetToggles(content: string) {
    Tauri bridge
    ================================================================ */
 
+const REVERSIBILITY_TEXT: Record<RiskNotice["reversibility"], string> = {
+  full: "undone by Revert",
+  partial: "only partly undone by Revert",
+  manual: "must be undone by hand",
+  one_way: "cannot be undone",
+};
+
+function confirmRiskyTweaks(tweaks: RiskNotice[]): Promise<boolean> {
+  return new Promise((resolve) => {
+    const rows = tweaks.map((t) =>
+      `<div class="report-error-item">⚠️ <b>${t.name}</b> — ${REVERSIBILITY_TEXT[t.reversibility]}${t.restart ? ", needs a restart" : ""}</div>`,
+    ).join("");
+    const overlay = document.createElement("div");
+    overlay.className = "modal-overlay";
+    overlay.innerHTML = `
+      <div class="modal-dialog">
+        <div class="modal-title">High-risk tweaks selected</div>
+        <p class="modal-message">These change boot, security or device settings. Apply them only if you know why you want them.</p>
+        ${rows}
+        <div class="modal-actions">
+          <button class="modal-btn modal-btn-danger">APPLY ANYWAY</button>
+          <button class="modal-btn modal-btn-cancel">CANCEL</button>
+        </div>
+      </div>
+    `;
+    document.body.appendChild(overlay);
+    const close = (val: boolean) => { overlay.remove(); resolve(val); };
+    overlay.querySelector<HTMLButtonElement>(".modal-btn-danger")!.addEventListener("click", () => close(true));
+    overlay.querySelector<HTMLButtonElement>(".modal-btn-cancel")!.addEventListener("click", () => close(false));
+    overlay.addEventListener("click", (e) => { if (e.target === overlay) close(false); });
+  });
+}
+
+/** Run a script command; when it refuses high-risk tweaks, ask and retry
+ *  with them acknowledged. Resolves null when the user backs out. */
+async function withAcknowledgement<T>(call: (acknowledge: string[]) => Promise<T>): Promise<T | null> {
+  try {
+    return await call([]);
+  } catch (e) {
+    const err = e as CommandError;
+    if (!err || err.kind !== "unacknowledged" || !err.tweaks) throw e;
+    if (!(await confirmRiskyTweaks(err.tweaks))) return null;
+    return call(err.tweaks.map((t) => t.key));
+  }
+}
+
 async function exportScript() {
   try {
     const config = collectConfig();
-    const ps1 = await invoke<string>("generate_script", { config });
+    const ps1 = await withAcknowledgement((acknowledge) =>
+      invoke<string>("generate_script", { config, account: steamAccount(), acknowledge }));
+    if (ps1 === null) return;
     await invoke("save_script", { scriptContent: ps1 });
     toast("Script exported successfully");
   } catch (e) {
     console.error(e);
-    toast("Error generating script — check console (F12)", true);
+    toast(`Error generating script: ${errorText(e)}`, true);
   }
 }
 
+interface ImportedScriptConfig {
+  source: "header" | "recognised";
+  generator?: string;
+  section?: string;
+  config: OptimizationConfig;
+  toggles: Record<string, boolean>;
+}
+
+/** Restore toggles and values from the backend's reading of a script */
+function applyImportedConfig(imported: ImportedScriptConfig) {
+  for (const [key, on] of Object.entries(imported.toggles)) setChecked(key, on);
+  setAutoexecValues(imported.config.autoexec);
+  setVal("lo_thr", imported.config.launch_options.threads);
+  setVal("lo_custom", imported.config.launch_options.custom_args);
+  updateImpact();
+}
+
+function setAutoexecValues(ae: AutoexecConfig) {
+  setVal("ae_fps", ae.fps_max);
+  setVal("ae_rate", ae.rate);
+  setVal("ae_int", ae.cl_interp);
+  setVal("ae_ir", ae.cl_interp_ratio);
+  setVal("ae_ur", ae.cl_updaterate);
+  setVal("ae_cr", ae.cl_cmdrate);
+  setVal("ae_xhcode", ae.crosshair_code ?? "");
+  _crosshairCvars = ae.crosshair_cvars ?? [];
+  setVal("ae_custom", ae.custom_commands);
+}
+
 async function importScript() {
   try {
     const result = await invoke<{ path: string; content: string }>("import_script");
     _currentScriptPath = result.path;
-    parsePs1AndSetToggles(result.content);
+    try {
+      applyImportedConfig(await invoke<ImportedScriptConfig>("import_script_config", { content: result.content }));
+    } catch (e) {
+      // Not one of ours (or unreadable header) — fall back to text matching
+      console.warn(e);
+      parsePs1AndSetToggles(result.content);
+    }
     toast(`Imported: ${result.path.split("\\").pop()}`);
     refreshSystemState();
   } catch (e) {
@@ -1818,12 +2039,16 @@ async function runConfigAsAdmin() {
   try {
     const config = collectConfig();
     toast("Generating & launching as Admin…");
-    const msg = await invoke<string>("run_config_as_admin", { config });
+    const msg = await withAcknowledgement((acknowledge) =>
+      invoke<string>("run_config_as_admin", {
+        config, profile: getActiveSchema()?.name, account: steamAccount(), acknowledge, preflight: preflightEnabled(),
+      }));
+    if (msg === null) return;
     toast(msg);
     pollScriptReport();
   } catch (e) {
     console.error(e);
-    toast("Failed to run config as admin", true);
+    toast(`Failed to run config as admin: ${errorText(e)}`, true);
   }
 }
 
@@ -1831,7 +2056,10 @@ async function runSectionAsAdmin(section: string, label: string) {
   try {
     const config = collectConfig();
     toast(`Applying ${label}…`);
-    const msg = await invoke<string>("run_config_as_admin", { config, section });
+    const msg = await withAcknowledgement((acknowledge) => invoke<string>("run_config_as_admin", {
+      config, section, profile: getActiveSchema()?.name, account: steamAccount(), acknowledge, preflight: preflightEnabled(),
+    }));
+    if (msg === null) return;
     toast(msg);
     // Remove pending badge from the card
     const card = document.querySelector(`[data-section="${section}"]`);
@@ -1839,10 +2067,15 @@ async function runSectionAsAdmin(section: string, label: string) {
     pollScriptReport();
   } catch (e) {
     console.error(e);
-    toast(`Failed to apply ${label}`, true);
+    toast(`Failed to apply ${label}: ${errorText(e)}`, true);
   }
 }
 
+/** Restore point + registry export before elevated runs; on unless opted out */
+function preflightEnabled(): boolean {
+  return localStorage.getItem("csmooth_preflight") !== "off";
+}
+
 /** Poll for PS1 script report and show modal when ready */
 async function pollScriptReport() {
   let attempts = 0;
@@ -1876,9 +2109,43 @@ function showScriptReportModal(report: ScriptReport) {
   else statusText = "Failed";
 
   const errorsHtml = report.errors && report.errors.length > 0
-    ? '<div class="report-errors"><div class="report-errors-title">Errors (' + report.errors.length + '):</div>' + report.errors.map((e) => '<div class="report-error-item">• ' + e + "</div>").join("") + "</div>"
+    ? '<div class="report-errors"><div class="report-errors-title">Errors (' + report.errors.length + '):</div>' + report.errors.map((e) => '<div class="report-error-item">• ' + esc(e) + "</div>").join("") + "</div>"
     : "";
 
+  const problemSteps = (report.steps || []).filter((st) => st.status !== "ok");
+  const stepsHtml = problemSteps.length > 0
+    ? '<div class="report-errors"><div class="report-errors-title">Steps needing attention (' + problemSteps.length + '):</div>' + problemSteps.map((st) => {
+        const mark = st.status === "failed" ? "❌" : st.status === "skipped" ? "⏭️" : "⚠️";
+        const code = st.exit_code ? " (exit " + st.exit_code + ")" : "";
+        const detail = st.error ? " — " + esc(st.error) : "";
+        return '<div class="report-error-item">' + mark + " " + esc(st.description) + code + detail + "</div>";
+      }).join("") + "</div>"
+    : "";
+
+  const unreadable = report.unreadable_steps || [];
+  const unreadableHtml = unreadable.length > 0
+    ? '<div class="report-errors"><div class="report-errors-title">Unreadable step records (' + unreadable.length + '):</div>' + unreadable.map((e) => '<div class="report-error-item">❓ ' + esc(e) + "</div>").join("") + "</div>"
+    : "";
+
+  const ackHtml = report.acknowledged && report.acknowledged.length > 0
+    ? '<div class="report-errors"><div class="report-errors-title">High-risk tweaks acknowledged (' + report.acknowledged.length + '):</div>' + report.acknowledged.map((t) => '<div class="report-error-item">⚠️ ' + t.name + "</div>").join("") + "</div>"
+    : "";
+
+  const pf = report.preflight;
+  let preflightHtml = "";
+  if (pf) {
+    const rp = pf.restore_point === "created"
+      ? "✅ Restore point created: " + esc(pf.restore_point_name ?? "")
+      : pf.restore_point === "failed"
+        ? "⚠️ Restore point not created" + (pf.restore_point_error ? " — " + esc(pf.restore_point_error) : "")
+        : "⏭️ Restore point skipped";
+    const reg = pf.backup_dir
+      ? '<div class="report-error-item">' + (pf.export_errors.length ? "⚠️ " : "✅ ") + pf.exported.length + " registry key(s) exported to " + esc(pf.backup_dir) + "</div>" +
+        pf.export_errors.map((k) => '<div class="report-error-item">❌ Export failed: ' + esc(k) + "</div>").join("")
+      : "";
+    preflightHtml = '<div class="report-errors"><div class="report-errors-title">Pre-flight backup:</div><div class="report-error-item">' + rp + "</div>" + reg + "</div>";
+  }
+
   const modal = document.createElement("div");
   modal.id = "script-report-modal";
   modal.className = "modal-overlay";
@@ -1911,6 +2178,10 @@ function showScriptReportModal(report: ScriptReport) {
           <span class="report-stat-label">End</span>
         </div>
       </div>
+      ${preflightHtml}
+      ${ackHtml}
+      ${stepsHtml}
+      ${unreadableHtml}
       ${errorsHtml}
       <button class="modal-close-btn" id="report-close-btn">Close</button>
     </div>
@@ -1921,6 +2192,66 @@ function showScriptReportModal(report: ScriptReport) {
   document.getElementById("report-close-btn")?.addEventListener("click", () => modal.remove());
 }
 
+interface CompatIssue {
+  rule: string;
+  severity: "blocking" | "warning";
+  title: string;
+  explanation: string;
+  evidence: string[];
+}
+
+interface CompatReport {
+  rules_updated: string;
+  processes_checked: boolean;
+  rulesets: Array<{ id: string; name: string; blocking: CompatIssue[]; warnings: CompatIssue[] }>;
+}
+
+/** Check the current profile, running processes and cfg against FACEIT / ESEA / MM rules */
+async function checkCompetitiveCompat() {
+  try {
+    const hw = await getHwProfile();
+    const report = await invoke<CompatReport>("check_competitive_compat", {
+      config: collectConfig(),
+      cfg: collectCfgContent(),
+      osBuild: Number(hw.osBuild) || null,
+    });
+    showCompatModal(report);
+  } catch (e) {
+    toast(errorText(e), true);
+  }
+}
+
+function showCompatModal(report: CompatReport) {
+  document.getElementById("compat-modal")?.remove();
+  const issueHtml = (i: CompatIssue) =>
+    '<div class="report-error-item">' + (i.severity === "blocking" ? "❌ " : "⚠️ ") + "<b>" + esc(i.title) + "</b> — " + esc(i.explanation) +
+    i.evidence.map((ev) => '<div style="opacity:0.6;font-family:monospace;font-size:10px;">' + esc(ev) + "</div>").join("") + "</div>";
+  const setsHtml = report.rulesets.map((rs) => {
+    const issues = [...rs.blocking, ...rs.warnings];
+    const mark = rs.blocking.length ? "❌" : rs.warnings.length ? "⚠️" : "✅";
+    return '<div class="report-errors"><div class="report-errors-title">' + mark + " " + esc(rs.name) +
+      " — " + rs.blocking.length + " blocking, " + rs.warnings.length + " warning(s)</div>" +
+      issues.map(issueHtml).join("") + "</div>";
+  }).join("");
+  const note = report.processes_checked ? "" : '<div class="report-error-item">Running processes could not be read — process rules were skipped.</div>';
+
+  const modal = document.createElement("div");
+  modal.id = "compat-modal";
+  modal.className = "modal-overlay";
+  modal.innerHTML = `
+    <div class="modal-box report-modal">
+      <div class="modal-title">🛡 Competitive Compatibility</div>
+      ${setsHtml}
+      ${note}
+      <div style="opacity:0.4;font-size:10px;">Rules updated ${esc(report.rules_updated)}</div>
+      <button class="modal-close-btn" id="compat-close-btn">Close</button>
+    </div>
+  `;
+  document.body.appendChild(modal);
+  modal.addEventListener("click", (e) => { if (e.target === modal) modal.remove(); });
+  document.getElementById("compat-close-btn")?.addEventListener("click", () => modal.remove());
+}
+
 function copyLaunchOptions() {
   const opts: string[] = [];
   if (ck("lo_exec")) opts.push("+exec autoexec.cfg");
@@ -1937,6 +2268,170 @@ function copyLaunchOptions() {
   toast(`Launch options copied: ${str}`);
 }
 
+interface SteamAccount {
+  steam_id64: string;
+  account_id: number;
+  account_name: string;
+  persona_name: string;
+  last_login: number;
+  most_recent: boolean;
+  cfg_dir: string | null;
+}
+
+/** SteamID64 of the account cfgs and launch options go to; null = auto. */
+function steamAccount(): string | null {
+  return localStorage.getItem("csmooth_steam_account") || null;
+}
+
+function steamAccountRow(): HTMLDivElement {
+  const row = document.createElement("div");
+  row.className = "input-row";
+  attachTooltip(row, tipFromLegacy(TIP.steam_account));
+  const lbl = document.createElement("label");
+  lbl.htmlFor = "steam_account";
+  lbl.textContent = "Steam account";
+  const sel = document.createElement("select");
+  sel.id = "steam_account";
+  const auto = document.createElement("option");
+  auto.value = "";
+  auto.textContent = "Auto (game cfg folder, all accounts)";
+  sel.appendChild(auto);
+  sel.addEventListener("change", () => {
+    if (sel.value) localStorage.setItem("csmooth_steam_account", sel.value);
+    else localStorage.removeItem("csmooth_steam_account");
+  });
+  invoke<SteamAccount[]>("list_steam_accounts")
+    .then((accounts) => {
+      for (const a of accounts) {
+        const o = document.createElement("option");
+        o.value = a.steam_id64;
+        o.textContent = `${a.persona_name || a.account_name} (${a.account_name})${a.cfg_dir ? "" : " — CS2 not run yet"}`;
+        o.disabled = !a.cfg_dir;
+        sel.appendChild(o);
+      }
+      sel.value = steamAccount() ?? "";
+    })
+    .catch(console.error);
+  row.appendChild(lbl);
+  row.appendChild(sel);
+  return row;
+}
+
+/* ── CS2 video settings (cs2_video.txt / cs2_machine_convars.vcfg) ── */
+
+/** Field → value; a missing field is left as it is in CS2's files */
+type VideoSettings = Record<string, string | number | boolean>;
+
+interface VideoPreset {
+  id: string;
+  name: string;
+  description: string;
+  settings: VideoSettings;
+}
+
+const LEVEL_LOW_HIGH: [string, string][] = [["0", "Low"], ["1", "Medium"], ["2", "High"], ["3", "Very High"]];
+const VIDEO_FIELDS: { key: string; label: string; kind: "enum" | "level" | "bool"; options: [string, string][] }[] = [
+  { key: "aspect_ratio", label: "Aspect ratio", kind: "enum", options: [["4:3", "4:3"], ["16:9", "16:9"], ["16:10", "16:10"]] },
+  { key: "display_mode", label: "Display mode", kind: "enum", options: [["fullscreen", "Fullscreen"], ["fullscreen_windowed", "Fullscreen windowed"], ["windowed", "Windowed"]] },
+  { key: "vsync", label: "V-Sync", kind: "bool", options: [["false", "Off"], ["true", "On"]] },
+  { key: "boost_player_contrast", label: "Boost Player Contrast", kind: "bool", options: [["true", "Enabled"], ["false", "Disabled"]] },
+  { key: "msaa", label: "Multisampling AA", kind: "enum", options: [["off", "None"], ["cmaa2", "CMAA2"], ["2x", "2x MSAA"], ["4x", "4x MSAA"], ["8x", "8x MSAA"]] },
+  { key: "shadow_quality", label: "Shadow quality", kind: "level", options: LEVEL_LOW_HIGH },
+  { key: "dynamic_shadows", label: "Dynamic shadows", kind: "level", options: [["0", "Sun only"], ["1", "All"]] },
+  { key: "texture_detail", label: "Model / texture detail", kind: "level", options: LEVEL_LOW_HIGH.slice(0, 3) },
+  { key: "texture_filtering", label: "Texture filtering", kind: "level", options: [["0", "Bilinear"], ["1", "Trilinear"], ["2", "2x Anisotropic"], ["3", "4x Anisotropic"], ["4", "8x Anisotropic"], ["5", "16x Anisotropic"]] },
+  { key: "shader_detail", label: "Shader detail", kind: "level", options: [["0", "Low"], ["1", "High"]] },
+  { key: "particle_detail", label: "Particle detail", kind: "level", options: LEVEL_LOW_HIGH },
+  { key: "ambient_occlusion", label: "Ambient occlusion", kind: "level", options: [["0", "Disabled"], ["1", "Medium"], ["2", "High"]] },
+  { key: "hdr", label: "High dynamic range", kind: "enum", options: [["performance", "Performance"], ["quality", "Quality"]] },
+  { key: "fsr", label: "FidelityFX Super Resolution", kind: "level", options: [["0", "Disabled"], ["1", "Ultra Quality"], ["2", "Quality"], ["3", "Balanced"], ["4", "Performance"]] },
+  { key: "reflex", label: "NVIDIA Reflex", kind: "enum", options: [["off", "Disabled"], ["enabled", "Enabled"], ["boost", "Enabled + Boost"]] },
+];
+
+function videoSelect(id: string, label: string, options: [string, string][]): HTMLDivElement {
+  const row = document.createElement("div");
+  row.className = "input-row";
+  const lbl = document.createElement("label");
+  lbl.htmlFor = id;
+  lbl.textContent = label;
+  const sel = document.createElement("select");
+  sel.id = id;
+  for (const [value, text] of [["", "— keep —"], ...options]) {
+    const o = document.createElement("option");
+    o.value = value;
+    o.textContent = text;
+    sel.appendChild(o);
+  }
+  row.appendChild(lbl);
+  row.appendChild(sel);
+  return row;
+}
+
+function setVideoValues(v: VideoSettings) {
+  setVal("vid_width", v.width != null ? String(v.width) : "");
+  setVal("vid_height", v.height != null ? String(v.height) : "");
+  for (const f of VIDEO_FIELDS) setVal(`vid_${f.key}`, v[f.key] != null ? String(v[f.key]) : "");
+}
+
+function collectVideoSettings(): VideoSettings {
+  const out: VideoSettings = {};
+  for (const key of ["width", "height"]) {
+    const v = vl(`vid_${key}`, "").trim();
+    if (v) out[key] = parseInt(v, 10);
+  }
+  for (const f of VIDEO_FIELDS) {
+    const v = vl(`vid_${f.key}`, "");
+    if (!v) continue;
+    out[f.key] = f.kind === "level" ? Number(v) : f.kind === "bool" ? v === "true" : v;
+  }
+  return out;
+}
+
+async function readVideoSettings() {
+  try {
+    const r = await invoke<{ settings: VideoSettings; video_path: string; machine_path: string | null }>(
+      "read_video_settings", { account: steamAccount() });
+    setVideoValues(r.settings);
+    toast(`Video settings loaded from ${r.video_path}`);
+  } catch (e) {
+    toast(errorText(e), true);
+  }
+}
+
+async function writeVideoSettings() {
+  try {
+    const backups = await invoke<string[]>("write_video_settings", {
+      settings: collectVideoSettings(),
+      account: steamAccount(),
+    });
+    toast(backups.length
+      ? `✔ CS2 video settings written — ${backups.length} backup(s) saved next to the files`
+      : "CS2 video settings already match");
+  } catch (e) {
+    toast(errorText(e), true);
+  }
+}
+
+interface LaunchOptionsWrite {
+  path: string;
+  backup: string | null;
+}
+
+async function writeLaunchOptionsToSteam() {
+  try {
+    const written = await invoke<LaunchOptionsWrite[]>("apply_launch_options", {
+      launchOptions: collectConfig().launch_options,
+      account: steamAccount(),
+    });
+    const changed = written.filter((w) => w.backup).length;
+    toast(changed
+      ? `✔ Launch options set for ${changed} Steam account(s) — backup saved next to localconfig.vdf`
+      : "Launch options already up to date in Steam");
+  } catch (e) {
+    toast(errorText(e), true);
+  }
+}
+
 function cardApplyBtn(label: string, section: string, icon: string): HTMLButtonElement {
   const btn = document.createElement("button");
   btn.className = "card-apply-btn";
@@ -2081,6 +2576,21 @@ const TIP: Record<string, string> = {
   nv_ambient: "✔ AUTO (Registry): Disables Ambient Occlusion globally. +1.0% (+3 FPS). Saves GPU shader cycles.",
   nv_fxaa: "✔ AUTO (Registry): Disables global FXAA (forced antialiasing). +0.5% (+1 FPS). Lets CS2 handle AA.",
 
+  /* AMD Radeon — auto-applied via registry (shown only on AMD GPUs) */
+  amd_antilag: "✔ AUTO (Registry): Enables Radeon Anti-Lag. +1.5% (+4 FPS). Cuts input-to-display latency when CPU-bound.",
+  amd_esync: "✔ AUTO (Registry): Disables Enhanced Sync. +1.0% (+3 FPS). Removes the extra buffered frame and its latency.",
+  amd_chill: "✔ AUTO (Registry): Disables Radeon Chill. +2.0% (+5 FPS). Stops the driver from capping FPS when you stand still.",
+  amd_surface: "✔ AUTO (Registry): Enables Surface Format Optimization. +0.5% (+1 FPS). Lets the driver use lighter render target formats.",
+  amd_shader: "✔ AUTO (PowerShell): Resets the AMD DirectX / Vulkan / OpenGL shader cache. 0 FPS — fixes stutter after driver updates.",
+  amd_rebar: "✔ AUTO (Check): Reports whether Smart Access Memory (Resizable BAR) is active. Changes nothing — enable it in BIOS if off.",
+
+  /* Intel Arc — auto-applied via registry (shown only on Intel Arc GPUs) */
+  intel_lowlat: "✔ AUTO (Registry): Enables Arc Low Latency mode. +1.5% (+4 FPS). Shortens the render queue like NVIDIA Ultra Low Latency.",
+  intel_sync: "✔ AUTO (Registry): Disables Speed Sync and Smart Sync. +1.0% (+3 FPS). Removes the extra buffered frame and its latency.",
+  intel_endurance: "✔ AUTO (Registry): Disables Endurance Gaming. +2.0% (+5 FPS). Stops the power-saving FPS limiter.",
+  intel_shader: "✔ AUTO (PowerShell): Resets the Intel shader cache. 0 FPS — fixes stutter after driver updates.",
+  intel_rebar: "✔ AUTO (Check): Reports whether Resizable BAR is active. Arc GPUs lose a lot of performance without it — enable it in BIOS if off.",
+
   /* Services — auto-applied via Stop-Service/Set-Service */
   s_sys: "✔ AUTO (Service): Stops and disables SysMain (Superfetch). +1.5% (+4 FPS). Frees RAM + disk I/O.",
   s_diag: "✔ AUTO (Service): Stops and disables DiagTrack telemetry. +0.5% (+1 FPS).",
@@ -2099,7 +2609,9 @@ const TIP: Record<string, string> = {
   /* autoexec.cfg — auto-generated by the app, written to CS2 cfg folder */
   ae_on: "✔ AUTO (File): The app generates autoexec.cfg and writes it to your CS2 cfg folder when you Export or Run.",
   ae_raw: "✔ AUTO (CFG): Sets m_rawinput 1 in autoexec.cfg. Raw mouse input for consistent aim. 0 FPS.",
+  ae_xhcode: "✔ AUTO (CFG): Paste a CS2 crosshair share code (CSGO-xxxxx-…). Its cl_crosshair* values are written to autoexec.cfg. 0 FPS.",
 
+  steam_account: "✔ AUTO (Steam): Which Steam account autoexec.cfg, saved cfgs and launch options go to. CS2 reads per-account cfgs from userdata/<id>/730/local/cfg. Auto uses the game's cfg folder and writes launch options for every account.",
   /* Launch Options — generated by the app, user must paste into Steam */
   lo_exec: '⚠ MANUAL PASTE: Generates "+exec autoexec" for Steam Launch Options. You must paste this into Steam → CS2 → Properties → Launch Options.',
   lo_nvid: '⚠ MANUAL PASTE: Generates "-novid" to skip intro video. 0 FPS. Paste into Steam Launch Options.',
@@ -2519,7 +3031,7 @@ function resetCfgDefaults() {
 async function saveCfgFile() {
   try {
     const content = collectCfgContent();
-    await invoke("save_cfg", { cfgContent: content });
+    await invoke("save_cfg", { cfgContent: content, account: steamAccount() });
     toast("CFG saved successfully");
   } catch (e) {
     console.error(e);
@@ -2532,11 +3044,43 @@ async function loadCfgFile() {
     const r = await invoke<{ path: string; content: string }>("load_cfg");
     parseCfgContent(r.content);
     toast(`Loaded: ${r.path.split("\\").pop()}`);
+    invoke<CfgLintIssue[]>("lint_cfg", { content: r.content })
+      .then(showCfgLint)
+      .catch(console.error);
+    // Structured autoexec fields; everything else lands in custom commands
+    invoke<AutoexecConfig>("import_autoexec", { content: r.content, current: collectConfig().autoexec })
+      .then((ae) => {
+        setAutoexecValues(ae);
+        setChecked("ae_on", ae.enabled);
+        setChecked("ae_raw", ae.m_rawinput);
+        updateImpact();
+      })
+      .catch(console.error);
   } catch (e) {
     console.error(e);
   }
 }
 
+interface CfgLintIssue {
+  line: number;
+  severity: "error" | "warning" | "info";
+  code: string;
+  name: string;
+  message: string;
+}
+
+function showCfgLint(issues: CfgLintIssue[]) {
+  const errors = issues.filter((i) => i.severity === "error");
+  const warnings = issues.filter((i) => i.severity === "warning");
+  issues.forEach((i) => console.info(`cfg line ${i.line}: [${i.severity}] ${i.message}`));
+  if (!errors.length && !warnings.length) return;
+  const first = errors[0] ?? warnings[0];
+  toast(
+    `CFG check: ${errors.length} error(s), ${warnings.length} warning(s) — line ${first.line}: ${first.message}`,
+    errors.length > 0,
+  );
+}
+
 /* ================================================================
    Pro Player Configs
    ================================================================ */
@@ -2913,6 +3457,35 @@ function migrateProfilesToSchemas(): void {
 // Run migration on load
 migrateProfilesToSchemas();
 
+/** Mirror a schema into the backend profile store (keeps a revision per save) */
+function backupSchemaToBackend(schema: Schema): void {
+  invoke("save_profile", { name: schema.name, config: collectConfig(), ui: schema }).catch(console.error);
+}
+
+/** After a WebView storage reset, bring schemas back from the backend profile store */
+async function restoreSchemasFromBackend(): Promise<void> {
+  if (getSchemas().length > 0) return;
+  try {
+    const profiles = await invoke<{ name: string; error: string | null }[]>("list_profiles");
+    const restored: Schema[] = [];
+    for (const p of profiles) {
+      if (p.error) {
+        console.warn("Profile " + p.name + " can't be read: " + p.error);
+        continue;
+      }
+      const loaded = await invoke<{ ui?: Schema }>("load_profile", { name: p.name });
+      if (loaded.ui?.id && loaded.ui.cfgLayout) restored.push({ ...loaded.ui, name: p.name });
+    }
+    if (restored.length === 0 || getSchemas().length > 0) return;
+    saveSchemas(restored);
+    setActiveSchemaId(restored[0].id);
+    window.location.reload();
+  } catch (e) {
+    console.error(e);
+  }
+}
+restoreSchemasFromBackend();
+
 /* ================================================================
    HW / PROC / NET tab builders (called from build)
    ================================================================ */
@@ -2975,6 +3548,33 @@ interface ScriptReport {
   errors: string[];
   start_time: string;
   end_time: string;
+  failed_steps?: number;
+  warning_steps?: number;
+  skipped_steps?: number;
+  snapshot_id?: string;
+  acknowledged?: RiskNotice[];
+  preflight?: PreflightResult;
+  steps?: ScriptStep[];
+  unreadable_steps?: string[];
+}
+
+interface PreflightResult {
+  restore_point: "created" | "failed" | "skipped";
+  restore_point_name?: string;
+  restore_point_error?: string;
+  backup_dir?: string;
+  exported: string[];
+  export_errors: string[];
+}
+
+interface ScriptStep {
+  step: string;
+  section?: string;
+  description: string;
+  status: "ok" | "warning" | "failed" | "skipped";
+  exit_code?: number;
+  error?: string;
+  duration_ms: number;
 }
 
 const DRV_CATEGORY_ICONS: Record<string, string> = {
@@ -3468,10 +4068,48 @@ interface BenchmarkResult {
   stutter_count: number;
   stutter_pct: number;
   dropped_frames: number;
+  format: CaptureFormat;
   frametimes: number[];
   timestamps: number[];
   fps_values: number[];
-}
+  series: CaptureSeries;
+  analysis: BottleneckAnalysis;
+}
+
+/** CPU/GPU limit, latency and present modes of a capture. */
+interface BottleneckAnalysis {
+  verdict: "cpu_bound" | "gpu_bound" | "capped" | "mixed" | "unknown";
+  summary: string;
+  source?: "busy" | "gpu_busy" | "gpu_load";
+  frames_analyzed: number;
+  cpu_bound_pct: number;
+  gpu_bound_pct: number;
+  capped_pct: number;
+  latency?: { source: "pc_latency" | "display"; avg_ms: number; p99_ms: number; frames: number };
+  present_modes: { mode: string; frames: number; pct: number }[];
+  notes: string[];
+}
+
+type CaptureFormat = "presentmon" | "presentmon2" | "frameview" | "ocat" | "mangohud" | "capframex";
+
+/** Extra per-frame series; null where a frame has no value. */
+interface CaptureSeries {
+  display_change?: (number | null)[];
+  cpu_busy?: (number | null)[];
+  gpu_busy?: (number | null)[];
+  pc_latency?: (number | null)[];
+  cpu_load?: (number | null)[];
+  gpu_load?: (number | null)[];
+}
+
+const CAPTURE_FORMAT_LABELS: Record<CaptureFormat, string> = {
+  presentmon: "PresentMon",
+  presentmon2: "PresentMon 2.x",
+  frameview: "FrameView",
+  ocat: "OCAT",
+  mangohud: "MangoHud",
+  capframex: "CapFrameX",
+};
 
 let _currentBenchResult: BenchmarkResult | null = null;
 const benchHistory: BenchmarkResult[] = [];
@@ -3742,12 +4380,271 @@ function showCapFrameXPicker(files: string[]) {
   });
 }
 
+/* ── Before/after comparison ─────────────────────────────────────── */
+
+interface BenchmarkComparison {
+  baseline: Array<{ file_name: string; process_name: string; duration_secs: number; frame_count: number }>;
+  candidate: Array<{ file_name: string; process_name: string; duration_secs: number; frame_count: number }>;
+  metrics: Array<{
+    metric: string; label: string; unit: string; higher_is_better: boolean;
+    baseline: number; candidate: number; delta: number; delta_pct: number;
+    ci_low: number; ci_high: number; significant: boolean;
+  }>;
+  mann_whitney: { u: number; z: number; p_value: number; prob_faster: number };
+  confidence: number;
+  iterations: number;
+  verdict: "better" | "worse" | "mixed" | "no_difference";
+  summary: string;
+  warnings: string[];
+}
+
+const benchCompareFiles: { baseline: string[]; candidate: string[] } = { baseline: [], candidate: [] };
+
+function showBenchCompareModal(result?: BenchmarkComparison) {
+  document.getElementById("bench-compare-modal")?.remove();
+  const name = (p: string) => p.split("\\").pop() || p;
+  const list = (side: "baseline" | "candidate") =>
+    benchCompareFiles[side].map((f, i) => '<div class="report-error-item">📄 ' + esc(name(f)) + ' <a href="#" data-remove="' + side + ":" + i + '">✖</a></div>').join("") ||
+    '<div class="report-error-item" style="opacity:0.5;">No runs yet</div>';
+
+  let resultHtml = "";
+  if (result) {
+    const mark = { better: "✅", worse: "❌", mixed: "⚠️", no_difference: "➖" }[result.verdict];
+    const sign = (v: number, d: number) => (v >= 0 ? "+" : "") + v.toFixed(d);
+    const rows = result.metrics.map((m) => {
+      const d = m.unit === "ms" ? 2 : 1;
+      const good = m.significant && (m.delta > 0) === m.higher_is_better;
+      const color = !m.significant ? "" : good ? "color:#4ade80;" : "color:#f87171;";
+      return "<tr><td>" + esc(m.label) + "</td><td>" + m.baseline.toFixed(d) + "</td><td>" + m.candidate.toFixed(d) + '</td><td style="' + color + '">' +
+        sign(m.delta, d) + " " + m.unit + " (" + sign(m.delta_pct, 1) + "%)</td><td>" + sign(m.ci_low, d) + " … " + sign(m.ci_high, d) + "</td></tr>";
+    }).join("");
+    resultHtml =
+      '<div class="report-errors"><div class="report-errors-title">' + mark + " " + esc(result.summary) + "</div>" +
+      '<table style="width:100%;font-size:11px;"><tr><th></th><th>Baseline</th><th>Candidate</th><th>Δ</th><th>' + Math.round(result.confidence * 100) + "% CI</th></tr>" + rows + "</table>" +
+      '<div style="opacity:0.6;font-size:10px;">Mann–Whitney U on frame times: z = ' + result.mann_whitney.z.toFixed(2) + ", p = " + result.mann_whitney.p_value.toPrecision(2) +
+      ", candidate frame faster in " + (result.mann_whitney.prob_faster * 100).toFixed(1) + "% of pairs · " + result.iterations + " bootstrap resamples</div></div>" +
+      result.warnings.map((w) => '<div class="report-error-item">⚠️ ' + esc(w) + "</div>").join("");
+  }
+
+  const modal = document.createElement("div");
+  modal.id = "bench-compare-modal";
+  modal.className = "modal-overlay";
+  modal.innerHTML = `
+    <div class="modal-box report-modal">
+      <div class="modal-title">⚖ Before / After</div>
+      <div class="report-errors"><div class="report-errors-title">Baseline runs</div>${list("baseline")}</div>
+      <div class="report-errors"><div class="report-errors-title">Candidate runs</div>${list("candidate")}</div>
+      <div style="display:flex;gap:6px;flex-wrap:wrap;">
+        <button class="btn-import" data-add="baseline">+ Baseline run</button>
+        <button class="btn-import" data-add="candidate">+ Candidate run</button>
+        <button class="btn-run" id="bench-compare-run">Compare</button>
+      </div>
+      ${resultHtml}
+      <button class="modal-close-btn" id="bench-compare-close">Close</button>
+    </div>
+  `;
+  document.body.appendChild(modal);
+
+  modal.querySelectorAll<HTMLElement>("[data-add]").forEach((b) => b.addEventListener("click", async () => {
+    try {
+      const path = await invoke<string>("pick_benchmark_file");
+      benchCompareFiles[b.dataset.add as "baseline" | "candidate"].push(path);
+      showBenchCompareModal();
+    } catch (e) {
+      if (String(e) !== "Cancelled") toast(errorText(e), true);
+    }
+  }));
+  modal.querySelectorAll<HTMLElement>("[data-remove]").forEach((a) => a.addEventListener("click", (e) => {
+    e.preventDefault();
+    const [side, idx] = a.dataset.remove!.split(":");
+    benchCompareFiles[side as "baseline" | "candidate"].splice(Number(idx), 1);
+    showBenchCompareModal();
+  }));
+  document.getElementById("bench-compare-run")?.addEventListener("click", async () => {
+    try {
+      toast("Comparing runs…");
+      showBenchCompareModal(await invoke<BenchmarkComparison>("compare_benchmarks", benchCompareFiles));
+    } catch (e) {
+      toast(errorText(e), true);
+    }
+  });
+  modal.addEventListener("click", (e) => { if (e.target === modal) modal.remove(); });
+  document.getElementById("bench-compare-close")?.addEventListener("click", () => modal.remove());
+}
+
+/* ── Tweak bisection ─────────────────────────────────────────────── */
+
+interface BisectionSample {
+  file_name: string;
+  avg_fps: number;
+  p1_fps: number;
+  frame_count: number;
+  duration_secs: number;
+}
+
+interface BisectionStep {
+  number: number;
+  kind: "baseline" | "group";
+  apply: string[];
+  restart: boolean;
+}
+
+interface BisectionSession {
+  started: string;
+  profile?: string;
+  candidates: string[];
+  excluded: string[];
+  baseline_runs: number;
+  baseline: BisectionSample[];
+  noise?: { avg_fps_pct: number; p1_fps_pct: number };
+  queue: string[][];
+  next?: BisectionStep;
+  history: Array<{ number: number; kind: "baseline" | "group"; apply: string[]; sample: BisectionSample; avg_fps_delta_pct?: number; p1_fps_delta_pct?: number; changed: boolean }>;
+  found: Array<{ key: string; name: string; avg_fps_delta_pct: number; p1_fps_delta_pct: number; combined: string[] }>;
+  cleared: string[];
+}
+
+async function openBisection() {
+  try {
+    showBisectionModal(await invoke<BisectionSession | null>("get_bisection"));
+  } catch (e) {
+    toast(errorText(e), true);
+  }
+}
+
+async function bisectionAction(action: string) {
+  try {
+    if (action === "start") {
+      const current = await invoke<BisectionSession | null>("get_bisection");
+      if (current?.next && !confirm(`A bisection is at step ${current.next.number}. Discard it and start over?`)) return;
+      const session = await invoke<BisectionSession>("start_bisection", { config: collectConfig(), profile: getActiveSchema()?.name, overwrite: !!current?.next });
+      toast(`Bisection started with ${session.candidates.length} tweaks`);
+      showBisectionModal(session);
+    } else if (action === "run") {
+      const msg = await withAcknowledgement((acknowledge) =>
+        invoke<string>("run_bisection_step", { acknowledge, preflight: preflightEnabled() }));
+      if (msg !== null) toast(msg);
+    } else if (action === "export") {
+      const ps1 = await withAcknowledgement((acknowledge) => invoke<string>("export_bisection_script", { acknowledge }));
+      if (ps1 === null) return;
+      await invoke("save_script", { scriptContent: ps1 });
+      toast("Bisection step exported");
+    } else if (action === "capture") {
+      const path = await invoke<string>("pick_benchmark_file");
+      showBisectionModal(await invoke<BisectionSession>("record_bisection_capture", { path }));
+    } else if (action === "cancel") {
+      if (!confirm("Discard the bisection session? Tweaks stay as the last step left them.")) return;
+      await invoke("cancel_bisection");
+      showBisectionModal(null);
+    }
+  } catch (e) {
+    if (String(e) !== "Cancelled") toast(errorText(e), true);
+  }
+}
+
+function showBisectionModal(session: BisectionSession | null) {
+  document.getElementById("bisect-modal")?.remove();
+  const pct = (v?: number) => (v === undefined || v === null ? "—" : (v > 0 ? "+" : "") + v.toFixed(1) + "%");
+  let body: string;
+  let buttons: string;
+  if (!session) {
+    body = '<div class="report-error-item">Applies the current profile\'s tweaks in halves, one step at a time, and asks for a PresentMon or CapFrameX capture after each step. Groups that move avg FPS or 1% lows beyond the noise are split until single tweaks are left. BIOS and one-way tweaks are left out. The session survives reboots.</div>';
+    buttons = '<button class="btn-run" data-bisect="start">▶ Start with current profile</button>';
+  } else {
+    const step = session.next;
+    let stepHtml: string;
+    if (!step) {
+      stepHtml = '<div class="report-error-item">✅ Finished — ' + session.found.length + " tweak(s) moved the benchmark.</div>";
+    } else if (step.kind === "baseline") {
+      stepHtml = '<div class="report-error-item">Step ' + step.number + ": baseline " + (session.baseline.length + 1) + "/" + session.baseline_runs +
+        " — all " + session.candidates.length + " tweaks reverted.</div>";
+    } else {
+      stepHtml = '<div class="report-error-item">Step ' + step.number + ": " + step.apply.length + " tweak(s) applied, the rest reverted — " + step.apply.join(", ") + "</div>";
+    }
+    if (step?.restart) stepHtml += '<div class="report-error-item">🔄 Restart Windows after running this step, then capture.</div>';
+    const noise = session.noise
+      ? '<div style="opacity:0.6;font-size:10px;">Noise threshold: avg FPS ±' + session.noise.avg_fps_pct.toFixed(1) + "%, 1% lows ±" + session.noise.p1_fps_pct.toFixed(1) + "%</div>"
+      : "";
+    const found = session.found.length
+      ? '<div class="report-errors"><div class="report-errors-title">Tweaks that moved the benchmark:</div>' +
+        session.found.map((f) => '<div class="report-error-item">' + (f.avg_fps_delta_pct + f.p1_fps_delta_pct >= 0 ? "📈 " : "📉 ") + esc(f.name) + (f.combined.length ? " (only together)" : "") +
+          " — avg " + pct(f.avg_fps_delta_pct) + ", 1% lows " + pct(f.p1_fps_delta_pct) + "</div>").join("") + "</div>"
+      : "";
+    const history = session.history.length
+      ? '<div class="report-errors"><div class="report-errors-title">Steps (' + session.history.length + "):</div>" +
+        session.history.map((r) => '<div class="report-error-item">' + (r.kind === "baseline" ? "📏 Baseline" : (r.changed ? "⚠️ " : "✔ ") + r.apply.length + " tweak(s)") +
+          " — " + r.sample.avg_fps.toFixed(0) + " fps avg (" + pct(r.avg_fps_delta_pct) + "), " + r.sample.p1_fps.toFixed(0) + " fps 1% (" + pct(r.p1_fps_delta_pct) + ")</div>").join("") + "</div>"
+      : "";
+    body = stepHtml + noise + found + history +
+      '<div style="opacity:0.4;font-size:10px;">' + session.cleared.length + " cleared · " + session.excluded.length + " left out (BIOS / one-way)</div>";
+    buttons = step
+      ? '<button class="btn-run" data-bisect="run">🛡 Run step (Admin)</button><button class="btn-export" data-bisect="export">Export step .ps1</button><button class="btn-import" data-bisect="capture">📂 Record capture</button>'
+      : "";
+    buttons += '<button class="btn-import" data-bisect="cancel">' + (step ? "✖ Cancel" : "🗑 Clear") + "</button>";
+  }
+
+  const modal = document.createElement("div");
+  modal.id = "bisect-modal";
+  modal.className = "modal-overlay";
+  modal.innerHTML = `
+    <div class="modal-box report-modal">
+      <div class="modal-title">🔬 Tweak Bisection</div>
+      ${body}
+      <div style="display:flex;gap:6px;flex-wrap:wrap;">${buttons}</div>
+      <button class="modal-close-btn" id="bisect-close-btn">Close</button>
+    </div>
+  `;
+  document.body.appendChild(modal);
+  modal.querySelectorAll<HTMLElement>("[data-bisect]").forEach((b) =>
+    b.addEventListener("click", () => bisectionAction(b.dataset.bisect!)));
+  modal.addEventListener("click", (e) => { if (e.target === modal) modal.remove(); });
+  document.getElementById("bisect-close-btn")?.addEventListener("click", () => modal.remove());
+}
+
 function stutterCls(pct: number): string {
   if (pct > 2) return "bad";
   if (pct > 0.5) return "warn";
   return "";
 }
 
+function bottleneckPanel(a: BottleneckAnalysis): HTMLElement {
+  const panel = document.createElement("div");
+  panel.className = "bench-assessment bench-bottleneck";
+  const rows: string[] = [`<div class="bench-bottleneck-verdict ${esc(a.verdict)}">${esc(a.summary)}</div>`];
+  if (a.frames_analyzed > 0) {
+    const seg = (cls: string, pct: number, label: string) =>
+      pct > 0 ? `<span class="bench-bound-seg ${cls}" style="width:${pct}%" title="${label} ${pct.toFixed(1)}%"></span>` : "";
+    rows.push(
+      `<div class="bench-bound-bar">${seg("cpu", a.cpu_bound_pct, "CPU-bound")}${seg("gpu", a.gpu_bound_pct, "GPU-bound")}${seg("cap", a.capped_pct, "Capped")}</div>`,
+      `<div class="bench-tip">CPU ${a.cpu_bound_pct.toFixed(0)}% · GPU ${a.gpu_bound_pct.toFixed(0)}% · capped ${a.capped_pct.toFixed(0)}% of ${a.frames_analyzed.toLocaleString()} frames</div>`,
+    );
+  }
+  if (a.latency) {
+    const label = a.latency.source === "pc_latency" ? "PC latency (input → display)" : "Present → display";
+    rows.push(`<div class="bench-tip">⏲ ${label}: avg ${a.latency.avg_ms.toFixed(1)}ms · p99 ${a.latency.p99_ms.toFixed(1)}ms</div>`);
+  }
+  if (a.present_modes.length) {
+    rows.push(`<div class="bench-tip">🖥 ${a.present_modes.map((m) => `${esc(m.mode)} ${m.pct.toFixed(0)}%`).join(" · ")}</div>`);
+  }
+  rows.push(...a.notes.map((n) => `<div class="bench-tip">⚠ ${esc(n)}</div>`));
+  panel.innerHTML = `<div class="bench-assessment-title">🧮 Bottleneck</div>` + rows.join("");
+  return panel;
+}
+
+/** Bottleneck verdict of the loaded capture, for the advisor prompts. */
+function benchAdvisorContext(): string {
+  const r = _currentBenchResult;
+  if (!r) return "";
+  const a = r.analysis;
+  const lines = [
+    `Benchmark (${r.process_name}, ${CAPTURE_FORMAT_LABELS[r.format] ?? r.format}): avg ${r.avg_fps.toFixed(0)} FPS, 1% low ${r.p1_fps.toFixed(0)} FPS`,
+    `Bottleneck: ${a.summary}`,
+  ];
+  if (a.latency) lines.push(`${a.latency.source === "pc_latency" ? "PC latency" : "Present-to-display latency"}: avg ${a.latency.avg_ms.toFixed(1)}ms, p99 ${a.latency.p99_ms.toFixed(1)}ms`);
+  if (a.present_modes.length) lines.push(`Present modes: ${a.present_modes.map((m) => `${m.mode} ${m.pct.toFixed(0)}%`).join(", ")}`);
+  return `\n\n${lines.join("\n")}`;
+}
+
 function displayBenchmarkResult(r: BenchmarkResult) {
   const el = document.getElementById("bench-content");
   if (!el) return;
@@ -3760,6 +4657,7 @@ function displayBenchmarkResult(r: BenchmarkResult) {
   info.innerHTML = `
     <span class="bench-info-label">📂 ${r.file_name}</span>
     <span class="bench-info-process">🎮 ${r.process_name}</span>
+    <span class="bench-info-format">🛠 ${CAPTURE_FORMAT_LABELS[r.format] ?? r.format}</span>
     <span class="bench-info-duration">⏱ ${r.duration_secs.toFixed(1)}s</span>
     <span class="bench-info-frames">${r.frame_count.toLocaleString()} frames</span>
   `;
@@ -3807,6 +4705,8 @@ function displayBenchmarkResult(r: BenchmarkResult) {
   assessment.innerHTML = `<div class="bench-assessment-title">🎮 CS2 Analysis</div>` + tips.map((t) => `<div class="bench-tip">${t}</div>`).join("");
   el.appendChild(assessment);
 
+  el.appendChild(bottleneckPanel(r.analysis));
+
   // Chart mode toggle
   const chartControls = document.createElement("div");
   chartControls.className = "bench-chart-controls";
@@ -4048,7 +4948,7 @@ async function refreshDriverInfo() {
               }
             } catch (e) {
               installBtn.textContent = "❌ Error";
-              toast(`Error installing driver: ${e}`, true);
+              toast(`Error installing driver: ${errorText(e)}`, true);
             }
           });
         }
@@ -4581,7 +5481,7 @@ Base your assessment on the demo metadata (duration, rounds, map, tickrate) and
 Keep each tip assessment to ONE line. Reply in the same language as the user notes (default English).`,
 
   hw: `You are a hardware analyst for competitive CS2 gaming. Analyze the system specs provided and give:
-1. Bottleneck analysis (CPU vs GPU vs RAM)
+1. Bottleneck analysis (CPU vs GPU vs RAM); when a benchmark bottleneck verdict is included, trust it over estimates from the specs
 2. CS2-specific performance expectations (estimated FPS at 1080p low)
 3. Upgrade priority recommendations with budget tiers
 4. BIOS/hardware settings to check for this specific hardware
@@ -5120,7 +6020,7 @@ function build() {
   const btnBenchTab = document.createElement("button");
   btnBenchTab.className = "sidebar-btn";
   btnBenchTab.innerHTML = `<svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="1.5"><path d="M18 20V10"/><path d="M12 20V4"/><path d="M6 20v-6"/></svg><span>Benchmark</span>`;
-  attachTooltip(btnBenchTab, tipHtml("Benchmark", "Import PresentMon CSV or CapFrameX JSON benchmark captures. Visualizes FPS over time, frame time distribution, 1% / 0.1% lows, and stutter analysis. Compare before/after optimization runs."));
+  attachTooltip(btnBenchTab, tipHtml("Benchmark", "Import PresentMon, FrameView, OCAT or MangoHud CSV, or CapFrameX JSON benchmark captures. Visualizes FPS over time, frame time distribution, 1% / 0.1% lows, and stutter analysis. Compare before/after optimization runs."));
 
   /* ── Community placeholder buttons ─────────────────────────── */
   const sidebarSep = document.createElement("div");
@@ -6016,6 +6916,29 @@ function build() {
   c4.appendChild(cardRecommendBtn("nvidia"));
   c4.appendChild(cardApplyBtn("Apply NVIDIA", "nvidia", "🛡"));
 
+  /* ── 4b. AMD Radeon ──────────────────────────────────────────── */
+  const c4amd = card("AMD Radeon", '⚠ Changes are only applied when you click "Apply AMD".');
+  c4amd.dataset.section = "amd";
+  c4amd.appendChild(toggle("amd_antilag", "Radeon Anti-Lag", true, TIP.amd_antilag));
+  c4amd.appendChild(toggle("amd_esync", "Disable Enhanced Sync", true, TIP.amd_esync));
+  c4amd.appendChild(toggle("amd_chill", "Disable Radeon Chill", true, TIP.amd_chill));
+  c4amd.appendChild(toggle("amd_surface", "Surface Format Optimization", true, TIP.amd_surface));
+  c4amd.appendChild(toggle("amd_shader", "Reset Shader Cache", false, TIP.amd_shader));
+  c4amd.appendChild(toggle("amd_rebar", "Check SAM / ReBAR", true, TIP.amd_rebar));
+  c4amd.appendChild(cardRecommendBtn("amd"));
+  c4amd.appendChild(cardApplyBtn("Apply AMD", "amd", "🛡"));
+
+  /* ── 4c. Intel Arc ───────────────────────────────────────────── */
+  const c4intel = card("Intel Arc", '⚠ Changes are only applied when you click "Apply Intel".');
+  c4intel.dataset.section = "intel";
+  c4intel.appendChild(toggle("intel_lowlat", "Low Latency Mode", true, TIP.intel_lowlat));
+  c4intel.appendChild(toggle("intel_sync", "Disable Speed / Smart Sync", true, TIP.intel_sync));
+  c4intel.appendChild(toggle("intel_endurance", "Disable Endurance Gaming", true, TIP.intel_endurance));
+  c4intel.appendChild(toggle("intel_shader", "Reset Shader Cache", false, TIP.intel_shader));
+  c4intel.appendChild(toggle("intel_rebar", "Check Resizable BAR", true, TIP.intel_rebar));
+  c4intel.appendChild(cardRecommendBtn("intel"));
+  c4intel.appendChild(cardApplyBtn("Apply Intel", "intel", "🛡"));
+
   /* ── 5. Services ─────────────────────────────────────────────── */
   const c5 = card("Services", '⚠ Changes are only applied when you click "Apply Services".');
   c5.dataset.section = "services";
@@ -6039,6 +6962,7 @@ function build() {
   const c6 = card("autoexec.cfg", '⚠ Changes are only applied when you click "Save autoexec".');
   c6.dataset.section = "autoexec";
   c6.appendChild(toggle("ae_on", "Generate autoexec.cfg", true, TIP.ae_on, false));
+  c6.appendChild(steamAccountRow());
   c6.appendChild(numInput("ae_fps", "fps_max", "400"));
   c6.appendChild(numInput("ae_rate", "rate", "786432"));
   c6.appendChild(numInput("ae_int", "cl_interp", "0"));
@@ -6046,6 +6970,17 @@ function build() {
   c6.appendChild(numInput("ae_ur", "cl_updaterate", "128"));
   c6.appendChild(numInput("ae_cr", "cl_cmdrate", "128"));
   c6.appendChild(toggle("ae_raw", "m_rawinput 1", true, TIP.ae_raw, false));
+  const xhRow = numInput("ae_xhcode", "Crosshair code", "", TIP.ae_xhcode);
+  xhRow.querySelector("input")?.addEventListener("change", (e) => {
+    const code = (e.target as HTMLInputElement).value.trim();
+    // A code typed in by hand writes every crosshair cvar
+    _crosshairCvars = [];
+    if (!code) return;
+    invoke<{ name: string; value: string }[]>("decode_crosshair", { code })
+      .then((cvars) => toast(`Crosshair code OK — ${cvars.length} cl_crosshair* cvars will be written to autoexec.cfg`))
+      .catch((err) => toast(errorText(err), true));
+  });
+  c6.appendChild(xhRow);
   const customLabel = document.createElement("label");
   customLabel.textContent = "Custom commands:";
   customLabel.className = "helper";
@@ -6067,7 +7002,7 @@ function build() {
   }
 
   /* ── 7. Launch Options ───────────────────────────────────────── */
-  const c7 = card("Launch Options", '⚠ Click "Copy" and paste in Steam → CS2 → Properties → Launch Options.');
+  const c7 = card("Launch Options", '⚠ Click "Write to Steam" with Steam closed, or "Copy" and paste in Steam → CS2 → Properties → Launch Options.');
   c7.dataset.section = "launch";
   c7.appendChild(toggle("lo_exec", "+exec autoexec.cfg", true, TIP.lo_exec, false));
   c7.appendChild(toggle("lo_nvid", "-novid", true, TIP.lo_nvid, false));
@@ -6093,12 +7028,68 @@ function build() {
     btn.title = "Copy the launch options to clipboard";
     btn.addEventListener("click", copyLaunchOptions);
     c7.appendChild(btn);
+    const btnSteam = document.createElement("button");
+    btnSteam.className = "card-apply-btn";
+    btnSteam.innerHTML = "🎮 Write to Steam";
+    btnSteam.title = "Set the launch options in Steam directly (Steam must be closed; localconfig.vdf is backed up first)";
+    btnSteam.addEventListener("click", writeLaunchOptionsToSteam);
+    c7.appendChild(btnSteam);
+  }
+
+  /* ── CS2 video settings ──────────────────────────────────────── */
+  const cVideo = card("CS2 Video Settings", '⚠ Edits cs2_video.txt of the selected Steam account. CS2 must be closed when you click "Write to CS2".');
+  {
+    const presetSel = videoSelect("vid_preset", "Preset", []);
+    (presetSel.querySelector("option") as HTMLOptionElement).textContent = "— choose —";
+    cVideo.appendChild(presetSel);
+    invoke<VideoPreset[]>("list_video_presets")
+      .then((presets) => {
+        const sel = presetSel.querySelector("select") as HTMLSelectElement;
+        for (const p of presets) {
+          const o = document.createElement("option");
+          o.value = p.id;
+          o.textContent = p.name;
+          o.title = p.description;
+          sel.appendChild(o);
+        }
+        sel.addEventListener("change", () => {
+          const p = presets.find((x) => x.id === sel.value);
+          if (!p) return;
+          // A preset leaves resolution and display mode to the player
+          setVideoValues({ ...collectVideoSettings(), ...p.settings });
+          toast(`Preset "${p.name}" loaded — click "Write to CS2" to apply`);
+        });
+      })
+      .catch(console.error);
+    cVideo.appendChild(numInput("vid_width", "Resolution width", ""));
+    cVideo.appendChild(numInput("vid_height", "Resolution height", ""));
+    for (const f of VIDEO_FIELDS) cVideo.appendChild(videoSelect(`vid_${f.key}`, f.label, f.options));
+    const btnRead = document.createElement("button");
+    btnRead.className = "card-apply-btn card-recommend-btn";
+    btnRead.innerHTML = "📥 Read from CS2";
+    btnRead.title = "Load the current values from cs2_video.txt";
+    btnRead.addEventListener("click", readVideoSettings);
+    cVideo.appendChild(btnRead);
+    const btnWrite = document.createElement("button");
+    btnWrite.className = "card-apply-btn";
+    btnWrite.innerHTML = "💾 Write to CS2";
+    btnWrite.title = "Write the chosen values (a backup of each file is kept next to it)";
+    btnWrite.addEventListener("click", writeVideoSettings);
+    cVideo.appendChild(btnWrite);
   }
 
   /* ── 8. Extras / FACEIT ──────────────────────────────────────── */
   const c8 = card("Extras & FACEIT", '⚠ Changes are only applied when you click "Apply Extras".');
   c8.dataset.section = "extras";
   c8.appendChild(infoRow("FACEIT Anti-Cheat status", TIP.x_faceit));
+  {
+    const btnCompat = document.createElement("button");
+    btnCompat.className = "card-apply-btn";
+    btnCompat.innerHTML = "🛡 Competitive check";
+    btnCompat.title = "Check this profile, running programs and the CFG against FACEIT, ESEA and matchmaking rules";
+    btnCompat.addEventListener("click", checkCompetitiveCompat);
+    c8.appendChild(btnCompat);
+  }
   c8.appendChild(toggle("x_steam", "Disable Steam Overlay", true, TIP.x_steam));
   c8.appendChild(toggle("x_disc", "Disable Discord Overlay", true, TIP.x_disc));
   c8.appendChild(toggle("x_resp", "SystemResponsiveness = 0", true, TIP.x_resp));
@@ -6251,6 +7242,14 @@ function build() {
   btnRun2.textContent = "🛡 Apply All (Admin)";
   btnRun2.title = "Generate and execute ALL sections at once with Administrator privileges";
 
+  const preflightToggle = document.createElement("label");
+  preflightToggle.className = "preflight-toggle";
+  preflightToggle.title = "Create a System Restore point and export the registry keys a run touches to a dated backup folder before every admin run";
+  preflightToggle.innerHTML = `<input type="checkbox" ${preflightEnabled() ? "checked" : ""}/> Backup first`;
+  preflightToggle.querySelector("input")!.addEventListener("change", (e) => {
+    localStorage.setItem("csmooth_preflight", (e.target as HTMLInputElement).checked ? "on" : "off");
+  });
+
   /* ── Impact prediction bar ───────────────────────────────────── */
   const impactBar = document.createElement("div");
   impactBar.className = "impact-bar";
@@ -6261,12 +7260,13 @@ function build() {
   const btnRecAll = document.createElement("button");
   btnRecAll.className = "btn-recommend-all";
   btnRecAll.innerHTML = "⚡ One-Click Config";
-  btnRecAll.title = "Apply ALL recommended settings based on your hardware (Windows + Network + NVIDIA + Services + Autoexec + Launch + Extras)";
+  btnRecAll.title = "Apply ALL recommended settings based on your hardware (Windows + Network + GPU driver + Services + Autoexec + Launch + Extras)";
   btnRecAll.addEventListener("click", applyAllSysRecommendations);
 
   actions.appendChild(btnExport);
   actions.appendChild(btnImport2);
   actions.appendChild(btnRun2);
+  actions.appendChild(preflightToggle);
   actions.appendChild(btnRecAll);
   actions.appendChild(impactBar);
 
@@ -6295,6 +7295,10 @@ function build() {
   sysPane1.className = "app-main";
   sysPane1.appendChild(c3);
   sysPane1.appendChild(c4);
+  sysPane1.appendChild(c4amd);
+  sysPane1.appendChild(c4intel);
+  // Only the driver card of the detected GPU vendor is offered
+  getHwProfile().then((hw) => offerGpuSection(hw.gpuVendor)).catch(console.error);
   sysSub.panels[1].appendChild(sysPane1);
 
   // sub 2: Services & Config
@@ -6303,6 +7307,7 @@ function build() {
   sysPane2.appendChild(c5);
   sysPane2.appendChild(c6);
   sysPane2.appendChild(c7);
+  sysPane2.appendChild(cVideo);
   sysSub.panels[2].appendChild(sysPane2);
 
   // sub 3: Extras & Tips
@@ -6390,7 +7395,7 @@ function build() {
   }
 
   /* ── Enable drag reorder within SYS cards ────────────────────── */
-  [c2, c3, c4, c5, c6, c7, c8].forEach((sysCard) => {
+  [c2, c3, c4, c4amd, c4intel, c5, c6, c7, c8].forEach((sysCard) => {
     restoreSysCardOrder(sysCard);
     enableCardDrag(sysCard, (ids) => {
       const sec = sysCard.dataset.section;
@@ -6883,7 +7888,7 @@ function build() {
         if (el?.checked) cfgStates.push(`${c.label}${vel ? " = " + vel.value : ""}`);
       }
     }
-    const context = `Hardware:\n${hwData}\n\nActive config commands:\n${cfgStates.join("\n")}`;
+    const context = `Hardware:\n${hwData}\n\nActive config commands:\n${cfgStates.join("\n")}${benchAdvisorContext()}`;
     try {
       const result = await advChat(ADV_PROMPTS.cfg, context);
       cfgAdvResp.innerHTML = mdToHtml(result);
@@ -7196,7 +8201,7 @@ function build() {
     btnHwAdv.disabled = true;
     hwAdvResp.innerHTML = '<div class="adv-loading">Analyzing hardware...</div>';
     try {
-      const result = await advChat(ADV_PROMPTS.hw, hwText);
+      const result = await advChat(ADV_PROMPTS.hw, hwText + benchAdvisorContext());
       hwAdvResp.innerHTML = mdToHtml(result);
     } catch (e) {
       hwAdvResp.innerHTML = `<span style="color:#ef4444">Error: ${e}</span>`;
@@ -7505,7 +8510,7 @@ function build() {
       const msg = await invoke<string>("apply_dns", { primary: preset.p, secondary: preset.s });
       dnsStatus.textContent = `✅ ${msg}`;
       toast(`DNS set to ${preset.label}`);
-    } catch (e) { dnsStatus.textContent = `❌ ${e}`; toast(String(e), true); }
+    } catch (e) { dnsStatus.textContent = `❌ ${errorText(e)}`; toast(errorText(e), true); }
     btnApplyDns.disabled = false;
   });
   btnRestoreDns.addEventListener("click", async () => {
@@ -7694,8 +8699,48 @@ function build() {
   demoCount.style.cssText = "font-size:10px;opacity:0.5;";
   demoCount.textContent = "Select your CS2/replays folder or a custom folder with .dem files";
 
+  // Match share code → replay download through the configured endpoint
+  const demoCodeInput = document.createElement("input");
+  demoCodeInput.type = "text";
+  demoCodeInput.placeholder = "CSGO-xxxxx-xxxxx-xxxxx-xxxxx-xxxxx";
+  demoCodeInput.title = "Paste a match share code to download its replay";
+
+  const demoEndpointInput = document.createElement("input");
+  demoEndpointInput.type = "text";
+  demoEndpointInput.placeholder = "Replay endpoint, e.g. https://host/replay?match={match_id}&outcome={outcome_id}&token={token}";
+  demoEndpointInput.value = localStorage.getItem("csmooth_replay_endpoint") || "";
+  demoEndpointInput.addEventListener("change", () => {
+    localStorage.setItem("csmooth_replay_endpoint", demoEndpointInput.value.trim());
+  });
+
+  const btnDemoDownload = document.createElement("button");
+  btnDemoDownload.className = "btn-import";
+  btnDemoDownload.textContent = "Download";
+  btnDemoDownload.title = "Download and decompress the replay into the selected folder";
+  btnDemoDownload.addEventListener("click", async () => {
+    const code = demoCodeInput.value.trim();
+    if (!code) return;
+    if (!currentDemoFolder) {
+      toast("Select a replays folder first", true);
+      return;
+    }
+    try {
+      const info = await invoke<{ match_id: string }>("decode_match_code", { code });
+      toast(`Downloading match ${info.match_id}…`);
+      await invoke("download_match_demo", { code, folder: currentDemoFolder, endpoint: demoEndpointInput.value.trim() });
+      demoCodeInput.value = "";
+      toast("Replay downloaded");
+      scanDemoFolder(currentDemoFolder);
+    } catch (e) {
+      toast(`Replay download failed: ${errorText(e)}`, true);
+    }
+  });
+
   demoToolbar.appendChild(btnDemoFolder);
   demoToolbar.appendChild(btnDemoRefresh);
+  demoToolbar.appendChild(demoCodeInput);
+  demoToolbar.appendChild(btnDemoDownload);
+  demoToolbar.appendChild(demoEndpointInput);
   demoToolbar.appendChild(demoPathEl);
   demoToolbar.appendChild(demoCount);
   demoSub.panels[0].appendChild(demoToolbar);
@@ -7853,7 +8898,7 @@ function build() {
   const btnBenchImport = document.createElement("button");
   btnBenchImport.className = "btn-export";
   btnBenchImport.textContent = "📂 Import CSV/JSON";
-  btnBenchImport.title = "Import PresentMon CSV or CapFrameX JSON file";
+  btnBenchImport.title = "Import a PresentMon, FrameView, OCAT or MangoHud CSV, or a CapFrameX JSON file";
   btnBenchImport.addEventListener("click", importBenchmarkFile);
 
   const btnBenchCX = document.createElement("button");
@@ -7872,16 +8917,30 @@ function build() {
     const el = document.getElementById("bench-content");
     if (el)
       el.innerHTML =
-        '<div class="bench-empty"><div class="bench-empty-icon">📊</div><div class="bench-empty-title">Benchmark & Frame Analysis</div><div class="bench-empty-desc">Import a PresentMon (.csv) or CapFrameX (.json) file to analyze frame times, FPS and stutters of your system in CS2.</div><div class="bench-empty-hint">Lightweight alternative to CapFrameX — analyzes the same data with full metrics and interactive charts.</div><div class="bench-compat"><span class="bench-compat-item">✅ PresentMon CSV</span><span class="bench-compat-item">✅ CapFrameX JSON</span><span class="bench-compat-item">✅ OCAT CSV</span><span class="bench-compat-item">✅ FrameView CSV</span></div></div>';
+        '<div class="bench-empty"><div class="bench-empty-icon">📊</div><div class="bench-empty-title">Benchmark & Frame Analysis</div><div class="bench-empty-desc">Import a PresentMon (.csv) or CapFrameX (.json) file to analyze frame times, FPS and stutters of your system in CS2.</div><div class="bench-empty-hint">Lightweight alternative to CapFrameX — analyzes the same data with full metrics and interactive charts.</div><div class="bench-compat"><span class="bench-compat-item">✅ PresentMon CSV</span><span class="bench-compat-item">✅ CapFrameX JSON</span><span class="bench-compat-item">✅ PresentMon 2.x CSV</span><span class="bench-compat-item">✅ OCAT CSV</span><span class="bench-compat-item">✅ FrameView CSV</span><span class="bench-compat-item">✅ MangoHud CSV</span></div></div>';
   });
 
+  const btnBenchCompare = document.createElement("button");
+  btnBenchCompare.className = "btn-import";
+  btnBenchCompare.textContent = "⚖ Compare";
+  btnBenchCompare.title = "Compare baseline and candidate runs: deltas with confidence intervals and a significance test";
+  btnBenchCompare.addEventListener("click", () => showBenchCompareModal());
+
+  const btnBenchBisect = document.createElement("button");
+  btnBenchBisect.className = "btn-import";
+  btnBenchBisect.textContent = "🔬 Bisect tweaks";
+  btnBenchBisect.title = "Find which tweaks of the profile actually changed avg FPS or 1% lows, one capture per step";
+  btnBenchBisect.addEventListener("click", openBisection);
+
   const benchInfo = document.createElement("span");
   benchInfo.style.cssText = "font-size:10px;opacity:0.5;flex:1;";
-  benchInfo.textContent = "Compatible with PresentMon, CapFrameX, OCAT and FrameView. Frame time, FPS, percentile and stutter analysis.";
+  benchInfo.textContent = "Compatible with PresentMon 1.x/2.x, CapFrameX, OCAT, FrameView and MangoHud. Frame time, FPS, percentile and stutter analysis.";
 
   benchToolbar.appendChild(btnBenchImport);
   benchToolbar.appendChild(btnBenchCX);
   benchToolbar.appendChild(btnBenchClear);
+  benchToolbar.appendChild(btnBenchCompare);
+  benchToolbar.appendChild(btnBenchBisect);
   benchToolbar.appendChild(benchInfo);
   tabBench.appendChild(benchToolbar);
 
@@ -7889,7 +8948,7 @@ function build() {
   benchContent.id = "bench-content";
   benchContent.style.cssText = "padding:0 12px;flex:1;overflow-y:auto;";
   benchContent.innerHTML =
-    '<div class="bench-empty"><div class="bench-empty-icon">📊</div><div class="bench-empty-title">Benchmark & Frame Analysis</div><div class="bench-empty-desc">Import a PresentMon (.csv) or CapFrameX (.json) file to analyze frame times, FPS and stutters on your CS2 system.</div><div class="bench-empty-hint">Lightweight alternative to CapFrameX — analyzes the same data with full metrics and interactive charts.</div><div class="bench-compat"><span class="bench-compat-item">✅ PresentMon CSV</span><span class="bench-compat-item">✅ CapFrameX JSON</span><span class="bench-compat-item">✅ OCAT CSV</span><span class="bench-compat-item">✅ FrameView CSV</span></div></div>';
+    '<div class="bench-empty"><div class="bench-empty-icon">📊</div><div class="bench-empty-title">Benchmark & Frame Analysis</div><div class="bench-empty-desc">Import a PresentMon (.csv) or CapFrameX (.json) file to analyze frame times, FPS and stutters on your CS2 system.</div><div class="bench-empty-hint">Lightweight alternative to CapFrameX — analyzes the same data with full metrics and interactive charts.</div><div class="bench-compat"><span class="bench-compat-item">✅ PresentMon CSV</span><span class="bench-compat-item">✅ CapFrameX JSON</span><span class="bench-compat-item">✅ PresentMon 2.x CSV</span><span class="bench-compat-item">✅ OCAT CSV</span><span class="bench-compat-item">✅ FrameView CSV</span><span class="bench-compat-item">✅ MangoHud CSV</span></div></div>';
   tabBench.appendChild(benchContent);
 
   const benchSig = document.createElement("section");
@@ -7967,6 +9026,7 @@ function build() {
     const schema = getActiveSchema();
     if (!schema) { toast("No schema selected"); return; }
     saveSchemaValues(schema.id, collectFullState());
+    backupSchemaToBackend(getActiveSchema() || schema);
     toast(`Schema "${schema.name}" saved ✔`);
   });
 
@@ -7991,6 +9051,7 @@ function build() {
     const newName = await showInputModal("Rename Schema", schema.name);
     if (!newName) return;
     renameSchema(schema.id, newName);
+    invoke("rename_profile", { name: schema.name, newName }).catch(console.error);
     refreshProfileDropdown();
     toast(`Renamed to "${newName}" ✔`);
   });
@@ -8005,6 +9066,7 @@ function build() {
     const ok = await showConfirmModal("Delete Schema", `Are you sure you want to delete <strong>${schema.name}</strong>?`);
     if (!ok) return;
     deleteSchema(schema.id);
+    invoke("delete_profile", { name: schema.name }).catch(console.error);
     const remaining = getSchemas();
     if (remaining.length > 0) {
       setActiveSchemaId(remaining[0].id);
@@ -8028,6 +9090,7 @@ function build() {
     if (existing) {
       saveSchemaValues(existing.id, collectFullState());
       setActiveSchemaId(existing.id);
+      backupSchemaToBackend(getActiveSchema() || existing);
       toast(`Schema "${trimmed}" updated ✔`);
     } else {
       const currentActive = getActiveSchema();
@@ -8042,6 +9105,7 @@ function build() {
       }
       saveSchemaValues(s.id, collectFullState());
       setActiveSchemaId(s.id);
+      backupSchemaToBackend(getActiveSchema() || s);
       toast(`Schema "${trimmed}" created ✔`);
     }
     refreshProfileDropdown();